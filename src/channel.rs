@@ -0,0 +1,401 @@
+//! Typed channels which send and receive Serde-serializable values.
+//!
+//! [`IpcSender`] serializes values with a [`Codec`] and writes every one of them as a separate message, while [`IpcReceiver`] reads those messages back and deserializes them. Message boundaries are provided by the transport: byte streams, like local sockets, Ud-socket streams and unnamed pipes, are wrapped into the length-prefixed framing from the [`framing`] module, while message-oriented primitives, like Ud-socket datagram sockets and message-mode named pipes, are used as-is via the [`WriteMsg`] and [`ReliableReadMsg`] traits.
+//!
+//! Two codecs are provided out of the box: [`Bincode`], which is compact and fast and is used by default, and [`Json`], which is human-readable and interoperates with programs not written in Rust. Other serialization formats can be plugged in by implementing the [`Codec`] trait.
+//!
+//! # Example
+//! ```no_run
+//! use interprocess::{
+//!     channel::{IpcReceiver, IpcSender},
+//!     local_socket::{LocalSocketListener, LocalSocketStream},
+//! };
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug)]
+//! enum Request {
+//!     Ping,
+//!     Echo(String),
+//! }
+//!
+//! // Client side
+//! let (_, writer) = LocalSocketStream::connect("/tmp/example.sock")?.into_split();
+//! let mut sender = IpcSender::<Request, _>::from_stream(writer);
+//! sender.send(&Request::Echo("Hello from client!".to_string()))?;
+//!
+//! // Server side
+//! let listener = LocalSocketListener::bind("/tmp/example.sock")?;
+//! let mut receiver = IpcReceiver::<Request, _>::from_stream(listener.accept()?);
+//! println!("Got a request: {:?}", receiver.recv()?);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`IpcSender`]: struct.IpcSender.html " "
+//! [`IpcReceiver`]: struct.IpcReceiver.html " "
+//! [`Codec`]: trait.Codec.html " "
+//! [`Bincode`]: struct.Bincode.html " "
+//! [`Json`]: struct.Json.html " "
+//! [`framing`]: ../framing/index.html " "
+//! [`WriteMsg`]: ../trait.WriteMsg.html " "
+//! [`ReliableReadMsg`]: ../trait.ReliableReadMsg.html " "
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::{self, Debug, Formatter},
+    io::{self, Read, Write},
+    marker::PhantomData,
+};
+use crate::{
+    framing::{FramedReader, FramedWriter},
+    ReliableReadMsg, WriteMsg,
+};
+
+/// A serialization format used by typed channels.
+///
+/// Implementing this trait allows [`IpcSender`] and [`IpcReceiver`] to use formats other than the built-in ones.
+///
+/// [`IpcSender`]: struct.IpcSender.html " "
+/// [`IpcReceiver`]: struct.IpcReceiver.html " "
+pub trait Codec {
+    /// Serializes the specified value, appending the result to the buffer.
+    ///
+    /// Errors should have the [`InvalidInput`] kind unless they originate from I/O.
+    ///
+    /// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+    fn encode<T: Serialize + ?Sized>(&self, value: &T, buf: &mut Vec<u8>) -> io::Result<()>;
+    /// Deserializes a value from one whole message.
+    ///
+    /// Errors should have the [`InvalidData`] kind unless they originate from I/O.
+    ///
+    /// [`InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData " "
+    fn decode<T: DeserializeOwned>(&self, msg: &[u8]) -> io::Result<T>;
+}
+
+/// The [Bincode] serialization format, used by typed channels by default.
+///
+/// [Bincode]: https://docs.rs/bincode " "
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bincode;
+impl Bincode {
+    fn convert_error(error: bincode::ErrorKind, kind: io::ErrorKind) -> io::Error {
+        match error {
+            bincode::ErrorKind::Io(error) => error,
+            error => io::Error::new(kind, error),
+        }
+    }
+}
+impl Codec for Bincode {
+    #[inline]
+    fn encode<T: Serialize + ?Sized>(&self, value: &T, buf: &mut Vec<u8>) -> io::Result<()> {
+        bincode::serialize_into(buf, value)
+            .map_err(|e| Self::convert_error(*e, io::ErrorKind::InvalidInput))
+    }
+    #[inline]
+    fn decode<T: DeserializeOwned>(&self, msg: &[u8]) -> io::Result<T> {
+        bincode::deserialize(msg).map_err(|e| Self::convert_error(*e, io::ErrorKind::InvalidData))
+    }
+}
+
+/// The [JSON] serialization format, with every value being sent as one JSON document.
+///
+/// [JSON]: https://docs.rs/serde_json " "
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Json;
+impl Codec for Json {
+    #[inline]
+    fn encode<T: Serialize + ?Sized>(&self, value: &T, buf: &mut Vec<u8>) -> io::Result<()> {
+        serde_json::to_writer(buf, value).map_err(|e| {
+            if e.is_io() {
+                e.into()
+            } else {
+                io::Error::new(io::ErrorKind::InvalidInput, e)
+            }
+        })
+    }
+    #[inline]
+    fn decode<T: DeserializeOwned>(&self, msg: &[u8]) -> io::Result<T> {
+        serde_json::from_slice(msg).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// The sending half of a typed channel, writing values of type `T` into a message-oriented transport `W` using the codec `C`.
+///
+/// Byte streams are turned into message-oriented transports by wrapping them into a [`FramedWriter`], which is what [`from_stream`] does. Ud-socket datagram sockets and message-mode named pipes can be used directly with [`new`].
+///
+/// # Example
+/// Sending values to a child process through an unnamed pipe:
+/// ```no_run
+/// use interprocess::{channel::IpcSender, unnamed_pipe::pipe};
+///
+/// let (writer, _reader) = pipe()?;
+/// // The reader is then passed to a child process...
+/// let mut sender = IpcSender::<Vec<u32>, _>::from_stream(writer);
+/// sender.send(&vec![1, 2, 3])?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`FramedWriter`]: ../framing/struct.FramedWriter.html " "
+/// [`from_stream`]: #method.from_stream " "
+/// [`new`]: #method.new " "
+pub struct IpcSender<T, W, C = Bincode> {
+    inner: W,
+    codec: C,
+    buffer: Vec<u8>,
+    _phantom: PhantomData<fn(&T)>,
+}
+impl<T, W, C: Default> IpcSender<T, W, C> {
+    /// Creates a sender which writes into the specified message-oriented transport, using the default configuration of the codec.
+    #[inline(always)]
+    pub fn new(inner: W) -> Self {
+        Self::with_codec(inner, C::default())
+    }
+}
+impl<T, W: Write, C: Default> IpcSender<T, FramedWriter<W>, C> {
+    /// Creates a sender which writes length-prefixed frames into the specified byte stream, using the default framing options and the default configuration of the codec.
+    ///
+    /// To use other framing options, wrap the stream into a [`FramedWriter`] manually and use [`new`] or [`with_codec`].
+    ///
+    /// [`FramedWriter`]: ../framing/struct.FramedWriter.html " "
+    /// [`new`]: #method.new " "
+    /// [`with_codec`]: #method.with_codec " "
+    #[inline(always)]
+    pub fn from_stream(stream: W) -> Self {
+        Self::new(FramedWriter::new(stream))
+    }
+}
+impl<T, W, C> IpcSender<T, W, C> {
+    /// Creates a sender which writes into the specified message-oriented transport, using the specified codec.
+    #[inline]
+    pub fn with_codec(inner: W, codec: C) -> Self {
+        Self {
+            inner,
+            codec,
+            buffer: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+    /// Returns the codec used by the sender.
+    #[inline(always)]
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+    /// Returns a shared reference to the underlying transport.
+    #[inline(always)]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+    /// Returns a mutable reference to the underlying transport. Writing into it directly is allowed only as long as the other side expects it.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+    /// Unwraps the underlying transport.
+    #[inline(always)]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+impl<T: Serialize, W: WriteMsg, C: Codec> IpcSender<T, W, C> {
+    /// Serializes the specified value and sends it as one message.
+    ///
+    /// Serialization errors are reported with the [`InvalidInput`] error kind and leave the transport untouched. Errors from the transport are returned as-is; see its [`write_msg`] for how partially written messages are handled.
+    ///
+    /// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+    /// [`write_msg`]: ../trait.WriteMsg.html#tymethod.write_msg " "
+    pub fn send(&mut self, value: &T) -> io::Result<()> {
+        self.buffer.clear();
+        self.codec.encode(value, &mut self.buffer)?;
+        self.inner.write_msg(&self.buffer)
+    }
+}
+impl<T, W: Debug, C: Debug> Debug for IpcSender<T, W, C> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IpcSender")
+            .field("inner", &self.inner)
+            .field("codec", &self.codec)
+            .finish()
+    }
+}
+
+/// The receiving half of a typed channel, reading values of type `T` from a message-oriented transport `R` using the codec `C`.
+///
+/// Byte streams are turned into message-oriented transports by wrapping them into a [`FramedReader`], which is what [`from_stream`] does. Ud-socket datagram sockets (on Linux) and message-mode named pipes can be used directly with [`new`], since they implement [`ReliableReadMsg`].
+///
+/// # Example
+/// Receiving values from a Ud-socket datagram socket:
+/// ```no_run
+/// # #[cfg(target_os = "linux")] {
+/// use interprocess::{channel::{IpcReceiver, Json}, os::unix::udsocket::UdSocket};
+///
+/// let socket = UdSocket::bind("/tmp/example.sock")?;
+/// let mut receiver = IpcReceiver::<String, _, Json>::new(socket);
+/// loop {
+///     println!("Got a string: {}", receiver.recv()?);
+/// }
+/// # }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`FramedReader`]: ../framing/struct.FramedReader.html " "
+/// [`ReliableReadMsg`]: ../trait.ReliableReadMsg.html " "
+/// [`from_stream`]: #method.from_stream " "
+/// [`new`]: #method.new " "
+pub struct IpcReceiver<T, R, C = Bincode> {
+    inner: R,
+    codec: C,
+    buffer: Vec<u8>,
+    _phantom: PhantomData<fn() -> T>,
+}
+impl<T, R, C: Default> IpcReceiver<T, R, C> {
+    /// Creates a receiver which reads from the specified message-oriented transport, using the default configuration of the codec.
+    #[inline(always)]
+    pub fn new(inner: R) -> Self {
+        Self::with_codec(inner, C::default())
+    }
+}
+impl<T, R: Read, C: Default> IpcReceiver<T, FramedReader<R>, C> {
+    /// Creates a receiver which reads length-prefixed frames from the specified byte stream, using the default framing options and the default configuration of the codec.
+    ///
+    /// To use other framing options, wrap the stream into a [`FramedReader`] manually and use [`new`] or [`with_codec`].
+    ///
+    /// [`FramedReader`]: ../framing/struct.FramedReader.html " "
+    /// [`new`]: #method.new " "
+    /// [`with_codec`]: #method.with_codec " "
+    #[inline(always)]
+    pub fn from_stream(stream: R) -> Self {
+        Self::new(FramedReader::new(stream))
+    }
+}
+impl<T, R, C> IpcReceiver<T, R, C> {
+    /// Creates a receiver which reads from the specified message-oriented transport, using the specified codec.
+    #[inline]
+    pub fn with_codec(inner: R, codec: C) -> Self {
+        Self {
+            inner,
+            codec,
+            buffer: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+    /// Returns the codec used by the receiver.
+    #[inline(always)]
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+    /// Returns a shared reference to the underlying transport.
+    #[inline(always)]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+    /// Returns a mutable reference to the underlying transport. Reading from it directly is allowed only as long as the other side expects it.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+    /// Unwraps the underlying transport.
+    #[inline(always)]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+impl<T: DeserializeOwned, R: ReliableReadMsg, C: Codec> IpcReceiver<T, R, C> {
+    /// Receives one message and deserializes a value from it.
+    ///
+    /// The receive buffer grows to fit the largest message received so far and is reused between calls. If the message could not be deserialized, an error of kind [`InvalidData`] is returned and the message is discarded, so that the next call receives the next message. Errors from the transport are returned as-is — in particular, a byte stream which was closed by the other side produces an error of kind [`UnexpectedEof`].
+    ///
+    /// [`InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData " "
+    /// [`UnexpectedEof`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof " "
+    pub fn recv(&mut self) -> io::Result<T> {
+        let size = match self.inner.read_msg(&mut self.buffer)? {
+            Ok(size) => size,
+            Err(bigger_buffer) => {
+                self.buffer = bigger_buffer;
+                self.buffer.len()
+            }
+        };
+        self.codec.decode(&self.buffer[..size])
+    }
+}
+impl<T, R: Debug, C: Debug> Debug for IpcReceiver<T, R, C> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IpcReceiver")
+            .field("inner", &self.inner)
+            .field("codec", &self.codec)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::unnamed_pipe::pipe;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Message {
+        Empty,
+        Number(u64),
+        Text(String),
+        List(Vec<i32>),
+    }
+    fn messages() -> Vec<Message> {
+        vec![
+            Message::Empty,
+            Message::Number(u64::MAX),
+            Message::Text("Hello from interprocess!".to_string()),
+            Message::List((-500..500).collect()),
+        ]
+    }
+
+    fn round_trip<C: Codec + Default>() {
+        let (writer, reader) = pipe().unwrap();
+        let mut sender = IpcSender::<Message, _, C>::from_stream(writer);
+        let mut receiver = IpcReceiver::<Message, _, C>::from_stream(reader);
+        for msg in messages() {
+            sender.send(&msg).unwrap();
+        }
+        for msg in messages() {
+            assert_eq!(receiver.recv().unwrap(), msg);
+        }
+        drop(sender);
+        assert_eq!(receiver.recv().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+    #[test]
+    fn bincode_over_pipe() {
+        round_trip::<Bincode>();
+    }
+    #[test]
+    fn json_over_pipe() {
+        round_trip::<Json>();
+    }
+    #[test]
+    fn invalid_data() {
+        let (writer, reader) = pipe().unwrap();
+        let mut sender = IpcSender::<serde_json::Value, _, Json>::from_stream(writer);
+        let mut receiver = IpcReceiver::<u32, _, Json>::from_stream(reader);
+        sender.send(&"not a number".into()).unwrap();
+        sender.send(&1.into()).unwrap();
+        assert_eq!(receiver.recv().unwrap_err().kind(), io::ErrorKind::InvalidData);
+        // The bad message must have been consumed
+        assert_eq!(receiver.recv().unwrap(), 1);
+    }
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn over_udsocket() {
+        use crate::os::unix::udsocket::UdSocket;
+        let path = format!("/tmp/{}.sock", crate::rand_string(10));
+        let server = UdSocket::bind(path.as_str()).unwrap();
+        let client = UdSocket::connect(path.as_str()).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let mut sender = IpcSender::<Message, _>::new(client);
+        let mut receiver = IpcReceiver::<Message, _>::new(server);
+        for msg in messages() {
+            sender.send(&msg).unwrap();
+        }
+        for msg in messages() {
+            assert_eq!(receiver.recv().unwrap(), msg);
+        }
+    }
+}
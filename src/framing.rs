@@ -0,0 +1,658 @@
+//! Length-prefixed message framing over byte streams.
+//!
+//! Byte streams, such as local sockets or unnamed pipes, don't preserve the boundaries between the chunks of data written into them, which is why a protocol has to be layered on top of them to send discrete messages. The simplest such protocol is prefixing every message, or *frame*, with its length. This module implements exactly that, with a configurable width and byte order of the length prefix.
+//!
+//! [`FramedReader`] reads frames from any [`Read`] and implements [`ReliableReadMsg`], which means that code written for message-oriented IPC primitives, like message-mode named pipes or Ud-socket datagrams, can be reused with byte streams as well. [`FramedWriter`] writes frames into any [`Write`]. With the `nonblocking` feature enabled, the same types also serve as a [`Stream`] of frames over any [`AsyncRead`] and a [`Sink`] of frames over any [`AsyncWrite`], respectively.
+//!
+//! Both sides remember their progress within the current frame between calls, so a frame which was only partially transferred because of a non-blocking stream or a timeout is resumed by the next call rather than leaving the stream desynchronized. Reading from or writing to the underlying stream directly while it's wrapped, however, breaks the framing.
+//!
+//! # Example
+//! ```no_run
+//! use interprocess::{
+//!     local_socket::LocalSocketStream,
+//!     framing::{FramedReader, FramedWriter},
+//! };
+//!
+//! let (reader, writer) = LocalSocketStream::connect("/tmp/example.sock")?.into_split();
+//! let (mut reader, mut writer) = (FramedReader::new(reader), FramedWriter::new(writer));
+//! writer.write_msg(b"Hello from client!")?;
+//! if let Some(answer) = reader.read_frame()? {
+//!     println!("Server answered: {}", String::from_utf8_lossy(&answer));
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`FramedReader`]: struct.FramedReader.html " "
+//! [`FramedWriter`]: struct.FramedWriter.html " "
+//! [`ReliableReadMsg`]: ../trait.ReliableReadMsg.html " "
+//! [`Read`]: https://doc.rust-lang.org/std/io/trait.Read.html " "
+//! [`Write`]: https://doc.rust-lang.org/std/io/trait.Write.html " "
+//! [`Stream`]: https://docs.rs/futures/*/futures/stream/trait.Stream.html " "
+//! [`Sink`]: https://docs.rs/futures/*/futures/sink/trait.Sink.html " "
+//! [`AsyncRead`]: https://docs.rs/futures/*/futures/io/trait.AsyncRead.html " "
+//! [`AsyncWrite`]: https://docs.rs/futures/*/futures/io/trait.AsyncWrite.html " "
+
+#[cfg(feature = "nonblocking")]
+use futures::{
+    io::{AsyncRead, AsyncWrite},
+    sink::Sink,
+    stream::Stream,
+};
+#[cfg(feature = "nonblocking")]
+use std::{pin::Pin, task::Context};
+use std::{
+    io::{self, Read, Write},
+    task::Poll,
+};
+use crate::{ReliableReadMsg, WriteMsg, Sealed};
+
+/// The width of the length prefix of every frame, which limits the maximum size of a frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PrefixWidth {
+    /// 1 byte, for frames of up to 255 bytes.
+    U8,
+    /// 2 bytes, for frames of up to 64 KiB.
+    U16,
+    /// 4 bytes, for frames of up to 4 GiB.
+    U32,
+    /// 8 bytes, which is wide enough for any frame.
+    U64,
+}
+impl PrefixWidth {
+    /// Returns the size of the prefix in bytes.
+    #[inline]
+    pub fn size(self) -> usize {
+        match self {
+            Self::U8 => 1,
+            Self::U16 => 2,
+            Self::U32 => 4,
+            Self::U64 => 8,
+        }
+    }
+    /// Returns the largest frame size which can be expressed by a prefix of this width.
+    #[inline]
+    pub fn max_frame_size(self) -> u64 {
+        match self {
+            Self::U8 => u8::MAX as u64,
+            Self::U16 => u16::MAX as u64,
+            Self::U32 => u32::MAX as u64,
+            Self::U64 => u64::MAX,
+        }
+    }
+}
+
+/// The byte order of the length prefix of every frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// The most significant byte comes first. Also known as network byte order.
+    Big,
+    /// The least significant byte comes first.
+    Little,
+}
+
+/// The format of the frames, which must be the same on both sides of the stream.
+///
+/// The default is a 4-byte big-endian length prefix and a maximum frame size of 16 MiB.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct FramingOptions {
+    /// The width of the length prefix.
+    pub prefix_width: PrefixWidth,
+    /// The byte order of the length prefix.
+    pub endianness: Endianness,
+    /// The maximum size of a frame, not including the length prefix. A reader fails with an error of kind [`InvalidData`] upon receiving a length prefix which exceeds it, before allocating any memory for the frame, which prevents the other side from making the reader run out of memory. A writer refuses to send such frames with an error of kind [`InvalidInput`].
+    ///
+    /// [`InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData " "
+    /// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+    pub max_frame_size: usize,
+}
+impl FramingOptions {
+    /// Creates a new builder with default options.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            prefix_width: PrefixWidth::U32,
+            endianness: Endianness::Big,
+            max_frame_size: 16 * 1024 * 1024,
+        }
+    }
+    /// Sets the [`prefix_width`] parameter to the specified value.
+    ///
+    /// [`prefix_width`]: #structfield.prefix_width " "
+    #[inline]
+    #[must_use = "builder setters take the entire structure and return the result"]
+    pub fn prefix_width(mut self, prefix_width: PrefixWidth) -> Self {
+        self.prefix_width = prefix_width;
+        self
+    }
+    /// Sets the [`endianness`] parameter to the specified value.
+    ///
+    /// [`endianness`]: #structfield.endianness " "
+    #[inline]
+    #[must_use = "builder setters take the entire structure and return the result"]
+    pub fn endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+    /// Sets the [`max_frame_size`] parameter to the specified value.
+    ///
+    /// [`max_frame_size`]: #structfield.max_frame_size " "
+    #[inline]
+    #[must_use = "builder setters take the entire structure and return the result"]
+    pub fn max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size;
+        self
+    }
+    fn encode_prefix(&self, len: usize) -> io::Result<([u8; 8], usize)> {
+        let limit = (self.max_frame_size as u64).min(self.prefix_width.max_frame_size());
+        if len as u64 > limit {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("frame of {} bytes exceeds the maximum frame size of {} bytes", len, limit),
+            ));
+        }
+        let size = self.prefix_width.size();
+        let mut prefix = [0; 8];
+        match self.endianness {
+            Endianness::Big => prefix[..size].copy_from_slice(&(len as u64).to_be_bytes()[8 - size..]),
+            Endianness::Little => prefix[..size].copy_from_slice(&(len as u64).to_le_bytes()[..size]),
+        }
+        Ok((prefix, size))
+    }
+    fn decode_prefix(&self, prefix: &[u8]) -> io::Result<usize> {
+        let mut bytes = [0; 8];
+        let len = match self.endianness {
+            Endianness::Big => {
+                bytes[8 - prefix.len()..].copy_from_slice(prefix);
+                u64::from_be_bytes(bytes)
+            },
+            Endianness::Little => {
+                bytes[..prefix.len()].copy_from_slice(prefix);
+                u64::from_le_bytes(bytes)
+            },
+        };
+        if len > self.max_frame_size as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("received frame of {} bytes, which exceeds the maximum frame size of {} bytes", len, self.max_frame_size),
+            ));
+        }
+        Ok(len as usize)
+    }
+}
+impl Default for FramingOptions {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The memory for incoming frames is allocated in steps no bigger than this, so that a frame which the other side announced but never sent doesn't make the reader allocate all of it.
+const READ_CHUNK: usize = 64 * 1024;
+
+/// Reads length-prefixed frames from a byte stream.
+///
+/// Frames can be read with [`read_frame`] or via the [`ReliableReadMsg`] interface. With the `nonblocking` feature, `FramedReader` also implements [`Stream`] if the underlying stream implements [`AsyncRead`], yielding one frame at a time until the end of the stream.
+///
+/// # Example
+/// ```no_run
+/// use interprocess::{
+///     framing::{FramedReader, FramingOptions, PrefixWidth},
+///     local_socket::LocalSocketListener,
+///     ReliableReadMsg,
+/// };
+///
+/// let listener = LocalSocketListener::bind("/tmp/example.sock")?;
+/// let options = FramingOptions::new()
+///     .prefix_width(PrefixWidth::U16)
+///     .max_frame_size(4096);
+/// let mut reader = FramedReader::with_options(listener.accept()?, options);
+/// let mut buffer = Vec::new();
+/// loop {
+///     // Works just like it would with a message-mode named pipe.
+///     let size = match reader.read_msg(&mut buffer)? {
+///         Ok(size) => size,
+///         Err(bigger_buffer) => {
+///             buffer = bigger_buffer;
+///             buffer.len()
+///         },
+///     };
+///     println!("Got a message: {:?}", &buffer[..size]);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`read_frame`]: #method.read_frame " "
+/// [`ReliableReadMsg`]: ../trait.ReliableReadMsg.html " "
+/// [`Stream`]: https://docs.rs/futures/*/futures/stream/trait.Stream.html " "
+/// [`AsyncRead`]: https://docs.rs/futures/*/futures/io/trait.AsyncRead.html " "
+#[derive(Debug)]
+pub struct FramedReader<R> {
+    inner: R,
+    options: FramingOptions,
+    prefix: [u8; 8],
+    prefix_filled: usize,
+    // Known once the whole prefix has been read.
+    frame_len: Option<usize>,
+    frame: Vec<u8>,
+    frame_filled: usize,
+}
+impl<R> FramedReader<R> {
+    /// Wraps the specified stream, using the default [`FramingOptions`].
+    ///
+    /// [`FramingOptions`]: struct.FramingOptions.html " "
+    #[inline(always)]
+    pub fn new(inner: R) -> Self {
+        Self::with_options(inner, FramingOptions::new())
+    }
+    /// Wraps the specified stream, using the specified options.
+    #[inline]
+    pub fn with_options(inner: R, options: FramingOptions) -> Self {
+        Self {
+            inner,
+            options,
+            prefix: [0; 8],
+            prefix_filled: 0,
+            frame_len: None,
+            frame: Vec::new(),
+            frame_filled: 0,
+        }
+    }
+    /// Returns the options used by the reader.
+    #[inline(always)]
+    pub fn options(&self) -> &FramingOptions {
+        &self.options
+    }
+    /// Returns a shared reference to the underlying stream.
+    #[inline(always)]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+    /// Returns a mutable reference to the underlying stream. Reading from it directly breaks the framing.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+    /// Unwraps the underlying stream. Any part of a frame which was already read from it is lost.
+    #[inline(always)]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Drives reading of the current frame using the specified read function, resolving to `true` once the frame is complete or to `false` if the stream ended cleanly before a new frame began.
+    fn poll_fill(
+        &mut self,
+        mut read: impl FnMut(&mut R, &mut [u8]) -> Poll<io::Result<usize>>,
+    ) -> Poll<io::Result<bool>> {
+        let prefix_size = self.options.prefix_width.size();
+        let frame_len = loop {
+            if let Some(frame_len) = self.frame_len {
+                break frame_len;
+            }
+            let bytes_read = match read(&mut self.inner, &mut self.prefix[self.prefix_filled..prefix_size]) {
+                Poll::Ready(Ok(bytes_read)) => bytes_read,
+                Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+            if bytes_read == 0 {
+                return if self.prefix_filled == 0 {
+                    Poll::Ready(Ok(false))
+                } else {
+                    Poll::Ready(Err(unexpected_eof()))
+                };
+            }
+            self.prefix_filled += bytes_read;
+            if self.prefix_filled == prefix_size {
+                let frame_len = self.options.decode_prefix(&self.prefix[..prefix_size])?;
+                self.frame_len = Some(frame_len);
+                self.frame.clear();
+                self.frame_filled = 0;
+            }
+        };
+        while self.frame_filled < frame_len {
+            if self.frame_filled == self.frame.len() {
+                let new_len = frame_len.min(self.frame_filled + READ_CHUNK);
+                self.frame.resize(new_len, 0);
+            }
+            let bytes_read = match read(&mut self.inner, &mut self.frame[self.frame_filled..]) {
+                Poll::Ready(Ok(bytes_read)) => bytes_read,
+                Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+            if bytes_read == 0 {
+                return Poll::Ready(Err(unexpected_eof()));
+            }
+            self.frame_filled += bytes_read;
+        }
+        Poll::Ready(Ok(true))
+    }
+    /// Takes the completed frame out of the reader, making way for the next one.
+    fn take_frame(&mut self) -> Vec<u8> {
+        self.prefix_filled = 0;
+        self.frame_len = None;
+        self.frame_filled = 0;
+        std::mem::take(&mut self.frame)
+    }
+    fn discard_frame(&mut self) {
+        self.prefix_filled = 0;
+        self.frame_len = None;
+        self.frame_filled = 0;
+        self.frame.clear();
+    }
+}
+impl<R: Read> FramedReader<R> {
+    /// Reads one frame from the stream, returning `None` if the stream ended cleanly at a frame boundary.
+    ///
+    /// If the stream ends in the middle of a frame, an error of kind [`UnexpectedEof`] is returned. If any other error occurs, including [`WouldBlock`] and [`TimedOut`], the part of the frame which was already read is kept and the next call continues from there.
+    ///
+    /// [`UnexpectedEof`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof " "
+    /// [`WouldBlock`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.WouldBlock " "
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    pub fn read_frame(&mut self) -> io::Result<Option<Vec<u8>>> {
+        if self.fill_sync()? {
+            Ok(Some(self.take_frame()))
+        } else {
+            Ok(None)
+        }
+    }
+    fn fill_sync(&mut self) -> io::Result<bool> {
+        match self.poll_fill(|inner, buf| Poll::Ready(inner.read(buf))) {
+            Poll::Ready(result) => result,
+            Poll::Pending => unreachable!("synchronous reads never return Poll::Pending"),
+        }
+    }
+    /// Like `fill_sync`, but treats the end of the stream as an error, for the `ReliableReadMsg` interface which has no way of reporting it otherwise.
+    fn fill_sync_or_eof(&mut self) -> io::Result<usize> {
+        if self.fill_sync()? {
+            Ok(self.frame_filled)
+        } else {
+            Err(unexpected_eof())
+        }
+    }
+}
+/// The end of the stream is reported as an error of kind [`UnexpectedEof`], since the interface has no other way of signalling it. Use [`read_frame`] to tell a cleanly closed stream apart from a broken one.
+///
+/// [`UnexpectedEof`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof " "
+/// [`read_frame`]: #method.read_frame " "
+impl<R: Read> ReliableReadMsg for FramedReader<R> {
+    fn read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, Vec<u8>>> {
+        let len = self.fill_sync_or_eof()?;
+        if len <= buf.len() {
+            buf[..len].copy_from_slice(&self.frame[..len]);
+            self.discard_frame();
+            Ok(Ok(len))
+        } else {
+            Ok(Err(self.take_frame()))
+        }
+    }
+    /// If the frame doesn't fit into the buffer, it stays in the reader, and the next call with a big enough buffer receives it.
+    fn try_read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, usize>> {
+        let len = self.fill_sync_or_eof()?;
+        if len <= buf.len() {
+            buf[..len].copy_from_slice(&self.frame[..len]);
+            self.discard_frame();
+            Ok(Ok(len))
+        } else {
+            Ok(Err(len))
+        }
+    }
+}
+impl<R> Sealed for FramedReader<R> {}
+#[cfg(feature = "nonblocking")]
+impl<R: AsyncRead + Unpin> Stream for FramedReader<R> {
+    type Item = io::Result<Vec<u8>>;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        match this.poll_fill(|inner, buf| Pin::new(inner).poll_read(cx, buf)) {
+            Poll::Ready(Ok(true)) => Poll::Ready(Some(Ok(this.take_frame()))),
+            Poll::Ready(Ok(false)) => Poll::Ready(None),
+            Poll::Ready(Err(e)) => Poll::Ready(Some(Err(e))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Once this much data is buffered, the sink stops accepting new frames until some of it is written out.
+#[cfg(feature = "nonblocking")]
+const SINK_BACKPRESSURE: usize = 64 * 1024;
+
+/// Writes length-prefixed frames into a byte stream.
+///
+/// Frames are written with [`write_msg`]. With the `nonblocking` feature, `FramedWriter` also implements [`Sink`] for any byte slice-like type if the underlying stream implements [`AsyncWrite`]; the frames sent into the sink are buffered until it's flushed or until the buffer grows large enough.
+///
+/// # Example
+/// ```no_run
+/// use interprocess::{framing::FramedWriter, local_socket::LocalSocketStream};
+///
+/// let mut writer = FramedWriter::new(LocalSocketStream::connect("/tmp/example.sock")?);
+/// writer.write_msg(b"first message")?;
+/// writer.write_msg(b"second message")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`write_msg`]: #method.write_msg " "
+/// [`Sink`]: https://docs.rs/futures/*/futures/sink/trait.Sink.html " "
+/// [`AsyncWrite`]: https://docs.rs/futures/*/futures/io/trait.AsyncWrite.html " "
+#[derive(Debug)]
+pub struct FramedWriter<W> {
+    inner: W,
+    options: FramingOptions,
+    // Encoded frames which haven't been written out yet, starting at `written`.
+    buffer: Vec<u8>,
+    written: usize,
+}
+impl<W> FramedWriter<W> {
+    /// Wraps the specified stream, using the default [`FramingOptions`].
+    ///
+    /// [`FramingOptions`]: struct.FramingOptions.html " "
+    #[inline(always)]
+    pub fn new(inner: W) -> Self {
+        Self::with_options(inner, FramingOptions::new())
+    }
+    /// Wraps the specified stream, using the specified options.
+    #[inline]
+    pub fn with_options(inner: W, options: FramingOptions) -> Self {
+        Self {
+            inner,
+            options,
+            buffer: Vec::new(),
+            written: 0,
+        }
+    }
+    /// Returns the options used by the writer.
+    #[inline(always)]
+    pub fn options(&self) -> &FramingOptions {
+        &self.options
+    }
+    /// Returns a shared reference to the underlying stream.
+    #[inline(always)]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+    /// Returns a mutable reference to the underlying stream. Writing into it directly breaks the framing.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+    /// Unwraps the underlying stream. Any frames which haven't been written out yet are lost.
+    #[inline(always)]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn encode(&mut self, frame: &[u8]) -> io::Result<()> {
+        let (prefix, prefix_size) = self.options.encode_prefix(frame.len())?;
+        if self.written == self.buffer.len() {
+            self.buffer.clear();
+            self.written = 0;
+        }
+        self.buffer.reserve(prefix_size + frame.len());
+        self.buffer.extend_from_slice(&prefix[..prefix_size]);
+        self.buffer.extend_from_slice(frame);
+        Ok(())
+    }
+    /// Drives writing of the buffered frames using the specified write function.
+    fn poll_write_buffer(
+        &mut self,
+        mut write: impl FnMut(&mut W, &[u8]) -> Poll<io::Result<usize>>,
+    ) -> Poll<io::Result<()>> {
+        while self.written < self.buffer.len() {
+            match write(&mut self.inner, &self.buffer[self.written..]) {
+                Poll::Ready(Ok(0)) => return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write the whole frame",
+                ))),
+                Poll::Ready(Ok(bytes_written)) => self.written += bytes_written,
+                Poll::Ready(Err(e)) if e.kind() == io::ErrorKind::Interrupted => {},
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        self.buffer.clear();
+        self.written = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+impl<W: Write> FramedWriter<W> {
+    /// Writes one frame into the stream.
+    ///
+    /// If writing fails, including with [`WouldBlock`] or [`TimedOut`], the rest of the frame is kept and sent before anything else by the next call to `write_msg` or [`flush`]. Frames which exceed the maximum frame size are rejected with an error of kind [`InvalidInput`] without writing anything.
+    ///
+    /// [`WouldBlock`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.WouldBlock " "
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    /// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+    /// [`flush`]: #method.flush " "
+    pub fn write_msg(&mut self, frame: &[u8]) -> io::Result<()> {
+        self.write_buffer()?;
+        self.encode(frame)?;
+        self.write_buffer()
+    }
+    /// Writes out the rest of a frame which was interrupted by an error, if any, and flushes the underlying stream.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.write_buffer()?;
+        self.inner.flush()
+    }
+    fn write_buffer(&mut self) -> io::Result<()> {
+        match self.poll_write_buffer(|inner, buf| Poll::Ready(inner.write(buf))) {
+            Poll::Ready(result) => result,
+            Poll::Pending => unreachable!("synchronous writes never return Poll::Pending"),
+        }
+    }
+}
+impl<W: Write> WriteMsg for FramedWriter<W> {
+    #[inline(always)]
+    fn write_msg(&mut self, msg: &[u8]) -> io::Result<()> {
+        FramedWriter::write_msg(self, msg)
+    }
+}
+impl<W> Sealed for FramedWriter<W> {}
+#[cfg(feature = "nonblocking")]
+impl<W: AsyncWrite + Unpin, T: AsRef<[u8]>> Sink<T> for FramedWriter<W> {
+    type Error = io::Error;
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.buffer.len() - this.written < SINK_BACKPRESSURE {
+            return Poll::Ready(Ok(()));
+        }
+        this.poll_write_buffer(|inner, buf| Pin::new(inner).poll_write(cx, buf))
+    }
+    #[inline]
+    fn start_send(self: Pin<&mut Self>, item: T) -> io::Result<()> {
+        self.get_mut().encode(item.as_ref())
+    }
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        futures::ready!(this.poll_write_buffer(|inner, buf| Pin::new(inner).poll_write(cx, buf)))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        futures::ready!(Sink::<T>::poll_flush(self.as_mut(), cx))?;
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
+
+fn unexpected_eof() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "the stream ended in the middle of a frame",
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ReliableReadMsg;
+    use std::io::Cursor;
+
+    fn encode(options: FramingOptions, frames: &[&[u8]]) -> Vec<u8> {
+        let mut writer = FramedWriter::with_options(Vec::new(), options);
+        for frame in frames {
+            writer.write_msg(frame).unwrap();
+        }
+        writer.into_inner()
+    }
+
+    #[test]
+    fn round_trip() {
+        let options = FramingOptions::new()
+            .prefix_width(PrefixWidth::U16)
+            .endianness(Endianness::Little);
+        let bytes = encode(options, &[b"hello", b"", b"world"]);
+        assert_eq!(&bytes[..7], b"\x05\x00hello");
+        let mut reader = FramedReader::with_options(Cursor::new(bytes), options);
+        assert_eq!(reader.read_frame().unwrap().unwrap(), b"hello");
+        assert_eq!(reader.read_frame().unwrap().unwrap(), b"");
+        assert_eq!(reader.read_frame().unwrap().unwrap(), b"world");
+        assert!(reader.read_frame().unwrap().is_none());
+    }
+    #[test]
+    fn reliable_read_msg() {
+        let bytes = encode(FramingOptions::new(), &[b"a longer message", b"short"]);
+        assert_eq!(&bytes[..4], b"\x00\x00\x00\x10");
+        let mut reader = FramedReader::new(Cursor::new(bytes));
+        let mut buffer = [0; 8];
+        // The frame doesn't fit and must stay in the reader.
+        assert_eq!(reader.try_read_msg(&mut buffer).unwrap(), Err(16));
+        let frame = reader.read_msg(&mut buffer).unwrap().unwrap_err();
+        assert_eq!(frame, b"a longer message");
+        assert_eq!(reader.read_msg(&mut buffer).unwrap(), Ok(5));
+        assert_eq!(&buffer[..5], b"short");
+        let error = reader.read_msg(&mut buffer).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+    #[test]
+    fn max_frame_size() {
+        let options = FramingOptions::new().max_frame_size(4);
+        let mut writer = FramedWriter::with_options(Vec::new(), options);
+        let error = writer.write_msg(b"too long").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(writer.get_ref().is_empty());
+
+        let bytes = encode(FramingOptions::new(), &[b"too long"]);
+        let mut reader = FramedReader::with_options(Cursor::new(bytes), options);
+        let error = reader.read_frame().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let mut reader = FramedReader::new(Cursor::new(b"\x00\x00\x00\x10short".to_vec()));
+        let error = reader.read_frame().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+    #[cfg(feature = "nonblocking")]
+    #[test]
+    fn stream_and_sink() {
+        use futures::{executor::block_on, io::Cursor, SinkExt, TryStreamExt};
+
+        let options = FramingOptions::new().prefix_width(PrefixWidth::U8);
+        let mut writer = FramedWriter::with_options(Cursor::new(Vec::new()), options);
+        block_on(async {
+            writer.send(b"hello").await.unwrap();
+            writer.send(b"world").await.unwrap();
+        });
+        let bytes = writer.into_inner().into_inner();
+        let reader = FramedReader::with_options(Cursor::new(bytes), options);
+        let frames: Vec<_> = block_on(reader.try_collect()).unwrap();
+        assert_eq!(frames, vec![b"hello".to_vec(), b"world".to_vec()]);
+    }
+}
@@ -9,6 +9,11 @@
 //! - **Local sockets** — platform independent interface utilizing named pipes on Windows and Unix domain sockets on Unix. **Async support included!**
 //! - **Signals** — Unix-specific signals, used to receive critical messages from the OS and other programs, as well as sending those messages
 //!
+//! # Feature gates
+//! - **`nonblocking`**, *enabled by default* — runtime-agnostic async local sockets implementing the `futures` I/O traits, as well as a `Stream` of received Unix signals
+//! - **`tokio`** — Tokio-native local sockets, Ud-sockets and unnamed pipes, registered with the Tokio reactor and implementing the Tokio I/O traits
+//! - **`serde`** — typed channels which send and receive Serde-serializable values over local sockets, Ud-sockets and unnamed pipes, using either Bincode or JSON
+//!
 //! # License
 //! This crate, along with all community contributions made to it, is dual-licensed under the terms of either the [MIT license] or the [Apache 2.0 license].
 //!
@@ -24,6 +29,10 @@
 #![deny(rust_2018_idioms)]
 #![warn(missing_docs)]
 #![allow(unused_unsafe)]
+// libc re-exports its C types from core::ffi on new compilers and defines them itself on old ones,
+// which this lint mistakes for using items that are too new. The pinned CI toolchain checks the
+// minimum supported version instead.
+#![allow(clippy::incompatible_msrv)]
 
 // If an operating system is not listed here, the `compile_error!` is invoked
 #[cfg(not(any(
@@ -72,11 +81,52 @@ pub(crate) use private::Sealed;
 #[macro_use]
 pub(crate) mod private {
     macro_rules! impmod {
-        ($osmod:ident, $($orig:ident $(as $into:ident)?),* $(,)?) => {
+        ($($osmod:ident)::+, $($orig:ident $(as $into:ident)?),* $(,)?) => {
             #[cfg(unix)]
-            use $crate::os::unix::$osmod::{$($orig $(as $into)?,)*};
+            use $crate::os::unix::$($osmod)::+::{$($orig $(as $into)?,)*};
             #[cfg(windows)]
-            use $crate::os::windows::$osmod::{$($orig $(as $into)?,)*};
+            use $crate::os::windows::$($osmod)::+::{$($orig $(as $into)?,)*};
+        };
+    }
+    macro_rules! impl_owned_fd {
+        ($ty:ident) => {
+            #[cfg(unix)]
+            impl ::std::os::unix::io::AsFd for $ty {
+                #[inline(always)]
+                fn as_fd(&self) -> ::std::os::unix::io::BorrowedFd<'_> {
+                    // SAFETY: the file descriptor is owned by self and thus stays open for as long
+                    // as it's borrowed
+                    unsafe {
+                        ::std::os::unix::io::BorrowedFd::borrow_raw(
+                            ::std::os::unix::io::AsRawFd::as_raw_fd(self)
+                        )
+                    }
+                }
+            }
+            #[cfg(unix)]
+            impl ::std::convert::From<::std::os::unix::io::OwnedFd> for $ty {
+                #[inline(always)]
+                fn from(fd: ::std::os::unix::io::OwnedFd) -> Self {
+                    // SAFETY: OwnedFd guarantees that we're the only owner of the descriptor
+                    unsafe {
+                        ::std::os::unix::io::FromRawFd::from_raw_fd(
+                            ::std::os::unix::io::IntoRawFd::into_raw_fd(fd)
+                        )
+                    }
+                }
+            }
+            #[cfg(unix)]
+            impl ::std::convert::From<$ty> for ::std::os::unix::io::OwnedFd {
+                #[inline(always)]
+                fn from(op: $ty) -> Self {
+                    // SAFETY: as above
+                    unsafe {
+                        ::std::os::unix::io::FromRawFd::from_raw_fd(
+                            ::std::os::unix::io::IntoRawFd::into_raw_fd(op)
+                        )
+                    }
+                }
+            }
         };
     }
     macro_rules! impl_handle_manip {
@@ -123,6 +173,7 @@ pub(crate) mod private {
                     ::std::os::unix::io::IntoRawFd::into_raw_fd(self.inner)
                 }
             }
+            impl_owned_fd!($ty);
         };
     }
     // If the trait itself was pub(crate), it wouldn't work as a supertrait on public traits. We use a
@@ -132,8 +183,15 @@ pub(crate) mod private {
 
 #[cfg(feature = "nonblocking")]
 pub mod nonblocking;
+#[cfg(feature = "tokio")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "tokio")))]
+pub mod tokio;
 pub mod local_socket;
 pub mod unnamed_pipe;
+pub mod framing;
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "serde")))]
+pub mod channel;
 //pub mod shared_memory;
 
 pub mod os;
@@ -168,6 +226,8 @@ pub mod os;
 /// - Named pipes on Windows (module `interprocess::os::windows::named_pipe`)
 /// - Unix domain pipes, but only on Linux (module `interprocess::os::unix::udsocket`)
 ///     - This is because only Linux provides a special flag for `recv` which returns the amount of bytes in the message regardless of the provided buffer size when peeking.
+/// - Message local sockets, on Windows and Linux (module `interprocess::local_socket`)
+/// - Length-prefixed frames over any byte stream, on all platforms (module `interprocess::framing`)
 pub trait ReliableReadMsg: Sealed {
     /// Reads one message from the stream into the specified buffer, returning either the size of the message written, a bigger buffer if the one provided was too small, or an error in the outermost `Result` if the operation could not be completed for OS reasons.
     fn read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, Vec<u8>>>;
@@ -176,6 +236,25 @@ pub trait ReliableReadMsg: Sealed {
     fn try_read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, usize>>;
 }
 
+/// Writing whole messages into message-oriented IPC primitives, the counterpart of [`ReliableReadMsg`].
+///
+/// Each call transfers one whole message or returns an error. What happens to a message which was only partially sent is up to the implementation: datagram-based primitives fail with a [`PartialMsgWriteError`], while [`FramedWriter`] keeps the rest of the frame and sends it before the next one.
+///
+/// ## Platform support
+/// The trait is implemented for:
+/// - Message-mode named pipes on Windows (module `interprocess::os::windows::named_pipe`)
+/// - Ud-socket datagram and sequential packet sockets (module `interprocess::os::unix::udsocket`)
+/// - Message local sockets, on all platforms (module `interprocess::local_socket`)
+/// - Length-prefixed frames over any byte stream, on all platforms (module `interprocess::framing`)
+///
+/// [`ReliableReadMsg`]: trait.ReliableReadMsg.html " "
+/// [`PartialMsgWriteError`]: struct.PartialMsgWriteError.html " "
+/// [`FramedWriter`]: framing/struct.FramedWriter.html " "
+pub trait WriteMsg: Sealed {
+    /// Writes one message into the stream, returning an error if it could not be transferred as a whole.
+    fn write_msg(&mut self, msg: &[u8]) -> io::Result<()>;
+}
+
 /// Marker error indicating that a datagram write operation failed because the amount of bytes which were actually written as reported by the operating system was smaller than the size of the message which was requested to be written.
 ///
 /// Always emitted with the `ErrorKind::Other` error type.
@@ -189,8 +268,26 @@ impl Display for PartialMsgWriteError {
 }
 impl Error for PartialMsgWriteError {}
 
+/// Error indicating that a read half and a write half were not from the same stream, and thus could not be reunited.
+///
+/// Both halves are returned back to the caller unchanged.
+#[derive(Debug)]
+pub struct ReuniteError<R, W> {
+    /// The read half which was passed to `reunite`.
+    pub read_half: R,
+    /// The write half which was passed to `reunite`.
+    pub write_half: W,
+}
+impl<R, W> Display for ReuniteError<R, W> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("tried to reunite halves of different streams")
+    }
+}
+impl<R: fmt::Debug, W: fmt::Debug> Error for ReuniteError<R, W> {}
+
 #[cfg(test)]
-pub fn rand_string(n: usize) -> String {
+pub(crate) fn rand_string(n: usize) -> String {
     use rand::Rng;
     rand::thread_rng()
         .sample_iter(&rand::distributions::Alphanumeric)
@@ -12,8 +12,14 @@ use std::{
     borrow::Cow,
     ffi::{OsStr, OsString, CStr, CString},
     path::{Path, PathBuf},
+    net::Shutdown,
     str,
+    sync::Arc,
+    time::Duration,
 };
+use crate::{ReuniteError, WriteMsg, Sealed};
+#[cfg(any(windows, target_os = "linux"))]
+use crate::ReliableReadMsg;
 
 impmod!{local_socket,
     name_type_support_query as name_type_support_query_impl,
@@ -22,6 +28,8 @@ impmod!{local_socket,
     to_local_socket_name_osstring,
     LocalSocketListener as LocalSocketListenerImpl,
     LocalSocketStream as LocalSocketStreamImpl,
+    LocalSocketMsgListener as LocalSocketMsgListenerImpl,
+    LocalSocketMsgStream as LocalSocketMsgStreamImpl,
 }
 
 /// A local socket server, listening for connections.
@@ -52,14 +60,38 @@ impmod!{local_socket,
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 pub struct LocalSocketListener {
-    inner: LocalSocketListenerImpl,
+    pub(crate) inner: LocalSocketListenerImpl,
 }
 impl LocalSocketListener {
     /// Creates a socket server with the specified local socket name.
+    ///
+    /// Use [`LocalSocketListenerOptions`] to create a listener with non-default settings.
+    ///
+    /// [`LocalSocketListenerOptions`]: struct.LocalSocketListenerOptions.html " "
     #[inline(always)]
     pub fn bind<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
         Ok(Self {inner: LocalSocketListenerImpl::bind(name)?})
     }
+    /// Creates a socket server with the specified local socket name, replacing the leftovers of a previous server which didn't shut down cleanly and cleaning up after itself when dropped.
+    ///
+    /// On Unix, local sockets with filesystem paths leave socket files behind unless removed explicitly, which makes [`bind`] fail if the previous server crashed. This method removes such a file if no server is listening on it anymore, and removes the socket file of the new server when it's dropped. See [`UdStreamListener::bind_reclaiming`] for the details.
+    ///
+    /// On Windows and for namespaced names, this is the same as [`bind`], since there is nothing left to clean up after a server exits.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use interprocess::local_socket::LocalSocketListener;
+    ///
+    /// let listener = LocalSocketListener::bind_reclaiming("/tmp/example.sock")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`bind`]: #method.bind " "
+    /// [`UdStreamListener::bind_reclaiming`]: ../os/unix/udsocket/struct.UdStreamListener.html#method.bind_reclaiming " "
+    #[inline(always)]
+    pub fn bind_reclaiming<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        Ok(Self {inner: LocalSocketListenerImpl::bind_reclaiming(name)?})
+    }
     /// Listens for incoming connections to the socket, blocking until a client is connected.
     ///
     /// See [`incoming`] for a convenient way to create a main loop for a server.
@@ -77,6 +109,45 @@ impl LocalSocketListener {
     pub fn incoming(&self) -> Incoming<'_> {
         Incoming::from(self)
     }
+    /// Listens for incoming connections to the socket, blocking until a client is connected or the specified amount of time passes, in which case an error of kind [`TimedOut`] is returned.
+    ///
+    /// # Platform-specific behavior
+    /// Not supported on Windows, where an error of kind [`Other`] is always returned.
+    ///
+    /// On Unix, if another thread or process accepts the connection which this call woke up for, the deadline is only kept to if the listener is in [non-blocking mode]; otherwise, the call blocks until the next connection arrives.
+    ///
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    /// [non-blocking mode]: #method.set_nonblocking " "
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    #[inline(always)]
+    pub fn accept_timeout(&self, timeout: Duration) -> io::Result<LocalSocketStream> {
+        Ok(LocalSocketStream {inner: self.inner.accept_timeout(timeout)?})
+    }
+    /// Enables or disables the non-blocking mode for the listener. By default, it is disabled.
+    ///
+    /// In non-blocking mode, calling [`accept`] when there are no pending connections immediately returns an error of kind [`WouldBlock`] instead of blocking until a client connects.
+    ///
+    /// # Platform-specific behavior
+    /// Not supported on Windows, where an error of kind [`Other`] is always returned.
+    ///
+    /// [`accept`]: #method.accept " "
+    /// [`WouldBlock`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.WouldBlock " "
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    #[inline(always)]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+    /// Returns the name the listener is bound to, which is useful for finding out what a listener created from a raw file descriptor is listening on.
+    ///
+    /// # Platform-specific behavior
+    /// ## Unix
+    /// The name is retrieved from the system with `getsockname`, so it's the path or namespaced name as the system sees it. For listeners with file paths, this is the path the listener was bound with as is, without resolving relative paths or symbolic links.
+    /// ## Windows
+    /// Named pipes can't be asked for their name, so this returns the name the listener was created with. It's always a namespaced name.
+    #[inline(always)]
+    pub fn local_addr(&self) -> io::Result<LocalSocketName<'static>> {
+        self.inner.local_addr()
+    }
 }
 impl Debug for LocalSocketListener {
     #[inline(always)]
@@ -85,6 +156,101 @@ impl Debug for LocalSocketListener {
     }
 }
 
+/// The builder which can be used to create a [`LocalSocketListener`] with non-default settings, providing the subset of listener options which makes sense on all platforms.
+///
+/// For more control on Unix, such as the permissions of the socket file, use [`UdStreamListenerOptions`] and convert the result with `From`.
+///
+/// # Example
+/// ```no_run
+/// use interprocess::local_socket::LocalSocketListenerOptions;
+///
+/// let listener = LocalSocketListenerOptions::new()
+///     .backlog(16)
+///     .reclaim(true)
+///     .create("/tmp/example.sock")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`LocalSocketListener`]: struct.LocalSocketListener.html " "
+/// [`UdStreamListenerOptions`]: ../os/unix/udsocket/struct.UdStreamListenerOptions.html " "
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct LocalSocketListenerOptions {
+    /// The maximum number of pending connections which haven't been accepted yet.
+    ///
+    /// On Windows, this is ignored, since new instances of the named pipe are created as clients get accepted.
+    pub backlog: u32,
+    /// Replaces the leftovers of a previous server which didn't shut down cleanly and cleans up after the listener when it's dropped. See [`LocalSocketListener::bind_reclaiming`] for the details.
+    ///
+    /// [`LocalSocketListener::bind_reclaiming`]: struct.LocalSocketListener.html#method.bind_reclaiming " "
+    pub reclaim: bool,
+    /// Puts the listener into non-blocking mode right away. See [`LocalSocketListener::set_nonblocking`] for the details.
+    ///
+    /// On Windows, non-blocking mode is not supported, and creating the listener with this option enabled fails with an error of kind [`Other`].
+    ///
+    /// [`LocalSocketListener::set_nonblocking`]: struct.LocalSocketListener.html#method.set_nonblocking " "
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    pub nonblocking: bool,
+}
+impl LocalSocketListenerOptions {
+    /// Creates a new builder with default options, which are the ones used by [`LocalSocketListener::bind`].
+    ///
+    /// [`LocalSocketListener::bind`]: struct.LocalSocketListener.html#method.bind " "
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            backlog: 128,
+            reclaim: false,
+            nonblocking: false,
+        }
+    }
+    /// Sets the [`backlog`] parameter to the specified value.
+    ///
+    /// [`backlog`]: #structfield.backlog " "
+    #[inline]
+    #[must_use = "builder setters take the entire structure and return the result"]
+    pub fn backlog(mut self, backlog: u32) -> Self {
+        self.backlog = backlog;
+        self
+    }
+    /// Sets the [`reclaim`] parameter to the specified value.
+    ///
+    /// [`reclaim`]: #structfield.reclaim " "
+    #[inline]
+    #[must_use = "builder setters take the entire structure and return the result"]
+    pub fn reclaim(mut self, reclaim: bool) -> Self {
+        self.reclaim = reclaim;
+        self
+    }
+    /// Sets the [`nonblocking`] parameter to the specified value.
+    ///
+    /// [`nonblocking`]: #structfield.nonblocking " "
+    #[inline]
+    #[must_use = "builder setters take the entire structure and return the result"]
+    pub fn nonblocking(mut self, nonblocking: bool) -> Self {
+        self.nonblocking = nonblocking;
+        self
+    }
+    /// Creates a socket server with the specified local socket name using the options from the builder.
+    #[inline]
+    pub fn create<'a>(&self, name: impl ToLocalSocketName<'a>) -> io::Result<LocalSocketListener> {
+        Ok(LocalSocketListener {inner: LocalSocketListenerImpl::create(name, self)?})
+    }
+}
+impl Default for LocalSocketListenerOptions {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+#[cfg(unix)]
+impl From<crate::os::unix::udsocket::UdStreamListener> for LocalSocketListener {
+    #[inline]
+    fn from(listener: crate::os::unix::udsocket::UdStreamListener) -> Self {
+        Self {inner: LocalSocketListenerImpl {inner: listener}}
+    }
+}
+
 /// An infinite iterator over incoming client connections of a [`LocalSocketListener`].
 ///
 /// This iterator is created by the [`incoming`] method on [`LocalSocketListener`] — see its documentation for more.
@@ -133,7 +299,7 @@ impl FusedIterator for Incoming<'_> {}
 ///
 /// [`LocalSocketListener`]: struct.LocalSocketListener.html " "
 pub struct LocalSocketStream {
-    inner: LocalSocketStreamImpl,
+    pub(crate) inner: LocalSocketStreamImpl,
 }
 impl LocalSocketStream {
     /// Connects to a remote local socket server.
@@ -141,29 +307,250 @@ impl LocalSocketStream {
     pub fn connect<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
         Ok(Self {inner: LocalSocketStreamImpl::connect(name)?})
     }
+    /// Creates a pair of local socket streams which are connected to each other, without a listener or a name which other processes could connect to. Useful for communicating with a child process or between threads.
+    ///
+    /// # Platform-specific behavior
+    /// ## Unix
+    /// Uses `socketpair`, so that the streams aren't bound to any path. Both streams are inherited by child processes, which makes it possible to pass one of them to a child by communicating its file descriptor number.
+    /// ## Windows
+    /// Named pipes always have a name, so a uniquely named pipe is created, connected to and then immediately released from the listener. In the short window between the creation of the pipe and the connection, another process which guesses the name could connect to it instead, in which case the client side of the pair would fail to connect and an error is returned.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use interprocess::local_socket::LocalSocketStream;
+    /// use std::{io::prelude::*, thread};
+    ///
+    /// let (mut left, mut right) = LocalSocketStream::pair()?;
+    /// thread::spawn(move || right.write_all(b"Hello from the other side!"));
+    /// let mut message = String::new();
+    /// left.read_to_string(&mut message)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[inline]
+    pub fn pair() -> io::Result<(Self, Self)> {
+        let (inner1, inner2) = LocalSocketStreamImpl::pair()?;
+        Ok((Self {inner: inner1}, Self {inner: inner2}))
+    }
+    /// Enables or disables the non-blocking mode for the stream. By default, it is disabled.
+    ///
+    /// In non-blocking mode, reading from the stream when no data is available or writing into it when its buffer is full immediately returns an error of kind [`WouldBlock`] instead of blocking.
+    ///
+    /// # Platform-specific behavior
+    /// Not supported on Windows, where an error of kind [`Other`] is always returned.
+    ///
+    /// [`WouldBlock`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.WouldBlock " "
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    #[inline(always)]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+    /// Sets the timeout for read operations on the stream. `None` disables the timeout, which is the default.
+    ///
+    /// If a read operation doesn't complete within the timeout, it fails with an error of kind [`TimedOut`]. Passing a zero duration is an error of kind [`InvalidInput`].
+    ///
+    /// # Platform-specific behavior
+    /// Not supported on Windows, where an error of kind [`Other`] is always returned.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use interprocess::local_socket::LocalSocketStream;
+    /// use std::{io::{self, prelude::*}, time::Duration};
+    ///
+    /// let mut conn = LocalSocketStream::connect("/tmp/example.sock")?;
+    /// conn.set_read_timeout(Some(Duration::from_secs(5)))?;
+    /// let mut buffer = [0; 128];
+    /// match conn.read(&mut buffer) {
+    ///     Ok(bytes_read) => println!("Server sent {} bytes", bytes_read),
+    ///     Err(error) if error.kind() == io::ErrorKind::TimedOut => println!("Server is stuck"),
+    ///     Err(error) => return Err(error.into()),
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    /// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    #[inline(always)]
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.inner.set_read_timeout(timeout)
+    }
+    /// Sets the timeout for write operations on the stream. `None` disables the timeout, which is the default.
+    ///
+    /// If a write operation doesn't complete within the timeout, it fails with an error of kind [`TimedOut`]. Passing a zero duration is an error of kind [`InvalidInput`].
+    ///
+    /// # Platform-specific behavior
+    /// Not supported on Windows, where an error of kind [`Other`] is always returned.
+    ///
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    /// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    #[inline(always)]
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.inner.set_write_timeout(timeout)
+    }
+    /// Returns the timeout for read operations on the stream, or `None` if there is none.
+    #[inline(always)]
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        self.inner.read_timeout()
+    }
+    /// Returns the timeout for write operations on the stream, or `None` if there is none.
+    #[inline(always)]
+    pub fn write_timeout(&self) -> io::Result<Option<Duration>> {
+        self.inner.write_timeout()
+    }
+    /// Shuts down the read half, the write half or both halves of the stream.
+    ///
+    /// Once the write half is shut down, the other side reads EOF as soon as it receives all the data sent before that, while still being able to send data back, which is commonly used to signal the end of a request. Shutting down the read half makes further reads on this side return EOF.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use interprocess::local_socket::LocalSocketStream;
+    /// use std::{io::prelude::*, net::Shutdown};
+    ///
+    /// let mut conn = LocalSocketStream::connect("/tmp/example.sock")?;
+    /// conn.write_all(b"Hello from client!")?;
+    /// conn.shutdown(Shutdown::Write)?;
+    /// let mut response = String::new();
+    /// conn.read_to_string(&mut response)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// # Platform-specific behavior
+    /// Not supported on Windows, where an error of kind [`Other`] is always returned, since named pipes can only be closed entirely.
+    ///
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    #[inline(always)]
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.inner.shutdown(how)
+    }
+    /// Creates a new handle to the same stream. Both handles refer to the same connection, sharing the non-blocking mode and the timeouts, which is closed once all of the handles are dropped.
+    ///
+    /// # Platform-specific behavior
+    /// Not supported on Windows, where an error of kind [`Other`] is always returned, since the connection of a named pipe is severed when any of its handles are dropped. Use [`into_split`] instead to use the stream from two threads at once.
+    ///
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    /// [`into_split`]: #method.into_split " "
+    #[inline(always)]
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Ok(Self {inner: self.inner.try_clone()?})
+    }
+    /// Splits the stream into a read half and a write half, which can be moved to different threads and used concurrently, and which can later be put back together with [`ReadHalf::reunite`].
+    ///
+    /// To use a stream from two places without moving it, use the `Read` and `Write` implementations on `&LocalSocketStream` instead.
+    ///
+    /// # Platform-specific behavior
+    /// On Windows, I/O on a named pipe is serialized by the system, so a read which is waiting for data blocks writes from the other half until it completes.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use interprocess::local_socket::LocalSocketStream;
+    /// use std::{io::{prelude::*, BufReader}, thread};
+    ///
+    /// let conn = LocalSocketStream::connect("/tmp/example.sock")?;
+    /// let (reader, mut writer) = conn.into_split();
+    /// let writer_thread = thread::spawn(move || {
+    ///     writer.write_all(b"Hello from client!\n").map(|_| writer)
+    /// });
+    /// let mut reader = BufReader::new(reader);
+    /// let mut buffer = String::new();
+    /// reader.read_line(&mut buffer)?;
+    /// println!("Server answered: {}", buffer);
+    /// let writer = writer_thread.join().unwrap()?;
+    /// let conn = reader.into_inner().reunite(writer)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`ReadHalf::reunite`]: struct.ReadHalf.html#method.reunite " "
+    #[inline]
+    pub fn into_split(self) -> (ReadHalf, WriteHalf) {
+        let stream = Arc::new(self);
+        (ReadHalf(Arc::clone(&stream)), WriteHalf(stream))
+    }
+    /// Returns the name of the local end of the stream, or `None` if it doesn't have one. Streams accepted by a listener have the name of the listener, while streams created by [`connect`] or [`pair`] are unnamed.
+    ///
+    /// # Platform-specific behavior
+    /// ## Unix
+    /// The name is retrieved from the system with `getsockname`. See [`UdStream::local_addr`] for the details.
+    /// ## Windows
+    /// Named pipes can't be asked for their name, so this returns the name of the listener which accepted the stream. Streams created from a raw handle are always reported as unnamed.
+    ///
+    /// [`connect`]: #method.connect " "
+    /// [`pair`]: #method.pair " "
+    /// [`UdStream::local_addr`]: ../os/unix/udsocket/struct.UdStream.html#method.local_addr " "
+    #[inline(always)]
+    pub fn local_addr(&self) -> io::Result<Option<LocalSocketName<'static>>> {
+        self.inner.local_addr()
+    }
+    /// Returns the name of the other end of the stream, or `None` if it doesn't have one. Streams created by [`connect`] have the name of the server they're connected to, while the clients of streams accepted by a listener are usually unnamed, and so are both streams created by [`pair`].
+    ///
+    /// # Platform-specific behavior
+    /// ## Unix
+    /// The name is retrieved from the system with `getpeername`. Clients which connect with this crate never bind their sockets, so streams accepted by a listener only have a peer name if the client bound its socket by other means before connecting.
+    /// ## Windows
+    /// Named pipes can't be asked for their name, so this returns the name the stream was connected to. Streams created from a raw handle are always reported as unnamed.
+    ///
+    /// [`connect`]: #method.connect " "
+    /// [`pair`]: #method.pair " "
+    #[inline(always)]
+    pub fn peer_addr(&self) -> io::Result<Option<LocalSocketName<'static>>> {
+        self.inner.peer_addr()
+    }
+    /// Retrieves the credentials of the process on the other end of the connection from the kernel. See [`UdStream::peer_credentials`] for details.
+    ///
+    /// [`UdStream::peer_credentials`]: ../os/unix/udsocket/struct.UdStream.html#method.peer_credentials " "
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(target_os = "linux")))]
+    #[inline(always)]
+    pub fn peer_credentials(&self) -> io::Result<crate::os::unix::udsocket::PeerCredentials> {
+        self.inner.peer_credentials()
+    }
+}
+impl Read for &LocalSocketStream {
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&self.inner).read(buf)
+    }
+    #[inline(always)]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (&self.inner).read_vectored(bufs)
+    }
+}
+impl Write for &LocalSocketStream {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&self.inner).write(buf)
+    }
+    #[inline(always)]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (&self.inner).write_vectored(bufs)
+    }
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        (&self.inner).flush()
+    }
 }
 impl Read for LocalSocketStream {
     #[inline(always)]
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.read(buf)
+        (&*self).read(buf)
     }
     #[inline(always)]
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
-        self.inner.read_vectored(bufs)
+        (&*self).read_vectored(bufs)
     }
 }
 impl Write for LocalSocketStream {
     #[inline(always)]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.write(buf)
+        (&*self).write(buf)
     }
     #[inline(always)]
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        self.inner.write_vectored(bufs)
+        (&*self).write_vectored(bufs)
     }
     #[inline(always)]
     fn flush(&mut self) -> io::Result<()> {
-        self.inner.flush()
+        (&*self).flush()
     }
 }
 impl Debug for LocalSocketStream {
@@ -174,6 +561,247 @@ impl Debug for LocalSocketStream {
 }
 impl_handle_manip!(LocalSocketStream);
 
+/// The read half of a [`LocalSocketStream`], created by [`into_split`].
+///
+/// [`LocalSocketStream`]: struct.LocalSocketStream.html " "
+/// [`into_split`]: struct.LocalSocketStream.html#method.into_split " "
+pub struct ReadHalf(Arc<LocalSocketStream>);
+impl ReadHalf {
+    /// Puts the stream back together from the two halves, failing if the write half was split off a different stream.
+    #[inline]
+    pub fn reunite(self, write_half: WriteHalf) -> Result<LocalSocketStream, ReuniteError<Self, WriteHalf>> {
+        if !Arc::ptr_eq(&self.0, &write_half.0) {
+            return Err(ReuniteError {read_half: self, write_half});
+        }
+        drop(write_half);
+        Ok(Arc::try_unwrap(self.0)
+            .expect("the halves of a stream are the only owners of the stream"))
+    }
+}
+impl Read for ReadHalf {
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self.0).read(buf)
+    }
+    #[inline(always)]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (&*self.0).read_vectored(bufs)
+    }
+}
+impl Debug for ReadHalf {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ReadHalf")
+            .field(&self.0)
+            .finish()
+    }
+}
+
+/// The write half of a [`LocalSocketStream`], created by [`into_split`].
+///
+/// [`LocalSocketStream`]: struct.LocalSocketStream.html " "
+/// [`into_split`]: struct.LocalSocketStream.html#method.into_split " "
+pub struct WriteHalf(Arc<LocalSocketStream>);
+impl WriteHalf {
+    /// Puts the stream back together from the two halves, failing if the read half was split off a different stream.
+    #[inline(always)]
+    pub fn reunite(self, read_half: ReadHalf) -> Result<LocalSocketStream, ReuniteError<ReadHalf, Self>> {
+        read_half.reunite(self)
+    }
+}
+impl Write for WriteHalf {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self.0).write(buf)
+    }
+    #[inline(always)]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (&*self.0).write_vectored(bufs)
+    }
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self.0).flush()
+    }
+}
+impl Debug for WriteHalf {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WriteHalf")
+            .field(&self.0)
+            .finish()
+    }
+}
+
+/// A local socket server which accepts message-oriented connections, the counterpart of [`LocalSocketListener`] for [`LocalSocketMsgStream`].
+///
+/// # Platform-specific behavior
+/// ## Unix
+/// Uses Ud-sockets of the `SOCK_SEQPACKET` type, i.e. [`UdSeqpacketListener`]. macOS and iOS don't support those, so binding always fails there.
+/// ## Windows
+/// Uses named pipes in message mode, same as [`DuplexMsgPipeStream`].
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(any(windows, target_os = "linux"))] {
+/// use interprocess::{
+///     local_socket::LocalSocketMsgListener,
+///     ReliableReadMsg, WriteMsg,
+/// };
+///
+/// let listener = LocalSocketMsgListener::bind("/tmp/example.sock")?;
+/// let mut conn = listener.accept()?;
+/// let mut buffer = vec![0; 128];
+/// let size = match conn.read_msg(&mut buffer)? {
+///     Ok(size) => size,
+///     Err(bigger_buffer) => {
+///         buffer = bigger_buffer;
+///         buffer.len()
+///     }
+/// };
+/// println!("Client sent: {}", String::from_utf8_lossy(&buffer[..size]));
+/// conn.write_msg(b"Hello from server!")?;
+/// # }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`LocalSocketListener`]: struct.LocalSocketListener.html " "
+/// [`LocalSocketMsgStream`]: struct.LocalSocketMsgStream.html " "
+/// [`UdSeqpacketListener`]: ../os/unix/udsocket/struct.UdSeqpacketListener.html " "
+/// [`DuplexMsgPipeStream`]: ../os/windows/named_pipe/struct.DuplexMsgPipeStream.html " "
+pub struct LocalSocketMsgListener {
+    inner: LocalSocketMsgListenerImpl,
+}
+impl LocalSocketMsgListener {
+    /// Creates a message socket server with the specified local socket name.
+    #[inline(always)]
+    pub fn bind<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        Ok(Self {inner: LocalSocketMsgListenerImpl::bind(name)?})
+    }
+    /// Creates a message socket server with the specified local socket name, replacing the leftovers of a previous server which didn't shut down cleanly and cleaning up after itself when dropped. See [`LocalSocketListener::bind_reclaiming`] for the details.
+    ///
+    /// [`LocalSocketListener::bind_reclaiming`]: struct.LocalSocketListener.html#method.bind_reclaiming " "
+    #[inline(always)]
+    pub fn bind_reclaiming<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        Ok(Self {inner: LocalSocketMsgListenerImpl::bind_reclaiming(name)?})
+    }
+    /// Listens for incoming connections to the socket, blocking until a client is connected.
+    #[inline(always)]
+    pub fn accept(&self) -> io::Result<LocalSocketMsgStream> {
+        Ok(LocalSocketMsgStream {inner: self.inner.accept()?})
+    }
+    /// Enables or disables the non-blocking mode for the listener. By default, it is disabled.
+    ///
+    /// # Platform-specific behavior
+    /// Not supported on Windows, where an error of kind [`Other`] is always returned.
+    ///
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    #[inline(always)]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+}
+impl Debug for LocalSocketMsgListener {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+
+/// A local socket connection which preserves message boundaries, obtained either from [`LocalSocketMsgListener`] or by connecting to an existing message socket server.
+///
+/// Messages are sent with [`WriteMsg`] and received with [`ReliableReadMsg`], each call transferring exactly one message as a whole, which makes it possible to write one message-based protocol for all platforms. Message local sockets can't talk to byte stream local sockets — the server and the client must both use the message variety.
+///
+/// # Platform-specific behavior
+/// `ReliableReadMsg` is only implemented on Windows and Linux, since other Unix systems provide no way to find out the size of the next message before receiving it.
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(any(windows, target_os = "linux"))] {
+/// use interprocess::{
+///     local_socket::LocalSocketMsgStream,
+///     ReliableReadMsg, WriteMsg,
+/// };
+///
+/// let mut conn = LocalSocketMsgStream::connect("/tmp/example.sock")?;
+/// conn.write_msg(b"Hello from client!")?;
+/// let mut buffer = [0; 128];
+/// if let Ok(size) = conn.read_msg(&mut buffer)? {
+///     println!("Server answered: {}", String::from_utf8_lossy(&buffer[..size]));
+/// }
+/// # }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`LocalSocketMsgListener`]: struct.LocalSocketMsgListener.html " "
+/// [`WriteMsg`]: ../trait.WriteMsg.html " "
+/// [`ReliableReadMsg`]: ../trait.ReliableReadMsg.html " "
+pub struct LocalSocketMsgStream {
+    pub(crate) inner: LocalSocketMsgStreamImpl,
+}
+impl LocalSocketMsgStream {
+    /// Connects to a remote message socket server.
+    #[inline(always)]
+    pub fn connect<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        Ok(Self {inner: LocalSocketMsgStreamImpl::connect(name)?})
+    }
+    /// Enables or disables the non-blocking mode for the stream. By default, it is disabled.
+    ///
+    /// # Platform-specific behavior
+    /// Not supported on Windows, where an error of kind [`Other`] is always returned.
+    ///
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    #[inline(always)]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+    /// Sets the timeout for receiving messages. `None` disables the timeout, which is the default.
+    ///
+    /// # Platform-specific behavior
+    /// Not supported on Windows, where an error of kind [`Other`] is always returned.
+    ///
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    #[inline(always)]
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.inner.set_read_timeout(timeout)
+    }
+    /// Sets the timeout for sending messages. `None` disables the timeout, which is the default.
+    ///
+    /// # Platform-specific behavior
+    /// Not supported on Windows, where an error of kind [`Other`] is always returned.
+    ///
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    #[inline(always)]
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.inner.set_write_timeout(timeout)
+    }
+}
+#[cfg(any(windows, target_os = "linux"))]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(any(windows, target_os = "linux"))))]
+impl ReliableReadMsg for LocalSocketMsgStream {
+    #[inline(always)]
+    fn read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, Vec<u8>>> {
+        self.inner.read_msg(buf)
+    }
+    #[inline(always)]
+    fn try_read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, usize>> {
+        self.inner.try_read_msg(buf)
+    }
+}
+impl WriteMsg for LocalSocketMsgStream {
+    #[inline(always)]
+    fn write_msg(&mut self, msg: &[u8]) -> io::Result<()> {
+        self.inner.write_msg(msg)
+    }
+}
+impl Sealed for LocalSocketMsgStream {}
+impl Debug for LocalSocketMsgStream {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+impl_handle_manip!(LocalSocketMsgStream);
+
 /// A name for a local socket.
 ///
 /// Due to vast differences between platforms in terms of how local sockets are named, there needs to be a way to store and process those in a unified way while also retaining platform-specific pecularities. `LocalSocketName` aims to bridge the gap between portability and platform-specific correctness.
@@ -426,7 +1054,7 @@ impl ToLocalSocketName<'static> for CString {
 
 #[cfg(test)]
 mod test {
-    use crate::local_socket::{LocalSocketListener, LocalSocketStream};
+    use crate::local_socket::{LocalSocketListener, LocalSocketListenerOptions, LocalSocketStream};
     use crate::rand_string;
 
     #[test]
@@ -533,4 +1161,238 @@ mod test {
         });
         h2.join().unwrap();
     }
+    #[test]
+    #[cfg(unix)]
+    fn timeouts() {
+        use std::{
+            io::{self, prelude::*},
+            time::Duration,
+        };
+
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = LocalSocketListener::bind(socket_name.clone()).unwrap();
+        let timeout = Duration::from_millis(50);
+
+        let error = listener.accept_timeout(timeout).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        listener.set_nonblocking(true).unwrap();
+        let error = listener.accept().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WouldBlock);
+
+        let mut client = LocalSocketStream::connect(socket_name).unwrap();
+        let _server = listener.accept_timeout(timeout).unwrap();
+        client.set_read_timeout(Some(timeout)).unwrap();
+        assert!(client.read_timeout().unwrap().is_some());
+        let error = client.read(&mut [0; 16]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        client.set_nonblocking(true).unwrap();
+        let error = client.read(&mut [0; 16]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WouldBlock);
+    }
+    #[test]
+    fn pair() {
+        use std::{io::prelude::*, thread};
+
+        let (mut left, mut right) = LocalSocketStream::pair().unwrap();
+        let right_thread = thread::spawn(move || {
+            let mut buffer = [0; 4];
+            right.read_exact(&mut buffer).unwrap();
+            assert_eq!(&buffer, b"ping");
+            right.write_all(b"pong").unwrap();
+        });
+        left.write_all(b"ping").unwrap();
+        let mut buffer = Vec::new();
+        left.read_to_end(&mut buffer).unwrap();
+        assert_eq!(buffer, b"pong");
+        right_thread.join().unwrap();
+    }
+    #[test]
+    #[cfg(unix)]
+    fn addresses() {
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = LocalSocketListener::bind(socket_name.clone()).unwrap();
+        let client = LocalSocketStream::connect(socket_name.clone()).unwrap();
+        let server = listener.accept().unwrap();
+
+        let local_addr = listener.local_addr().unwrap();
+        assert!(local_addr.is_path());
+        assert_eq!(local_addr.inner(), socket_name.as_str());
+        assert_eq!(client.peer_addr().unwrap().unwrap().inner(), socket_name.as_str());
+        assert_eq!(server.local_addr().unwrap().unwrap().inner(), socket_name.as_str());
+        assert!(client.local_addr().unwrap().is_none());
+        assert!(server.peer_addr().unwrap().is_none());
+
+        let (left, _right) = LocalSocketStream::pair().unwrap();
+        assert!(left.local_addr().unwrap().is_none());
+        assert!(left.peer_addr().unwrap().is_none());
+    }
+    #[test]
+    fn split() {
+        use std::{io::prelude::*, thread};
+
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = LocalSocketListener::bind(socket_name.clone()).unwrap();
+        let client = LocalSocketStream::connect(socket_name.clone()).unwrap();
+        let mut server = listener.accept().unwrap();
+        let (mut reader, mut writer) = client.into_split();
+        let writer_thread = thread::spawn(move || {
+            writer.write_all(b"ping").unwrap();
+            writer
+        });
+        let mut buffer = [0; 4];
+        server.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"ping");
+        server.write_all(b"pong").unwrap();
+        reader.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"pong");
+        let writer = writer_thread.join().unwrap();
+
+        let (_, other_writer) = LocalSocketStream::connect(socket_name).unwrap().into_split();
+        let reader = reader.reunite(other_writer).unwrap_err().read_half;
+        let mut client = reader.reunite(writer).unwrap();
+        client.write_all(b"done").unwrap();
+        server.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"done");
+
+        #[cfg(unix)]
+        {
+            let mut clone = client.try_clone().unwrap();
+            drop(client);
+            clone.write_all(b"more").unwrap();
+            server.read_exact(&mut buffer).unwrap();
+            assert_eq!(&buffer, b"more");
+        }
+    }
+    #[test]
+    #[cfg(unix)]
+    fn shutdown() {
+        use std::{io::prelude::*, net::Shutdown};
+
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = LocalSocketListener::bind(socket_name.clone()).unwrap();
+        let mut client = LocalSocketStream::connect(socket_name).unwrap();
+        let mut server = listener.accept().unwrap();
+        client.write_all(b"request").unwrap();
+        client.shutdown(Shutdown::Write).unwrap();
+        let mut request = Vec::new();
+        server.read_to_end(&mut request).unwrap();
+        assert_eq!(request, b"request");
+        server.write_all(b"response").unwrap();
+        drop(server);
+        let mut response = Vec::new();
+        client.read_to_end(&mut response).unwrap();
+        assert_eq!(response, b"response");
+    }
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn peer_credentials() {
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = LocalSocketListener::bind(socket_name.clone()).unwrap();
+        let _client = LocalSocketStream::connect(socket_name).unwrap();
+        let conn = listener.accept().unwrap();
+        let credentials = conn.peer_credentials().unwrap();
+        assert_eq!(credentials.pid, std::process::id() as libc::pid_t);
+        assert_eq!(credentials.uid, unsafe { libc::geteuid() });
+        assert_eq!(credentials.gid, unsafe { libc::getegid() });
+    }
+    #[test]
+    #[cfg(unix)]
+    fn reclaim() {
+        use std::{io, path::Path};
+
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        // Simulate a crashed server by leaking its socket file.
+        std::mem::forget(LocalSocketListener::bind(socket_name.clone()).unwrap());
+        // The leaked listener is still open in this process, so the path must not be reclaimed.
+        let error = LocalSocketListener::bind_reclaiming(socket_name.clone()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AddrInUse);
+
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        drop(LocalSocketListener::bind(socket_name.clone()).unwrap());
+        assert!(Path::new(&socket_name).exists());
+        let listener = LocalSocketListener::bind_reclaiming(socket_name.clone()).unwrap();
+        drop(listener);
+        assert!(!Path::new(&socket_name).exists());
+    }
+    #[test]
+    #[cfg(unix)]
+    fn listener_options() {
+        use crate::os::unix::udsocket::UdStreamListenerOptions;
+        use std::{fs, io::{self, prelude::*}, os::unix::fs::PermissionsExt};
+
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener: LocalSocketListener = UdStreamListenerOptions::new()
+            .mode(0o600)
+            .reclaim(true)
+            .create(socket_name.clone())
+            .unwrap()
+            .into();
+        let metadata = fs::symlink_metadata(&socket_name).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        let mut client = LocalSocketStream::connect(socket_name.clone()).unwrap();
+        let mut server = listener.accept().unwrap();
+        client.write_all(b"ping").unwrap();
+        let mut buffer = [0; 4];
+        server.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"ping");
+        // The path is taken by a live listener, so the staged bind must fail just like a plain one.
+        let error = UdStreamListenerOptions::new()
+            .mode(0o600)
+            .create(socket_name.clone())
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AddrInUse);
+        drop(listener);
+        assert!(fs::symlink_metadata(&socket_name).is_err());
+
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = LocalSocketListenerOptions::new()
+            .backlog(1)
+            .nonblocking(true)
+            .reclaim(true)
+            .create(socket_name)
+            .unwrap();
+        let error = listener.accept().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WouldBlock);
+    }
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn messages() {
+        use crate::{
+            local_socket::{LocalSocketMsgListener, LocalSocketMsgStream},
+            ReliableReadMsg, WriteMsg,
+        };
+        use std::io;
+
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = LocalSocketMsgListener::bind_reclaiming(socket_name.clone()).unwrap();
+        let mut client = LocalSocketMsgStream::connect(socket_name).unwrap();
+        let mut server = listener.accept().unwrap();
+        client.write_msg(b"short").unwrap();
+        client.write_msg(b"a message which doesn't fit").unwrap();
+        let mut buffer = [0; 8];
+        assert_eq!(server.read_msg(&mut buffer).unwrap(), Ok(5));
+        assert_eq!(&buffer[..5], b"short");
+        assert_eq!(
+            server.read_msg(&mut buffer).unwrap(),
+            Err(b"a message which doesn't fit".to_vec()),
+        );
+        server.write_msg(b"reply").unwrap();
+        assert_eq!(client.try_read_msg(&mut buffer).unwrap(), Ok(5));
+        assert_eq!(
+            client.write_msg(b"").unwrap_err().kind(),
+            io::ErrorKind::InvalidInput,
+        );
+
+        drop(client);
+        for _ in 0..2 {
+            assert_eq!(
+                server.read_msg(&mut buffer).unwrap_err().kind(),
+                io::ErrorKind::UnexpectedEof,
+            );
+            assert_eq!(
+                server.try_read_msg(&mut buffer).unwrap_err().kind(),
+                io::ErrorKind::UnexpectedEof,
+            );
+        }
+    }
 }
@@ -2,19 +2,32 @@
 //!
 //! See the [blocking version of this module] for more on what those are.
 //!
+//! ## Implementation
+//! On Unix, the sockets are put into non-blocking mode and registered with an `epoll`/`kqueue`-based reactor shared by the whole process, meaning that pending accept, read and write operations do not occupy any threads and are cancellation-safe: dropping the future of an operation which hasn't completed yet does not lose any connections or data. On Windows, named pipes cannot be used with readiness-based I/O, so the blocking operations are offloaded to a thread pool instead.
+//!
 //! [blocking version of this module]: ../../local_socket/index.html " "
 
-use blocking::{unblock, Unblock};
 use futures::{
     stream::{Stream, FusedStream},
+    task::{Context, Poll},
     AsyncRead, AsyncWrite,
 };
 use std::{
-    io,
-    sync::Arc,
+    io::{self, IoSlice, IoSliceMut},
+    fmt::{self, Formatter, Debug},
+    net::Shutdown,
+    pin::Pin,
 };
 
-use crate::local_socket::{self as sync, ToLocalSocketName};
+use crate::{local_socket::ToLocalSocketName, ReuniteError};
+
+impmod!{nonblocking::local_socket,
+    LocalSocketListener as LocalSocketListenerImpl,
+    LocalSocketStream as LocalSocketStreamImpl,
+    Incoming as IncomingImpl,
+    ReadHalf as ReadHalfImpl,
+    WriteHalf as WriteHalfImpl,
+}
 
 /// An asynchronous local socket server, listening for connections.
 ///
@@ -43,29 +56,29 @@ use crate::local_socket::{self as sync, ToLocalSocketName};
 ///     .await?;
 /// # Ok(()) }
 /// ```
-#[derive(Debug)]
 pub struct LocalSocketListener {
-    inner: Arc<sync::LocalSocketListener>,
+    inner: LocalSocketListenerImpl,
 }
-
 impl LocalSocketListener {
     /// Creates a socket server with the specified local socket name.
     #[inline]
     pub async fn bind<'a>(name: impl ToLocalSocketName<'_> + Send + 'static) -> io::Result<Self> {
         Ok(Self {
-            inner: Arc::new(unblock(move || sync::LocalSocketListener::bind(name)).await?),
+            inner: LocalSocketListenerImpl::bind(name).await?,
         })
     }
-    /// Listens for incoming connections to the socket, blocking until a client is connected.
+    /// Listens for incoming connections to the socket, asynchronously waiting until a client is connected.
     ///
     /// See [`incoming`] for a convenient way to create a main loop for a server.
     ///
+    /// # Cancellation
+    /// On Unix, this operation is cancellation-safe: if the future is dropped before it completes, no incoming connection is lost and can be accepted by a later call.
+    ///
     /// [`incoming`]: #method.incoming " "
     #[inline]
     pub async fn accept(&self) -> io::Result<LocalSocketStream> {
-        let s = self.inner.clone();
         Ok(LocalSocketStream {
-            inner: Unblock::new(unblock(move || s.accept()).await?),
+            inner: self.inner.accept().await?,
         })
     }
     /// Creates an infinite asynchronous stream which calls `accept()` with each iteration. Used together with [`for_each`]/[`try_for_each`] stream adaptors to conveniently create a main loop for a socket server.
@@ -78,14 +91,16 @@ impl LocalSocketListener {
     #[inline]
     pub fn incoming(&self) -> Incoming {
         Incoming {
-            inner: Unblock::new(
-                SyncArcIncoming {
-                    inner: Arc::clone(&self.inner),
-                }
-            )
+            inner: self.inner.incoming(),
         }
     }
 }
+impl Debug for LocalSocketListener {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
 
 /// An infinite asynchronous stream over incoming client connections of a [`LocalSocketListener`].
 ///
@@ -93,9 +108,8 @@ impl LocalSocketListener {
 ///
 /// [`LocalSocketListener`]: struct.LocalSocketListener.html " "
 /// [`incoming`]: struct.LocalSocketListener.html#method.incoming " "
-#[derive(Debug)]
 pub struct Incoming {
-    inner: Unblock<SyncArcIncoming>,
+    inner: IncomingImpl,
 }
 impl Stream for Incoming {
     type Item = Result<LocalSocketStream, io::Error>;
@@ -104,26 +118,9 @@ impl Stream for Incoming {
         mut self: Pin<&mut Self>,
         ctx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        let poll = <Unblock<_> as Stream>::poll_next(
-            Pin::new(&mut self.inner),
-            ctx,
-        );
-        match poll {
-            Poll::Ready(val) => {
-                let val = val.map(|val| {
-                    match val {
-                        Ok(inner) => Ok(
-                            LocalSocketStream {
-                                inner: Unblock::new(inner),
-                            }
-                        ),
-                        Err(error) => Err(error),
-                    }
-                });
-                Poll::Ready(val)
-            },
-            Poll::Pending => Poll::Pending,
-        }
+        Pin::new(&mut self.inner)
+            .poll_next(ctx)
+            .map(|val| val.map(|val| val.map(|inner| LocalSocketStream {inner})))
     }
 }
 impl FusedStream for Incoming {
@@ -132,16 +129,10 @@ impl FusedStream for Incoming {
         false
     }
 }
-
-#[derive(Debug)]
-struct SyncArcIncoming {
-    inner: Arc<sync::LocalSocketListener>,
-}
-impl Iterator for SyncArcIncoming {
-    type Item = Result<sync::LocalSocketStream, io::Error>;
+impl Debug for Incoming {
     #[inline(always)]
-    fn next(&mut self) -> Option<Self::Item> {
-        Some(self.inner.accept())
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.inner, f)
     }
 }
 
@@ -166,24 +157,69 @@ impl Iterator for SyncArcIncoming {
 /// ```
 ///
 /// [`LocalSocketListener`]: struct.LocalSocketListener.html " "
-#[derive(Debug)]
 pub struct LocalSocketStream {
-    inner: Unblock<sync::LocalSocketStream>,
+    inner: LocalSocketStreamImpl,
 }
 impl LocalSocketStream {
     /// Connects to a remote local socket server.
+    ///
+    /// On Unix, connecting to a Ud-socket server either succeeds or fails right away without waiting for the server to accept the connection, so the returned future never performs any blocking work.
+    #[inline]
     pub async fn connect<'a>(
         name: impl ToLocalSocketName<'a> + Send + 'static,
     ) -> io::Result<Self> {
         Ok(Self {
-            inner: Unblock::new(unblock(move || sync::LocalSocketStream::connect(name)).await?),
+            inner: LocalSocketStreamImpl::connect(name).await?,
         })
     }
+    /// Shuts down the read half, the write half or both halves of the stream. See [the synchronous version] for the details.
+    ///
+    /// Closing the stream via [`AsyncWriteExt::close`] shuts down the write half as well.
+    ///
+    /// # Platform-specific behavior
+    /// Not supported on Windows, where an error of kind [`Other`] is always returned and closing the stream only flushes it.
+    ///
+    /// [the synchronous version]: ../../local_socket/struct.LocalSocketStream.html#method.shutdown " "
+    /// [`AsyncWriteExt::close`]: https://docs.rs/futures/*/futures/io/trait.AsyncWriteExt.html#method.close " "
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    #[inline(always)]
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.inner.shutdown(how)
+    }
+    /// Splits the stream into a read half and a write half, which can be moved to different tasks and used concurrently, and which can later be put back together with [`ReadHalf::reunite`].
+    ///
+    /// # Platform-specific behavior
+    /// On Unix, the halves operate on the socket independently. On Windows, they share the stream through a lock, since the blocking operations are offloaded to a thread pool one at a time.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use futures::io::{AsyncReadExt, AsyncWriteExt};
+    /// use interprocess::nonblocking::local_socket::LocalSocketStream;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let conn = LocalSocketStream::connect("/tmp/example.sock").await?;
+    /// let (mut reader, mut writer) = conn.into_split();
+    /// let writer_task = tokio::spawn(async move {
+    ///     writer.write_all(b"Hello from client!\n").await.map(|_| writer)
+    /// });
+    /// let mut buffer = String::new();
+    /// reader.read_to_string(&mut buffer).await?;
+    /// let writer = writer_task.await??;
+    /// let conn = reader.reunite(writer)?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`ReadHalf::reunite`]: struct.ReadHalf.html#method.reunite " "
+    #[inline]
+    pub fn into_split(self) -> (ReadHalf, WriteHalf) {
+        let (read_half, write_half) = self.inner.into_split();
+        (ReadHalf {inner: read_half}, WriteHalf {inner: write_half})
+    }
 }
 
-use futures::task::{Context, Poll};
-use std::pin::Pin;
 impl AsyncRead for LocalSocketStream {
+    #[inline(always)]
     fn poll_read(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
@@ -191,8 +227,17 @@ impl AsyncRead for LocalSocketStream {
     ) -> Poll<Result<usize, futures::io::Error>> {
         AsyncRead::poll_read(Pin::new(&mut self.inner), cx, buf)
     }
+    #[inline(always)]
+    fn poll_read_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize, futures::io::Error>> {
+        AsyncRead::poll_read_vectored(Pin::new(&mut self.inner), cx, bufs)
+    }
 }
 impl AsyncWrite for LocalSocketStream {
+    #[inline(always)]
     fn poll_write(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
@@ -200,12 +245,22 @@ impl AsyncWrite for LocalSocketStream {
     ) -> Poll<Result<usize, futures::io::Error>> {
         AsyncWrite::poll_write(Pin::new(&mut self.inner), cx, buf)
     }
+    #[inline(always)]
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<Result<usize, futures::io::Error>> {
+        AsyncWrite::poll_write_vectored(Pin::new(&mut self.inner), cx, bufs)
+    }
+    #[inline(always)]
     fn poll_flush(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Result<(), futures::io::Error>> {
         AsyncWrite::poll_flush(Pin::new(&mut self.inner), cx)
     }
+    #[inline(always)]
     fn poll_close(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
@@ -213,3 +268,186 @@ impl AsyncWrite for LocalSocketStream {
         AsyncWrite::poll_close(Pin::new(&mut self.inner), cx)
     }
 }
+impl Debug for LocalSocketStream {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+
+/// The read half of an asynchronous [`LocalSocketStream`], created by [`into_split`].
+///
+/// [`LocalSocketStream`]: struct.LocalSocketStream.html " "
+/// [`into_split`]: struct.LocalSocketStream.html#method.into_split " "
+pub struct ReadHalf {
+    inner: ReadHalfImpl,
+}
+impl ReadHalf {
+    /// Puts the stream back together from the two halves, failing if the write half was split off a different stream.
+    #[inline]
+    pub fn reunite(self, write_half: WriteHalf) -> Result<LocalSocketStream, ReuniteError<Self, WriteHalf>> {
+        match self.inner.reunite(write_half.inner) {
+            Ok(inner) => Ok(LocalSocketStream {inner}),
+            Err((read_half, write_half)) => Err(ReuniteError {
+                read_half: Self {inner: read_half},
+                write_half: WriteHalf {inner: write_half},
+            }),
+        }
+    }
+}
+impl AsyncRead for ReadHalf {
+    #[inline(always)]
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize, futures::io::Error>> {
+        AsyncRead::poll_read(Pin::new(&mut self.inner), cx, buf)
+    }
+    #[inline(always)]
+    fn poll_read_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<Result<usize, futures::io::Error>> {
+        AsyncRead::poll_read_vectored(Pin::new(&mut self.inner), cx, bufs)
+    }
+}
+impl Debug for ReadHalf {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+
+/// The write half of an asynchronous [`LocalSocketStream`], created by [`into_split`].
+///
+/// [`LocalSocketStream`]: struct.LocalSocketStream.html " "
+/// [`into_split`]: struct.LocalSocketStream.html#method.into_split " "
+pub struct WriteHalf {
+    inner: WriteHalfImpl,
+}
+impl WriteHalf {
+    /// Puts the stream back together from the two halves, failing if the read half was split off a different stream.
+    #[inline(always)]
+    pub fn reunite(self, read_half: ReadHalf) -> Result<LocalSocketStream, ReuniteError<ReadHalf, Self>> {
+        read_half.reunite(self)
+    }
+}
+impl AsyncWrite for WriteHalf {
+    #[inline(always)]
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, futures::io::Error>> {
+        AsyncWrite::poll_write(Pin::new(&mut self.inner), cx, buf)
+    }
+    #[inline(always)]
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<Result<usize, futures::io::Error>> {
+        AsyncWrite::poll_write_vectored(Pin::new(&mut self.inner), cx, bufs)
+    }
+    #[inline(always)]
+    fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), futures::io::Error>> {
+        AsyncWrite::poll_flush(Pin::new(&mut self.inner), cx)
+    }
+    #[inline(always)]
+    fn poll_close(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), futures::io::Error>> {
+        AsyncWrite::poll_close(Pin::new(&mut self.inner), cx)
+    }
+}
+impl Debug for WriteHalf {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        FutureExt, StreamExt,
+    };
+    use super::{LocalSocketListener, LocalSocketStream};
+    use crate::rand_string;
+
+    #[tokio::test]
+    async fn cancelled_accept() {
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = LocalSocketListener::bind(socket_name.clone()).await.unwrap();
+        // Dropping a pending accept must not lose the connection that arrives afterwards.
+        assert!(listener.accept().now_or_never().is_none());
+
+        let client = tokio::spawn(async move {
+            let mut conn = LocalSocketStream::connect(socket_name).await.unwrap();
+            conn.write_all(b"Hello from client!\n").await.unwrap();
+            let mut conn = BufReader::new(conn);
+            let mut buffer = String::new();
+            conn.read_line(&mut buffer).await.unwrap();
+            assert_eq!(buffer, "Hello from server!\n");
+        });
+
+        let mut conn = BufReader::new(listener.incoming().next().await.unwrap().unwrap());
+        let mut buffer = String::new();
+        conn.read_line(&mut buffer).await.unwrap();
+        assert_eq!(buffer, "Hello from client!\n");
+        conn.get_mut().write_all(b"Hello from server!\n").await.unwrap();
+        client.await.unwrap();
+    }
+    #[tokio::test]
+    async fn split() {
+        use futures::io::AsyncReadExt;
+
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = LocalSocketListener::bind(socket_name.clone()).await.unwrap();
+        let client = LocalSocketStream::connect(socket_name).await.unwrap();
+        let mut server = listener.accept().await.unwrap();
+        let (mut reader, mut writer) = client.into_split();
+        // The read is started before anything is written, so that the write half has to make
+        // progress while the read half is waiting.
+        let read = tokio::spawn(async move {
+            let mut buffer = [0; 4];
+            reader.read_exact(&mut buffer).await.map(|_| (reader, buffer))
+        });
+        writer.write_all(b"ping").await.unwrap();
+        let mut buffer = [0; 4];
+        server.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, b"ping");
+        server.write_all(b"pong").await.unwrap();
+        let (reader, buffer) = read.await.unwrap().unwrap();
+        assert_eq!(&buffer, b"pong");
+        reader.reunite(writer).unwrap();
+    }
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn close_shuts_down_write_half() {
+        use futures::io::AsyncReadExt;
+
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = LocalSocketListener::bind(socket_name.clone()).await.unwrap();
+        let client = LocalSocketStream::connect(socket_name).await.unwrap();
+        let mut server = listener.accept().await.unwrap();
+        let (mut reader, mut writer) = client.into_split();
+        writer.write_all(b"request").await.unwrap();
+        writer.close().await.unwrap();
+        let mut request = Vec::new();
+        server.read_to_end(&mut request).await.unwrap();
+        assert_eq!(request, b"request");
+        server.write_all(b"response").await.unwrap();
+        server.close().await.unwrap();
+        let mut response = Vec::new();
+        reader.read_to_end(&mut response).await.unwrap();
+        assert_eq!(response, b"response");
+    }
+}
@@ -5,3 +5,6 @@
 //! The layout of this module aims to closely resemble the crate root, in that all the modules here mirror their blocking counterparts — check them out for usage examples and details about the differences you may encounter when porting blocking code to an async architecture.
 
 pub mod local_socket;
+#[cfg(unix)]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+pub mod signal;
@@ -0,0 +1,147 @@
+//! Asynchronous reception of Unix signals, mirroring [`os::unix::signal`].
+//!
+//! Only the reception side needs an asynchronous counterpart: handlers are still installed with [`set_handler`] and [`HandlerOptions`], and the subscriptions here cooperate with them exactly like the blocking [`Signals`] does.
+//!
+//! # Example
+//! ```no_run
+//! # #[cfg(unix)]
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use futures::StreamExt;
+//! use interprocess::{
+//!     nonblocking::signal::Signals,
+//!     os::unix::signal::SignalType,
+//! };
+//!
+//! let mut signals = Signals::new(&[SignalType::Termination, SignalType::KeyboardInterrupt])?;
+//! if let Some(info) = signals.next().await {
+//!     println!("Shutting down because of {:?}", info.signal);
+//! }
+//! # Ok(()) }
+//! ```
+//!
+//! [`os::unix::signal`]: ../../os/unix/signal/index.html " "
+//! [`set_handler`]: ../../os/unix/signal/fn.set_handler.html " "
+//! [`HandlerOptions`]: ../../os/unix/signal/struct.HandlerOptions.html " "
+//! [`Signals`]: ../../os/unix/signal/struct.Signals.html " "
+
+use async_io::Async;
+use futures::{
+    ready,
+    stream::{FusedStream, Stream},
+    task::{Context, Poll},
+};
+use std::{
+    io,
+    fmt::{self, Formatter, Debug},
+    os::unix::io::{AsRawFd, RawFd},
+    pin::Pin,
+};
+use crate::os::unix::signal::{
+    Signals as SyncSignals,
+    SignalInfo,
+    SignalType,
+    SetHandlerError,
+};
+
+/// A subscription to a set of signals, delivering them as a [`Stream`] of [`SignalInfo`].
+///
+/// This is the asynchronous counterpart of the blocking [`Signals`] — see its documentation for how subscriptions interact with signal handlers. The stream ends if reading the notifications fails, which can only happen if the process is out of resources.
+///
+/// [`Stream`]: https://docs.rs/futures/*/futures/stream/trait.Stream.html " "
+/// [`SignalInfo`]: ../../os/unix/signal/struct.SignalInfo.html " "
+/// [`Signals`]: ../../os/unix/signal/struct.Signals.html " "
+pub struct Signals {
+    inner: Async<SyncSignals>,
+    ended: bool,
+}
+impl Signals {
+    /// Creates a subscription to the specified standard signals. More signals, including real-time ones, can be added with [`add_signal`] and [`add_rtsignal`].
+    ///
+    /// [`add_signal`]: #method.add_signal " "
+    /// [`add_rtsignal`]: #method.add_rtsignal " "
+    #[inline]
+    pub fn new<'a>(signals: impl IntoIterator<Item = &'a SignalType>) -> Result<Self, SetHandlerError> {
+        Ok(Self::from_sync(SyncSignals::new(signals)?)?)
+    }
+    /// Subscribes to the specified standard signal.
+    #[inline(always)]
+    pub fn add_signal(&mut self, signal: SignalType) -> Result<(), SetHandlerError> {
+        self.inner.get_mut().add_signal(signal)
+    }
+    /// Subscribes to the specified real-time signal.
+    ///
+    /// # Panics
+    /// Panics if the specified real-time signal is outside the range of real-time signals supported by the OS. See [`NUM_REALTIME_SIGNALS`].
+    ///
+    /// [`NUM_REALTIME_SIGNALS`]: ../../os/unix/signal/constant.NUM_REALTIME_SIGNALS.html " "
+    #[inline(always)]
+    pub fn add_rtsignal(&mut self, rtsignal: u32) -> Result<(), SetHandlerError> {
+        self.inner.get_mut().add_rtsignal(rtsignal)
+    }
+    /// Converts a blocking subscription into an asynchronous one, keeping all of its signals.
+    #[inline]
+    pub fn from_sync(signals: SyncSignals) -> io::Result<Self> {
+        Ok(Self {
+            inner: Async::new(signals)?,
+            ended: false,
+        })
+    }
+}
+impl Stream for Signals {
+    type Item = SignalInfo;
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.ended {
+            return Poll::Ready(None);
+        }
+        loop {
+            // The pipe is in non-blocking mode, so this either returns a pending signal right away
+            // or reports that there is none yet, in which case we wait for the pipe to become
+            // readable and try again.
+            match self.inner.get_ref().wait() {
+                Ok(info) => return Poll::Ready(Some(info)),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(..) => break,
+            }
+            if ready!(self.inner.poll_readable(ctx)).is_err() {
+                break;
+            }
+        }
+        self.ended = true;
+        Poll::Ready(None)
+    }
+}
+impl FusedStream for Signals {
+    #[inline(always)]
+    fn is_terminated(&self) -> bool {
+        self.ended
+    }
+}
+impl Debug for Signals {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.inner.get_ref(), f)
+    }
+}
+impl AsRawFd for Signals {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::{FutureExt, StreamExt};
+    use super::Signals;
+    use crate::os::unix::signal::{Signal, SignalType};
+
+    #[tokio::test]
+    async fn stream() {
+        let mut signals = Signals::new(&[SignalType::Continue]).unwrap();
+        assert!(signals.next().now_or_never().is_none());
+        unsafe {libc::raise(libc::SIGCONT)};
+        let info = signals.next().await.unwrap();
+        assert_eq!(info.signal, Signal::Standard(SignalType::Continue));
+        assert_eq!(info.sender_pid, Some(std::process::id()));
+    }
+}
@@ -1,26 +1,39 @@
+use libc::c_int;
 use std::{
     io::{self, IoSlice, IoSliceMut, prelude::*},
     fmt::{self, Formatter, Debug},
     ffi::{OsStr, OsString, CStr, CString},
     borrow::Cow,
+    convert::TryFrom,
+    net::Shutdown,
+    time::Duration,
     os::unix::{
         io::{AsRawFd, IntoRawFd, FromRawFd},
         ffi::{OsStrExt, OsStringExt},
     },
 };
+use crate::{WriteMsg, Sealed};
+#[cfg(target_os = "linux")]
+use crate::ReliableReadMsg;
 use crate::local_socket::{
     NameTypeSupport,
     LocalSocketName,
+    LocalSocketListenerOptions,
     ToLocalSocketName,
 };
+#[cfg(target_os = "linux")]
+use super::udsocket::PeerCredentials;
 use super::udsocket::{
     UdStreamListener,
+    UdStreamListenerOptions,
     UdSocketPath,
     UdStream,
+    UdSeqpacketListener,
+    UdSeqpacket,
 };
 
 pub(crate) struct LocalSocketListener {
-    inner: UdStreamListener,
+    pub(crate) inner: UdStreamListener,
 }
 impl LocalSocketListener {
     #[inline]
@@ -29,11 +42,43 @@ impl LocalSocketListener {
         let inner = UdStreamListener::bind(path)?;
         Ok(Self {inner})
     }
+    #[inline]
+    pub fn bind_reclaiming<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        let path = local_socket_name_to_ud_socket_path(name.to_local_socket_name()?)?;
+        let inner = UdStreamListener::bind_reclaiming(path)?;
+        Ok(Self {inner})
+    }
+    #[inline]
+    pub fn create<'a>(name: impl ToLocalSocketName<'a>, options: &LocalSocketListenerOptions) -> io::Result<Self> {
+        let path = local_socket_name_to_ud_socket_path(name.to_local_socket_name()?)?;
+        let inner = UdStreamListenerOptions::new()
+            .backlog(c_int::try_from(options.backlog).unwrap_or(c_int::MAX))
+            .reclaim(options.reclaim)
+            .create(path)?;
+        if options.nonblocking {
+            inner.set_nonblocking(true)?;
+        }
+        Ok(Self {inner})
+    }
     #[inline(always)]
     pub fn accept(&self) -> io::Result<LocalSocketStream> {
         let inner = self.inner.accept()?;
         Ok(LocalSocketStream {inner})
     }
+    #[inline(always)]
+    pub fn accept_timeout(&self, timeout: Duration) -> io::Result<LocalSocketStream> {
+        let inner = self.inner.accept_timeout(timeout)?;
+        Ok(LocalSocketStream {inner})
+    }
+    #[inline(always)]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<LocalSocketName<'static>> {
+        ud_socket_path_to_local_socket_name(self.inner.local_addr()?)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "the listener is not bound to any name"))
+    }
 }
 impl Debug for LocalSocketListener {
     #[inline]
@@ -63,7 +108,7 @@ impl FromRawFd for LocalSocketListener {
 }
 
 pub(crate) struct LocalSocketStream {
-    inner: UdStream,
+    pub(crate) inner: UdStream,
 }
 impl LocalSocketStream {
     #[inline]
@@ -72,29 +117,100 @@ impl LocalSocketStream {
         let inner = UdStream::connect(path)?;
         Ok(Self {inner})
     }
+    #[inline]
+    pub fn pair() -> io::Result<(Self, Self)> {
+        let (inner1, inner2) = UdStream::pair()?;
+        Ok((Self {inner: inner1}, Self {inner: inner2}))
+    }
+    #[inline(always)]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+    #[inline(always)]
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.inner.set_read_timeout(timeout)
+    }
+    #[inline(always)]
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.inner.set_write_timeout(timeout)
+    }
+    #[inline(always)]
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        self.inner.read_timeout()
+    }
+    #[inline(always)]
+    pub fn write_timeout(&self) -> io::Result<Option<Duration>> {
+        self.inner.write_timeout()
+    }
+    #[inline(always)]
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.inner.shutdown(how)
+    }
+    #[inline]
+    pub fn try_clone(&self) -> io::Result<Self> {
+        let inner = self.inner.try_clone()?;
+        Ok(Self {inner})
+    }
+    #[cfg(target_os = "linux")]
+    #[inline(always)]
+    pub fn peer_credentials(&self) -> io::Result<PeerCredentials> {
+        self.inner.peer_credentials()
+    }
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<Option<LocalSocketName<'static>>> {
+        Ok(ud_socket_path_to_local_socket_name(self.inner.local_addr()?))
+    }
+    #[inline]
+    pub fn peer_addr(&self) -> io::Result<Option<LocalSocketName<'static>>> {
+        Ok(ud_socket_path_to_local_socket_name(self.inner.peer_addr()?))
+    }
+}
+impl Read for &LocalSocketStream {
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&self.inner).read(buf)
+    }
+    #[inline(always)]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (&self.inner).read_vectored(bufs)
+    }
+}
+impl Write for &LocalSocketStream {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&self.inner).write(buf)
+    }
+    #[inline(always)]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (&self.inner).write_vectored(bufs)
+    }
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        (&self.inner).flush()
+    }
 }
 impl Read for LocalSocketStream {
     #[inline(always)]
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.read(buf)
+        (&*self).read(buf)
     }
     #[inline(always)]
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
-        self.inner.read_vectored(bufs)
+        (&*self).read_vectored(bufs)
     }
 }
 impl Write for LocalSocketStream {
     #[inline(always)]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.write(buf)
+        (&*self).write(buf)
     }
     #[inline(always)]
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        self.inner.write_vectored(bufs)
+        (&*self).write_vectored(bufs)
     }
     #[inline(always)]
     fn flush(&mut self) -> io::Result<()> {
-        self.inner.flush()
+        (&*self).flush()
     }
 }
 impl Debug for LocalSocketStream {
@@ -124,8 +240,111 @@ impl FromRawFd for LocalSocketStream {
     }
 }
 
+pub(crate) struct LocalSocketMsgListener {
+    pub(crate) inner: UdSeqpacketListener,
+}
+impl LocalSocketMsgListener {
+    #[inline]
+    pub fn bind<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        let path = local_socket_name_to_ud_socket_path(name.to_local_socket_name()?)?;
+        let inner = UdSeqpacketListener::bind(path)?;
+        Ok(Self {inner})
+    }
+    #[inline]
+    pub fn bind_reclaiming<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        let path = local_socket_name_to_ud_socket_path(name.to_local_socket_name()?)?;
+        let inner = UdSeqpacketListener::bind_reclaiming(path)?;
+        Ok(Self {inner})
+    }
+    #[inline(always)]
+    pub fn accept(&self) -> io::Result<LocalSocketMsgStream> {
+        let inner = self.inner.accept()?;
+        Ok(LocalSocketMsgStream {inner})
+    }
+    #[inline(always)]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+}
+impl Debug for LocalSocketMsgListener {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSocketMsgListener")
+            .field("file_descriptor", &self.inner.as_raw_fd())
+            .finish()
+    }
+}
+
+pub(crate) struct LocalSocketMsgStream {
+    pub(crate) inner: UdSeqpacket,
+}
+impl LocalSocketMsgStream {
+    #[inline]
+    pub fn connect<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        let path = local_socket_name_to_ud_socket_path(name.to_local_socket_name()?)?;
+        let inner = UdSeqpacket::connect(path)?;
+        Ok(Self {inner})
+    }
+    #[inline(always)]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+    #[inline(always)]
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.inner.set_read_timeout(timeout)
+    }
+    #[inline(always)]
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.inner.set_write_timeout(timeout)
+    }
+}
+#[cfg(target_os = "linux")]
+impl ReliableReadMsg for LocalSocketMsgStream {
+    #[inline(always)]
+    fn read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, Vec<u8>>> {
+        self.inner.read_msg(buf)
+    }
+    #[inline(always)]
+    fn try_read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, usize>> {
+        self.inner.try_read_msg(buf)
+    }
+}
+impl WriteMsg for LocalSocketMsgStream {
+    #[inline(always)]
+    fn write_msg(&mut self, msg: &[u8]) -> io::Result<()> {
+        self.inner.write_msg(msg)
+    }
+}
+impl Sealed for LocalSocketMsgStream {}
+impl Debug for LocalSocketMsgStream {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSocketMsgStream")
+            .field("file_descriptor", &self.inner.as_raw_fd())
+            .finish()
+    }
+}
+impl AsRawFd for LocalSocketMsgStream {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> i32 {
+        self.inner.as_raw_fd()
+    }
+}
+impl IntoRawFd for LocalSocketMsgStream {
+    #[inline(always)]
+    fn into_raw_fd(self) -> i32 {
+        self.inner.into_raw_fd()
+    }
+}
+impl FromRawFd for LocalSocketMsgStream {
+    #[inline(always)]
+    unsafe fn from_raw_fd(fd: i32) -> Self {
+        Self {inner: UdSeqpacket::from_raw_fd(fd)}
+    }
+}
+
 #[inline]
-fn local_socket_name_to_ud_socket_path(name: LocalSocketName<'_>) -> io::Result<UdSocketPath<'_>> {
+pub fn local_socket_name_to_ud_socket_path(name: LocalSocketName<'_>) -> io::Result<UdSocketPath<'_>> {
     #[inline]
     fn cow_osstr_to_cstr(osstr: Cow<'_, OsStr>) -> io::Result<Cow<'_, CStr>> {
         match osstr {
@@ -160,6 +379,18 @@ fn local_socket_name_to_ud_socket_path(name: LocalSocketName<'_>) -> io::Result<
     ))
 }
 
+/// Converts an address reported by the system back into a local socket name, or `None` if the socket is unnamed.
+fn ud_socket_path_to_local_socket_name(path: UdSocketPath<'_>) -> Option<LocalSocketName<'static>> {
+    let namespaced = match &path {
+        UdSocketPath::Unnamed => return None,
+        UdSocketPath::File(..) => false,
+        #[cfg(target_os = "linux")]
+        UdSocketPath::Namespaced(..) | UdSocketPath::NamespacedBytes(..) => true,
+    };
+    let name = OsString::from_vec(path.as_bytes().to_vec());
+    Some(LocalSocketName::from_raw_parts(Cow::Owned(name), namespaced))
+}
+
 #[inline(always)]
 pub fn name_type_support_query() -> NameTypeSupport {
     NAME_TYPE_ALWAYS_SUPPORTED
@@ -174,7 +405,7 @@ const AT_SIGN: u8 = 0x40;
 #[inline]
 pub fn to_local_socket_name_osstr(mut val: &OsStr) -> LocalSocketName<'_> {
     let mut namespaced = false;
-    if let Some(AT_SIGN) = val.as_bytes().get(0).copied() {
+    if let Some(AT_SIGN) = val.as_bytes().first().copied() {
         if val.len() >= 2 {
             val = OsStr::from_bytes(&val.as_bytes()[1..]);
         } else {
@@ -187,7 +418,7 @@ pub fn to_local_socket_name_osstr(mut val: &OsStr) -> LocalSocketName<'_> {
 #[inline]
 pub fn to_local_socket_name_osstring(mut val: OsString) -> LocalSocketName<'static> {
     let mut namespaced = false;
-    if let Some(AT_SIGN) = val.as_bytes().get(0).copied() {
+    if let Some(AT_SIGN) = val.as_bytes().first().copied() {
         let new_val = {
             let mut vec = val.into_vec();
             vec.remove(0);
@@ -39,6 +39,10 @@ pub mod udsocket;
 pub(crate) mod unnamed_pipe;
 #[cfg(unix)]
 pub(crate) mod local_socket;
+#[cfg(all(unix, feature = "nonblocking"))]
+pub(crate) mod nonblocking;
+#[cfg(all(unix, feature = "tokio"))]
+pub(crate) mod tokio;
 
 #[cfg(unix)]
 use libc::{
@@ -109,6 +113,64 @@ impl FdOps {
         }
     }
 }
+/// Sets or clears the `O_NONBLOCK` flag on the specified file descriptor, leaving the other file status flags unchanged.
+#[cfg(unix)]
+pub(crate) fn set_nonblocking(fd: c_int, nonblocking: bool) -> io::Result<()> {
+    let (success, old_flags) = unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL, 0);
+        (flags != -1, flags)
+    };
+    if !success {
+        return Err(io::Error::last_os_error());
+    }
+    let new_flags = if nonblocking {
+        old_flags | libc::O_NONBLOCK
+    } else {
+        old_flags & !libc::O_NONBLOCK
+    };
+    if new_flags == old_flags {
+        return Ok(());
+    }
+    let success = unsafe {
+        libc::fcntl(fd, libc::F_SETFL, new_flags) != -1
+    };
+    if success {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+/// Checks whether the `O_NONBLOCK` flag is set on the specified file descriptor.
+#[cfg(unix)]
+pub(crate) fn get_nonblocking(fd: c_int) -> io::Result<bool> {
+    let (success, flags) = unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL, 0);
+        (flags != -1, flags)
+    };
+    if success {
+        Ok(flags & libc::O_NONBLOCK != 0)
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+/// Lets the other tasks on the executor run before continuing, for the async wrappers to retry operations which can't be waited for through readiness events.
+#[cfg(all(unix, any(feature = "nonblocking", feature = "tokio")))]
+pub(crate) async fn yield_now() {
+    use std::{future::Future, pin::Pin, task::{Context, Poll}};
+    struct YieldNow(bool);
+    impl Future for YieldNow {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+    YieldNow(false).await
+}
 #[cfg(unix)]
 impl AsRawFd for FdOps {
     #[inline(always)]
@@ -0,0 +1,274 @@
+use async_io::Async;
+use futures::{
+    ready,
+    stream::Stream,
+    task::{Context, Poll},
+    AsyncRead, AsyncWrite,
+};
+use std::{
+    io::{self, IoSlice, IoSliceMut},
+    fmt::{self, Formatter, Debug},
+    net::Shutdown,
+    os::unix::io::AsRawFd,
+    pin::Pin,
+    sync::Arc,
+};
+
+use crate::local_socket::ToLocalSocketName;
+use super::super::{
+    yield_now,
+    local_socket::local_socket_name_to_ud_socket_path,
+    udsocket::{UdStreamListener, UdStream},
+};
+
+pub struct LocalSocketListener {
+    inner: Arc<Async<UdStreamListener>>,
+}
+impl LocalSocketListener {
+    #[inline]
+    pub async fn bind(name: impl ToLocalSocketName<'_> + Send + 'static) -> io::Result<Self> {
+        let path = local_socket_name_to_ud_socket_path(name.to_local_socket_name()?)?;
+        let inner = Async::new(UdStreamListener::bind(path)?)?;
+        Ok(Self {inner: Arc::new(inner)})
+    }
+    #[inline]
+    pub async fn accept(&self) -> io::Result<LocalSocketStream> {
+        let inner = self.inner.read_with(UdStreamListener::accept_nonblocking).await?;
+        LocalSocketStream::from_sync(inner)
+    }
+    #[inline]
+    pub fn incoming(&self) -> Incoming {
+        Incoming {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+impl Debug for LocalSocketListener {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSocketListener")
+            .field("file_descriptor", &self.inner.as_raw_fd())
+            .finish()
+    }
+}
+
+pub struct Incoming {
+    inner: Arc<Async<UdStreamListener>>,
+}
+impl Stream for Incoming {
+    type Item = io::Result<LocalSocketStream>;
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            // The listener is in non-blocking mode, so this either returns a pending connection
+            // right away or reports that there is none yet, in which case we register interest in
+            // the listener becoming readable and try again once it does.
+            match self.inner.get_ref().accept_nonblocking() {
+                Ok(inner) => return Poll::Ready(Some(LocalSocketStream::from_sync(inner))),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+            if let Err(e) = ready!(self.inner.poll_readable(ctx)) {
+                return Poll::Ready(Some(Err(e)));
+            }
+        }
+    }
+}
+impl Debug for Incoming {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Incoming")
+            .field("file_descriptor", &self.inner.as_raw_fd())
+            .finish()
+    }
+}
+
+/// Runs the specified operation on the stream until it stops failing with `WouldBlock`, waiting for the stream to become readable in between.
+fn poll_read_with<R>(
+    inner: &Async<UdStream>,
+    cx: &mut Context<'_>,
+    mut op: impl FnMut(&UdStream) -> io::Result<R>,
+) -> Poll<io::Result<R>> {
+    loop {
+        match op(inner.get_ref()) {
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+            result => return Poll::Ready(result),
+        }
+        ready!(inner.poll_readable(cx))?;
+    }
+}
+/// Same as `poll_read_with`, but waits for the stream to become writable.
+fn poll_write_with<R>(
+    inner: &Async<UdStream>,
+    cx: &mut Context<'_>,
+    mut op: impl FnMut(&UdStream) -> io::Result<R>,
+) -> Poll<io::Result<R>> {
+    loop {
+        match op(inner.get_ref()) {
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+            result => return Poll::Ready(result),
+        }
+        ready!(inner.poll_writable(cx))?;
+    }
+}
+
+pub struct LocalSocketStream {
+    inner: Async<UdStream>,
+}
+impl LocalSocketStream {
+    #[inline]
+    pub async fn connect<'a>(name: impl ToLocalSocketName<'a> + Send + 'static) -> io::Result<Self> {
+        let path = local_socket_name_to_ud_socket_path(name.to_local_socket_name()?)?;
+        let inner = Async::new(UdStream::new_nonblocking()?)?;
+        loop {
+            match inner.get_ref().connect_nonblocking(&path) {
+                Ok(true) => break,
+                Ok(false) => {
+                    inner.writable().await?;
+                    if let Some(error) = inner.get_ref().take_error()? {
+                        return Err(error);
+                    }
+                    break;
+                }
+                // The server's backlog is full, and there's no readiness event for it getting
+                // room again, so give the other tasks a chance to run before retrying.
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => yield_now().await,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(Self {inner})
+    }
+    #[inline]
+    fn from_sync(inner: UdStream) -> io::Result<Self> {
+        Ok(Self {
+            inner: Async::new(inner)?,
+        })
+    }
+    #[inline(always)]
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.inner.get_ref().shutdown(how)
+    }
+    #[inline]
+    pub fn into_split(self) -> (ReadHalf, WriteHalf) {
+        let inner = Arc::new(self.inner);
+        (ReadHalf {inner: Arc::clone(&inner)}, WriteHalf {inner})
+    }
+}
+
+impl AsyncRead for LocalSocketStream {
+    #[inline(always)]
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        poll_read_with(&self.inner, cx, |s| s.recv_nonblocking(buf))
+    }
+    #[inline(always)]
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<usize>> {
+        poll_read_with(&self.inner, cx, |s| s.recv_vectored_nonblocking(bufs))
+    }
+}
+impl AsyncWrite for LocalSocketStream {
+    #[inline(always)]
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        poll_write_with(&self.inner, cx, |s| s.send_nonblocking(buf))
+    }
+    #[inline(always)]
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        poll_write_with(&self.inner, cx, |s| s.send_vectored_nonblocking(bufs))
+    }
+    #[inline(always)]
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_flush(Pin::new(&mut self.inner), cx)
+    }
+    #[inline(always)]
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(AsyncWrite::poll_flush(Pin::new(&mut self.inner), cx))?;
+        Poll::Ready(self.inner.get_ref().shutdown(Shutdown::Write))
+    }
+}
+impl Debug for LocalSocketStream {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSocketStream")
+            .field("file_descriptor", &self.inner.as_raw_fd())
+            .finish()
+    }
+}
+
+pub struct ReadHalf {
+    inner: Arc<Async<UdStream>>,
+}
+impl ReadHalf {
+    #[inline]
+    pub fn reunite(self, write_half: WriteHalf) -> Result<LocalSocketStream, (Self, WriteHalf)> {
+        if !Arc::ptr_eq(&self.inner, &write_half.inner) {
+            return Err((self, write_half));
+        }
+        drop(write_half);
+        let inner = Arc::try_unwrap(self.inner)
+            .expect("the halves of a stream are the only owners of the stream");
+        Ok(LocalSocketStream {inner})
+    }
+}
+impl AsyncRead for ReadHalf {
+    #[inline(always)]
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        poll_read_with(&self.inner, cx, |s| s.recv_nonblocking(buf))
+    }
+    #[inline(always)]
+    fn poll_read_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<usize>> {
+        poll_read_with(&self.inner, cx, |s| s.recv_vectored_nonblocking(bufs))
+    }
+}
+impl Debug for ReadHalf {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadHalf")
+            .field("file_descriptor", &self.inner.as_raw_fd())
+            .finish()
+    }
+}
+
+pub struct WriteHalf {
+    inner: Arc<Async<UdStream>>,
+}
+impl AsyncWrite for WriteHalf {
+    #[inline(always)]
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        poll_write_with(&self.inner, cx, |s| s.send_nonblocking(buf))
+    }
+    #[inline(always)]
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        poll_write_with(&self.inner, cx, |s| s.send_vectored_nonblocking(bufs))
+    }
+    #[inline(always)]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_flush(Pin::new(&mut &*self.inner), cx)
+    }
+    #[inline(always)]
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(AsyncWrite::poll_flush(Pin::new(&mut &*self.inner), cx))?;
+        Poll::Ready(self.inner.get_ref().shutdown(Shutdown::Write))
+    }
+}
+impl Debug for WriteHalf {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteHalf")
+            .field("file_descriptor", &self.inner.as_raw_fd())
+            .finish()
+    }
+}
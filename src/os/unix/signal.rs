@@ -10,6 +10,15 @@
 //! # Real-time signals
 //! In addition to usual signals, there's a special group of signals called "real-time signals". Those signals do not have fixed identifiers and are not generated by the system or kernel. Instead, they can only be sent between processes.
 //!
+//! Real-time signals are numbered starting from 0, which corresponds to `SIGRTMIN` in C, up to [`NUM_REALTIME_SIGNALS`] exclusive.
+//!
+//! Unlike standard signals, real-time signals are queued rather than merged, and can carry an integer or a pointer-sized value when sent with [`send_rt_with_value`], which the receiver retrieves from the [`SignalInfo`] passed to its handler or returned by a [`Signals`] subscription.
+//!
+//! [`NUM_REALTIME_SIGNALS`]: constant.NUM_REALTIME_SIGNALS.html " "
+//! [`send_rt_with_value`]: fn.send_rt_with_value.html " "
+//! [`SignalInfo`]: struct.SignalInfo.html " "
+//! [`Signals`]: struct.Signals.html " "
+//!
 //! # Signal-safe system calls
 //! Not all system calls can be called from a signal handler. Allocating memory, using the thread API and manipulating interval timers, for example, is prohibited in a signal handler. Any attempt to call a system call which is not signal safe results in undefined behavior, i.e. memory unsafety. Rather than excluding certain specific system calls, the POSIX specification only speicifies system calls which *are* signal-safe. The following C functions are guaranteed to be safe to call from a signal handler:
 //! - `_Exit`
@@ -141,8 +150,8 @@ use libc::{
     SIGUSR1, SIGUSR2, SIGCHLD, SIGCONT, SIGSTOP, SIGTSTP, SIGTTIN, SIGTTOU, SIGBUS, SIGPROF,
     SIGPOLL, SIGSYS, SIGTRAP, SIGURG, SIGVTALRM, SIGXCPU, SIGXFSZ,
     SIG_DFL,
-    SA_NOCLDSTOP, SA_NODEFER, SA_RESETHAND, SA_RESTART,
-    sigaction,
+    SA_NOCLDSTOP, SA_NODEFER, SA_RESETHAND, SA_RESTART, SA_SIGINFO,
+    sigaction, siginfo_t,
 };
 #[cfg(not(unix))]
 macro_rules! fake_consts {
@@ -163,24 +172,26 @@ fake_consts! {
 }
 use std::{
     io::{self, prelude::*},
-    fmt::{self, Formatter, Display},
+    fmt::{self, Formatter, Display, Debug},
     error::Error,
     convert::TryFrom,
     mem::{self, zeroed},
     panic, process, thread,
     any::Any,
+    sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering},
+    iter::FusedIterator,
 };
 #[cfg(unix)]
-use spin::RwLock;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
 #[cfg(unix)]
-use intmap::IntMap;
+use libc::{c_int, c_void};
+#[cfg(unix)]
+use spin::Mutex;
 #[cfg(unix)]
 use thiserror::Error;
 #[cfg(unix)]
 use cfg_if::cfg_if;
 #[cfg(unix)]
-use lazy_static::lazy_static;
-#[cfg(unix)]
 cfg_if! {
     if #[cfg(any(
         target_os = "linux",
@@ -212,6 +223,41 @@ cfg_if! {
         const SIGRTMAX: i32 = 0;
     }
 }
+// The values of si_code for signals sent by processes, which libc doesn't provide for all platforms.
+#[cfg(unix)]
+cfg_if! {
+    if #[cfg(any(
+        target_os = "freebsd",
+        target_os = "dragonfly",
+        target_os = "macos",
+        target_os = "ios",
+    ))] {
+        const SI_USER: i32 = 0x10001;
+        const SI_QUEUE: i32 = 0x10002;
+    } else if #[cfg(any(
+        target_os = "openbsd",
+        target_os = "solaris",
+        target_os = "illumos",
+    ))] {
+        const SI_USER: i32 = 0;
+        const SI_QUEUE: i32 = -2;
+    } else {
+        const SI_USER: i32 = 0;
+        const SI_QUEUE: i32 = -1;
+    }
+}
+/// Returns `true` if the specified `si_code` means that the sender fields of `siginfo_t` are filled in.
+#[cfg(unix)]
+fn is_sent_by_process(code: i32) -> bool {
+    // tkill and tgkill, which raise() uses on Linux, have their own code.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        if code == libc::SI_TKILL {
+            return true;
+        }
+    }
+    code == SI_USER || code == SI_QUEUE
+}
 #[cfg(unix)]
 const _NUM_REALTIME_SIGNALS: u32 = (SIGRTMAX - SIGRTMIN + 1) as u32;
 #[cfg(not(unix))]
@@ -241,10 +287,100 @@ pub const fn is_valid_rtsignal(rtsignal: u32) -> bool {
 
 /// The first field is the current method of handling a specific signal, the second one is the flags which were set for it.
 type HandlerAndFlags = (SignalHandler, i32);
+
+/// The amount of slots in the handler table, which is indexed by signal number: every standard signal number, including ones which have no `SignalType` counterpart, and every real-time signal.
 #[cfg(unix)]
-lazy_static! {
-    static ref HANDLERS: RwLock<IntMap<HandlerAndFlags>> = RwLock::new(IntMap::new());
+const NUM_SIGNAL_SLOTS: usize = if SIGRTMAX >= 32 {SIGRTMAX as usize + 1} else {32};
+// The kinds of handlers stored in a slot.
+const HANDLER_DEFAULT: usize = 0;
+const HANDLER_IGNORE: usize = 1;
+const HANDLER_HOOK: usize = 2;
+const HANDLER_INFO_HOOK: usize = 3;
+
+/// An entry in the handler table. Only consists of atomics, so that the signal receiver can read and update it without locking or allocating, both of which are not async-signal-safe.
+struct HandlerSlot {
+    /// The kind of the handler. The function pointers are kept in separate fields, since they can't be tagged with the kind reliably.
+    handler: AtomicUsize,
+    /// The function of the last `Hook` handler, or 0 if there never was one. Never reset, so that the function is valid for a receiver which has seen the kind before it was changed.
+    hook: AtomicUsize,
+    /// Same as `hook`, but for `InfoHook` handlers.
+    info_hook: AtomicUsize,
+    flags: AtomicI32,
+    /// A bitmask of the [`Signals`] instances which are subscribed to the signal, indexed the same way as `SUBSCRIBER_PIPES`.
+    subscribers: AtomicUsize,
+    /// Whether the current `Ignore` handler was installed by a subscription in place of `Default`, and is thus to be reverted once the last subscriber is gone. Cleared whenever the handler is set by anything else.
+    ignored_by_subscribers: AtomicBool,
 }
+impl HandlerSlot {
+    // Only used to initialize the table, where every slot is a separate copy.
+    #[allow(clippy::declare_interior_mutable_const)]
+    const DEFAULT: Self = Self {
+        handler: AtomicUsize::new(HANDLER_DEFAULT),
+        hook: AtomicUsize::new(0),
+        info_hook: AtomicUsize::new(0),
+        flags: AtomicI32::new(0),
+        subscribers: AtomicUsize::new(0),
+        ignored_by_subscribers: AtomicBool::new(false),
+    };
+    fn load(&self) -> HandlerAndFlags {
+        let handler = self.decode_handler(self.handler.load(Ordering::Acquire));
+        (handler, self.flags.load(Ordering::Acquire))
+    }
+    /// Stores a new entry. The flags and the function go first, so that the receiver never sees a new handler with stale flags or without its function.
+    fn store(&self, (handler, flags): HandlerAndFlags) {
+        self.flags.store(flags, Ordering::Release);
+        let kind = match handler {
+            SignalHandler::Default => HANDLER_DEFAULT,
+            SignalHandler::Ignore => HANDLER_IGNORE,
+            SignalHandler::Hook(hook) => {
+                self.hook.store(hook.inner() as usize, Ordering::Release);
+                HANDLER_HOOK
+            },
+            SignalHandler::InfoHook(hook) => {
+                self.info_hook.store(hook.inner() as usize, Ordering::Release);
+                HANDLER_INFO_HOOK
+            },
+        };
+        self.handler.store(kind, Ordering::Release);
+    }
+    fn decode_handler(&self, kind: usize) -> SignalHandler {
+        // SAFETY: the function fields only ever contain function pointers of the right type once
+        // the kind says so
+        match kind {
+            HANDLER_HOOK => SignalHandler::Hook(SignalHook(unsafe {
+                mem::transmute::<usize, fn()>(self.hook.load(Ordering::Acquire))
+            })),
+            HANDLER_INFO_HOOK => SignalHandler::InfoHook(SignalInfoHook(unsafe {
+                mem::transmute::<usize, fn(&SignalInfo)>(self.info_hook.load(Ordering::Acquire))
+            })),
+            HANDLER_IGNORE => SignalHandler::Ignore,
+            _ => SignalHandler::Default,
+        }
+    }
+}
+
+/// The handlers for all signals, indexed by signal number, which the signal receiver dispatches according to.
+#[cfg(unix)]
+static HANDLERS: [HandlerSlot; NUM_SIGNAL_SLOTS] = [HandlerSlot::DEFAULT; NUM_SIGNAL_SLOTS];
+/// Serializes changes to the handler table. Never taken by the signal receiver, so a signal arriving while a handler is being set up can't deadlock.
+#[cfg(unix)]
+static HANDLERS_WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// The maximum amount of [`Signals`] instances which can exist in the process at the same time, equal to the amount of bits in a `usize`.
+///
+/// [`Signals`]: struct.Signals.html " "
+pub const MAX_SIGNAL_SUBSCRIBERS: usize = mem::size_of::<usize>() * 8;
+const NO_PIPE: i32 = -1;
+/// The write ends of the pipes of all [`Signals`] instances, or `NO_PIPE` for free entries.
+#[cfg(unix)]
+static SUBSCRIBER_PIPES: [AtomicI32; MAX_SIGNAL_SUBSCRIBERS] = {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const FREE: AtomicI32 = AtomicI32::new(NO_PIPE);
+    [FREE; MAX_SIGNAL_SUBSCRIBERS]
+};
+/// How many signal receivers are currently writing into subscriber pipes, which must not be closed until that's done.
+#[cfg(unix)]
+static NOTIFICATIONS_IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
 
 /// Installs the specified handler for the specified standard signal, using the default values for the flags.
 ///
@@ -359,7 +495,7 @@ pub fn set_rthandler(rtsignal: u32, handler: SignalHandler) -> Result<(), SetHan
 unsafe fn install_hook(signum: i32, hook: usize, flags: i32) -> io::Result<()> {
     let success = {
         let [mut old_handler, mut new_handler] = [zeroed::<sigaction>(); 2];
-        new_handler.sa_sigaction = hook as usize;
+        new_handler.sa_sigaction = hook;
         new_handler.sa_flags = flags;
         libc::sigaction(
             signum,
@@ -465,7 +601,7 @@ impl HandlerOptions {
             many are supported"
         );
         Self {
-            signal: rtsignal as i32,
+            signal: SIGRTMIN + rtsignal as i32,
             handler: None,
             ignore_child_stop_events: false,
             recursive_handler: false,
@@ -514,11 +650,7 @@ impl HandlerOptions {
     /// Installs the signal handler.
     #[inline]
     pub fn set(self) -> Result<(), SetHandlerError> {
-        if let Ok(val) = SignalType::try_from(self.signal) {
-            if val.is_unsafe() {
-                return Err(SetHandlerError::UnsafeSignal);
-            }
-        }
+        self.check_safe()?;
         unsafe {self.set_unsafe()}
     }
     
@@ -532,82 +664,76 @@ impl HandlerOptions {
     /// [`SegmentationFault`]: enum.SignalType.html#variant.SegmentationFault " "
     /// [`BusError`]: enum.SignalType.html#variant.BusError " "
     pub unsafe fn set_unsafe(self) -> Result<(), SetHandlerError> {
+        self.check_handleable()?;
+        let _guard = HANDLERS_WRITE_LOCK.lock();
+        self.set_locked()
+    }
+
+    fn check_safe(&self) -> Result<(), SetHandlerError> {
+        if let Ok(val) = SignalType::try_from(self.signal) {
+            if val.is_unsafe() {
+                return Err(SetHandlerError::UnsafeSignal);
+            }
+        }
+        Ok(())
+    }
+    fn check_handleable(&self) -> Result<(), SetHandlerError> {
         if let Ok(val) = SignalType::try_from(self.signal) {
             if val.is_unblockable() {
                 return Err(SetHandlerError::UnblockableSignal(val));
             }
-        } else if !is_valid_rtsignal(self.signal as u32) {
+        } else if !(SIGRTMIN..=SIGRTMAX).contains(&self.signal) {
             return Err(SetHandlerError::RealTimeSignalOutOfBounds {
-                attempted: self.signal as u32,
+                attempted: self.signal.wrapping_sub(SIGRTMIN) as u32,
                 max: NUM_REALTIME_SIGNALS,
             });
         }
-        let handlers = HANDLERS.upgradeable_read();
+        Ok(())
+    }
+    /// The part of [`set_unsafe`] which runs with `HANDLERS_WRITE_LOCK` held, which the caller must have taken and checked the signal beforehand.
+    ///
+    /// # Safety
+    /// Same as [`set_unsafe`].
+    ///
+    /// [`set_unsafe`]: #method.set_unsafe " "
+    unsafe fn set_locked(self) -> Result<(), SetHandlerError> {
+        let slot = &HANDLERS[self.signal as usize];
+        let (existing_handler, existing_flags) = slot.load();
+        let new_handler = self.handler.unwrap_or(existing_handler);
         let new_flags = self.flags_as_i32();
-        let mut need_to_upgrade_handle = false;
-        let need_to_install_hook = if let Some(
-            (existing_handler, existing_flags)
-        ) = handlers.get(self.signal as u64) {
-            // This signal's handler was set before — check if we need to install new flags or if
-            // one is default and another isn't, which would mean that either that no hook was
-            // installed and we have to install one or there was one installed but we need to
-            // install the default hook explicitly.
-            let new_handler = self.handler.unwrap_or_default();
-            if new_handler != *existing_handler || new_flags != *existing_flags {
-                need_to_upgrade_handle = true;
-                true
-            } else {
-                let one_handler_is_default_and_another_isnt = (
-                    new_handler.is_default() && !existing_handler.is_default()
-                ) || (
-                    !new_handler.is_default() && existing_handler.is_default()
-                );
-                   *existing_flags != new_flags
-                || one_handler_is_default_and_another_isnt
+        if new_handler.is_default() {
+            // The hook must be gone before the table says so, or the receiver could find no
+            // handler for a signal it was called for.
+            if !existing_handler.is_default() {
+                install_hook(self.signal, SIG_DFL, new_flags)?;
             }
-            
+            slot.store((new_handler, new_flags));
         } else {
-            need_to_upgrade_handle = true;
-            !self.handler.unwrap_or_default().is_default()
-        };
-
-        if need_to_install_hook {
-            let hook_val = match self.handler.unwrap_or_default() {
-                SignalHandler::Default => SIG_DFL,
-                _ => signal_receiver as usize,
-            };
-            unsafe {
-                // SAFETY: we're using a correct value for the hook
-                install_hook(
-                    self.signal,
-                    hook_val,
-                    new_flags,
-                )?
+            // Conversely, the table must be ready before the hook is installed.
+            slot.store((new_handler, new_flags));
+            if existing_handler.is_default() || existing_flags != new_flags {
+                let hook = signal_receiver as extern "C" fn(i32, *mut siginfo_t, *mut c_void) as usize;
+                if let Err(error) = install_hook(self.signal, hook, new_flags | SA_SIGINFO) {
+                    slot.store((existing_handler, existing_flags));
+                    return Err(error.into());
+                }
             }
         }
-        if need_to_upgrade_handle {
-            let mut handlers = handlers.upgrade();
-            let signal_u64 = self.signal as u64;
-            handlers.remove(signal_u64);
-            handlers.insert(signal_u64, (
-                self.handler.unwrap_or_default(),
-                new_flags,
-            ));
-        }
+        slot.ignored_by_subscribers.store(false, Ordering::Relaxed);
         Ok(())
     }
 
     #[inline]
     fn flags_as_i32(self) -> i32 {
         if self.handler.unwrap_or_default().is_default() {
-            debug_assert_eq!(
-                self.recursive_handler, false,
+            debug_assert!(
+                !self.recursive_handler,
                 "cannot use the recursive_handler flag with the default handling method",
             );
         }
         if self.signal != SIGCHLD {
-            debug_assert_eq!(
-                self.ignore_child_stop_events, false,
+            debug_assert!(
+                !self.ignore_child_stop_events,
                 "cannot use the ignore_child_stop_events flag when the signal to be handled isn't \
                 ChildProcessEvent",
             );
@@ -671,28 +797,44 @@ pub enum SetHandlerError {
     UnexpectedSystemCallFailure (#[cfg_attr(unix, from)] io::Error),
 }
 
-/// The actual hook which is passed to `sigaction` which dispatches signals according to the global handler map (the `HANDLERS` static).
-extern fn signal_receiver(signum: i32) {
+/// The actual hook which is passed to `sigaction` which dispatches signals according to the global handler table (the `HANDLERS` static).
+///
+/// Everything here must be async-signal-safe, which is why the table is only ever accessed with atomic operations.
+extern "C" fn signal_receiver(signum: i32, info: *mut siginfo_t, _context: *mut c_void) {
     let catched = panic::catch_unwind(|| {
-        let handler_and_flags = {
-            let handlers = HANDLERS.read();
-            let val = handlers.get(signum as u64)
-                .expect("unregistered signal passed by the OS to the shared receiver");
-            *val
+        let slot = match HANDLERS.get(signum as usize) {
+            Some(slot) => slot,
+            None => return,
+        };
+        // SAFETY: the OS passes a valid siginfo_t, since the hook is installed with SA_SIGINFO
+        let raw_info = match unsafe {info.as_ref()} {
+            Some(info) => RawSignalInfo::from_siginfo(signum, info),
+            None => RawSignalInfo::bare(signum),
         };
-        match handler_and_flags.0 {
+        let raw_handler = slot.handler.load(Ordering::Acquire);
+        let flags = slot.flags.load(Ordering::Acquire);
+        match slot.decode_handler(raw_handler) {
             SignalHandler::Ignore => {},
             SignalHandler::Hook(hook) => hook.inner()(),
-            SignalHandler::Default => unreachable!(
-                "signal receiver was unregistered but has been called by the OS anyway"
-            ),
+            SignalHandler::InfoHook(hook) => {
+                if let Some(info) = raw_info.decode() {
+                    hook.inner()(&info);
+                }
+            },
+            // The handler was reset while the signal was being delivered, which is the same as the
+            // signal arriving right before that.
+            SignalHandler::Default => return,
         }
-        if handler_and_flags.1 & SA_RESETHAND != 0 {
-            // If the signal is set to be reset to default handling, set the record accordingly.
-            let mut handlers = HANDLERS.write();
-            handlers.remove(signum as u64);
-            let handler_and_flags = (SignalHandler::Default, handler_and_flags.1);
-            handlers.insert(signum as u64, handler_and_flags);
+        notify_subscribers(slot, &raw_info);
+        if flags & SA_RESETHAND != 0 {
+            // The OS has already reset the handler, and so does the table, unless a different kind
+            // of handler was set in the meantime.
+            let _ = slot.handler.compare_exchange(
+                raw_handler,
+                HANDLER_DEFAULT,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            );
         }
     });
     match catched {
@@ -701,11 +843,105 @@ extern fn signal_receiver(signum: i32) {
     }
 }
 
+/// The parts of `siginfo_t` which are sent to subscriptions, in a form which can be written into a pipe as-is.
+///
+/// The sender and value fields are copied regardless of whether they're meaningful for the signal, and are only filtered by `decode`, which happens outside of the signal handler.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+struct RawSignalInfo {
+    signum: i32,
+    code: i32,
+    sender_pid: i32,
+    sender_uid: u32,
+    value: usize,
+}
+impl RawSignalInfo {
+    /// The `code` for a signal which came without a `siginfo_t`, which doesn't match any of the codes which carry a sender or a value.
+    const NO_CODE: i32 = i32::MIN;
+    fn from_siginfo(signum: i32, info: &siginfo_t) -> Self {
+        // SAFETY: those only read integers from the unions in siginfo_t, all bit patterns of which
+        // are valid
+        unsafe {
+            Self {
+                signum,
+                code: info.si_code,
+                sender_pid: info.si_pid(),
+                sender_uid: info.si_uid(),
+                value: info.si_value().sival_ptr as usize,
+            }
+        }
+    }
+    fn bare(signum: i32) -> Self {
+        Self {
+            signum,
+            code: Self::NO_CODE,
+            ..Self::default()
+        }
+    }
+    fn decode(self) -> Option<SignalInfo> {
+        let signal = Signal::from_raw(self.signum)?;
+        let (sender_pid, sender_uid) = if is_sent_by_process(self.code) {
+            (Some(self.sender_pid as u32), Some(self.sender_uid))
+        } else {
+            (None, None)
+        };
+        Some(SignalInfo {
+            signal,
+            sender_pid,
+            sender_uid,
+            code: self.code,
+            value: if self.code == SI_QUEUE {Some(self.value)} else {None},
+        })
+    }
+    fn as_bytes(&self) -> &[u8] {
+        // SAFETY: the structure is repr(C) and only consists of integers, with no padding in
+        // between
+        unsafe {std::slice::from_raw_parts(self as *const _ as *const u8, mem::size_of::<Self>())}
+    }
+}
+
+/// Writes the information about the signal into the pipes of all [`Signals`] instances subscribed to the signal. Only uses `write`, which is async-signal-safe.
+///
+/// The record is much smaller than `PIPE_BUF`, which means that it's written atomically and the reader never sees a partial one.
+fn notify_subscribers(slot: &HandlerSlot, info: &RawSignalInfo) {
+    let subscribers = slot.subscribers.load(Ordering::Acquire);
+    if subscribers == 0 {
+        return;
+    }
+    NOTIFICATIONS_IN_FLIGHT.fetch_add(1, Ordering::SeqCst);
+    // A failed write would clobber errno for the code which the signal interrupted.
+    let errno = unsafe {*errno_location()};
+    let record = info.as_bytes();
+    for (i, pipe) in SUBSCRIBER_PIPES.iter().enumerate() {
+        if subscribers & (1 << i) == 0 {
+            continue;
+        }
+        let fd = pipe.load(Ordering::SeqCst);
+        if fd != NO_PIPE {
+            // If the pipe is full, the subscriber has plenty of signals to process already, and the
+            // OS would've merged repeated standard signals anyway.
+            unsafe {libc::write(fd, record.as_ptr() as *const _, record.len())};
+        }
+    }
+    unsafe {*errno_location() = errno};
+    NOTIFICATIONS_IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+}
+#[cfg(unix)]
+cfg_if! {
+    if #[cfg(any(target_os = "linux", target_os = "redox"))] {
+        use libc::__errno_location as errno_location;
+    } else if #[cfg(any(target_os = "android", target_os = "netbsd", target_os = "openbsd"))] {
+        use libc::__errno as errno_location;
+    } else {
+        use libc::__error as errno_location;
+    }
+}
+
 fn handle_panic_from_signal_receiver(panic_payload: Box<dyn Any + Send>) -> ! {
     let panic_message = if let Some(msg) = panic_payload.downcast_ref::<&'static str>() {
         msg
     } else if let Some(msg) = panic_payload.downcast_ref::<String>() {
-        &msg
+        msg
     } else { "Box<dyn Any>" };
     let main_message = if let Some(name) = thread::current().name() {
         format!("thread {} panicked at '{}'", name, panic_message)
@@ -736,6 +972,8 @@ pub enum SignalHandler {
     Ignore,
     /// Call a function whenever the signal is received.
     Hook(SignalHook),
+    /// Call a function whenever the signal is received, passing information about the signal to it, including its sender.
+    InfoHook(SignalInfoHook),
 }
 impl SignalHandler {
     /// Returns `true` for the [`Default`] variant, `false` otherwise.
@@ -759,6 +997,13 @@ impl SignalHandler {
     pub fn is_hook(self) -> bool {
         matches!(self, Self::Hook(..))
     }
+    /// Returns `true` for the [`InfoHook`] variant, `false` otherwise.
+    ///
+    /// [`InfoHook`]: #variant.InfoHook.html " "
+    #[inline(always)]
+    pub fn is_info_hook(self) -> bool {
+        matches!(self, Self::InfoHook(..))
+    }
     /// Creates a handler which calls the specified function.
     ///
     /// # Safety
@@ -769,6 +1014,36 @@ impl SignalHandler {
     pub unsafe fn from_fn(function: fn()) -> Self {
         Self::Hook(SignalHook::from_fn(function))
     }
+    /// Creates a handler which calls the specified function with information about the received signal.
+    ///
+    /// # Safety
+    /// The function must not perform any system calls which are not considered signal-safe. See the [module-level section on signal-safe system calls] for more.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::signal::{self, SignalType, SignalHandler};
+    ///
+    /// let handler = unsafe {
+    ///     // Formatting the process ID doesn't perform any system calls, and the rest is the same as
+    ///     // in the example for from_fn.
+    ///     SignalHandler::from_info_fn(|info| {
+    ///         if let Some(pid) = info.sender_pid {
+    ///             println!("Process {} asked us to terminate", pid);
+    ///         }
+    ///     })
+    /// };
+    /// signal::set_handler(SignalType::Termination, handler)?;
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [module-level section on signal-safe system calls]: index.html#signal-safe-system-calls " "
+    #[inline(always)]
+    pub unsafe fn from_info_fn(function: fn(&SignalInfo)) -> Self {
+        Self::InfoHook(SignalInfoHook::from_fn(function))
+    }
 }
 impl Default for SignalHandler {
     /// Returns [`SignalHandler::Default`].
@@ -787,8 +1062,16 @@ impl From<SignalHook> for SignalHandler {
 }
 /// A function which can be used as a signal handler.
 #[repr(transparent)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug)]
 pub struct SignalHook (fn());
+impl PartialEq for SignalHook {
+    /// Compares the addresses of the functions. Note that the same function may have multiple addresses if it was instantiated in different codegen units.
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 as usize == other.0 as usize
+    }
+}
+impl Eq for SignalHook {}
 impl SignalHook {
     /// Creates a hook which calls the specified function.
     ///
@@ -812,6 +1095,410 @@ impl From<SignalHook> for fn() {
         op.0
     }
 }
+impl From<SignalInfoHook> for SignalHandler {
+    #[inline(always)]
+    fn from(op: SignalInfoHook) -> Self {
+        Self::InfoHook(op)
+    }
+}
+/// A function which can be used as a signal handler and receives information about the signal.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug)]
+pub struct SignalInfoHook (fn(&SignalInfo));
+impl PartialEq for SignalInfoHook {
+    /// Compares the addresses of the functions. Note that the same function may have multiple addresses if it was instantiated in different codegen units.
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 as usize == other.0 as usize
+    }
+}
+impl Eq for SignalInfoHook {}
+impl SignalInfoHook {
+    /// Creates a hook which calls the specified function.
+    ///
+    /// # Safety
+    /// The function must not perform any system calls which are not considered signal-safe. See the [module-level section on signal-safe system calls] for more.
+    ///
+    /// [module-level section on signal-safe system calls]: index.html#signal-safe-system-calls " "
+    #[inline(always)]
+    pub unsafe fn from_fn(function: fn(&SignalInfo)) -> Self {
+        Self (function)
+    }
+    /// Returns the wrapped function.
+    #[inline(always)]
+    pub fn inner(self) -> fn(&SignalInfo) {
+        self.0
+    }
+}
+impl From<SignalInfoHook> for fn(&SignalInfo) {
+    #[inline(always)]
+    fn from(op: SignalInfoHook) -> Self {
+        op.0
+    }
+}
+
+/// A real-time or standard signal, as received by [`Signals`].
+///
+/// [`Signals`]: struct.Signals.html " "
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Signal {
+    /// One of the standard signals.
+    Standard(SignalType),
+    /// A real-time signal, numbered starting from 0.
+    RealTime(u32),
+}
+impl Signal {
+    fn from_raw(signum: i32) -> Option<Self> {
+        if let Ok(signal) = SignalType::try_from(signum) {
+            Some(Self::Standard(signal))
+        } else if (SIGRTMIN..=SIGRTMAX).contains(&signum) {
+            Some(Self::RealTime((signum - SIGRTMIN) as u32))
+        } else {
+            None
+        }
+    }
+}
+impl From<SignalType> for Signal {
+    #[inline(always)]
+    fn from(op: SignalType) -> Self {
+        Self::Standard(op)
+    }
+}
+
+/// Information about a received signal, filled from the `siginfo_t` structure which the OS provides to the signal handler.
+///
+/// More fields may be added later, which is why the structure cannot be constructed or exhaustively destructured outside of `interprocess`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct SignalInfo {
+    /// The signal which was received.
+    pub signal: Signal,
+    /// The process ID of the process which sent the signal, or `None` if it was generated by the OS, such as [`SegmentationFault`] or [`AlarmClock`].
+    ///
+    /// [`SegmentationFault`]: enum.SignalType.html#variant.SegmentationFault " "
+    /// [`AlarmClock`]: enum.SignalType.html#variant.AlarmClock " "
+    pub sender_pid: Option<u32>,
+    /// The real user ID of the process which sent the signal, or `None` under the same conditions as for [`sender_pid`].
+    ///
+    /// [`sender_pid`]: #structfield.sender_pid " "
+    pub sender_uid: Option<u32>,
+    /// The raw `si_code` value, which describes why the signal was sent. Its meaning depends on the signal and the platform — consult your manual pages for the `sigaction` system call for more.
+    pub code: i32,
+    /// The value which was sent along with the signal, or `None` if the signal was not sent with `sigqueue`, which is what [`send_rt_with_value`] uses.
+    ///
+    /// If the sender used an integer value, use [`int_value`] to retrieve it instead.
+    ///
+    /// [`send_rt_with_value`]: fn.send_rt_with_value.html " "
+    /// [`int_value`]: #method.int_value " "
+    pub value: Option<usize>,
+}
+impl SignalInfo {
+    /// Returns the [`value`] sent along with the signal, interpreted as the integer variant of the value — see [`SignalValue`].
+    ///
+    /// [`value`]: #structfield.value " "
+    /// [`SignalValue`]: enum.SignalValue.html " "
+    #[inline]
+    pub fn int_value(&self) -> Option<i32> {
+        self.value.map(|value| SignalValue::Pointer(value).int_from_raw())
+    }
+}
+
+/// A value which can be sent along with a real-time signal using [`send_rt_with_value`].
+///
+/// The value is transmitted as the C `union sigval`, which means that the receiver cannot tell which variant the sender used — the sending and receiving sides need to agree on it, with [`SignalInfo::value`] corresponding to `Pointer` and [`SignalInfo::int_value`] corresponding to `Int`.
+///
+/// Pointers are only meaningful within the same address space, so sending them to other processes is only useful if they are treated as plain integers.
+///
+/// [`send_rt_with_value`]: fn.send_rt_with_value.html " "
+/// [`SignalInfo::value`]: struct.SignalInfo.html#structfield.value " "
+/// [`SignalInfo::int_value`]: struct.SignalInfo.html#method.int_value " "
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SignalValue {
+    /// The `sival_int` field.
+    Int(i32),
+    /// The `sival_ptr` field.
+    Pointer(usize),
+}
+impl SignalValue {
+    /// The value of the union as a whole, as read through the `sival_ptr` field.
+    fn to_raw(self) -> usize {
+        match self {
+            Self::Int(value) => {
+                // The integer field is at the start of the union, which is the high half of the
+                // pointer field on big-endian platforms.
+                let mut bytes = [0; mem::size_of::<usize>()];
+                bytes[..mem::size_of::<i32>()].copy_from_slice(&value.to_ne_bytes());
+                usize::from_ne_bytes(bytes)
+            },
+            Self::Pointer(value) => value,
+        }
+    }
+    /// The value of the union as read through the `sival_int` field.
+    fn int_from_raw(self) -> i32 {
+        let bytes = self.to_raw().to_ne_bytes();
+        let mut int_bytes = [0; mem::size_of::<i32>()];
+        int_bytes.copy_from_slice(&bytes[..mem::size_of::<i32>()]);
+        i32::from_ne_bytes(int_bytes)
+    }
+}
+impl From<i32> for SignalValue {
+    #[inline(always)]
+    fn from(op: i32) -> Self {
+        Self::Int(op)
+    }
+}
+impl From<usize> for SignalValue {
+    #[inline(always)]
+    fn from(op: usize) -> Self {
+        Self::Pointer(op)
+    }
+}
+
+/// A subscription to a set of signals, which receives them as values in normal context instead of running code in a signal handler.
+///
+/// The signal handler only writes the [information about the signal] into a pipe, which is then read by [`wait`] or by iterating over the subscription, so that reacting to a signal doesn't require any unsafe code and isn't restricted to [signal-safe system calls].
+///
+/// Subscribing to a signal leaves any handler installed for it with [`set_handler`] or [`HandlerOptions`] in place, and the handler keeps running before the subscribers are notified. If the signal was set to the [default handling method], it is changed to [`Ignore`] instead, so that the subscription doesn't race with the process being terminated; once the last subscription to the signal is dropped, the default handling method is restored, unless the handling method was changed in the meantime. Setting the handling method back to [`Default`] stops all subscriptions to the signal from receiving it.
+///
+/// Like the OS itself, a subscription may merge multiple instances of the same signal into one if they arrive faster than they are received.
+///
+/// # Example
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # #[cfg(unix)] {
+/// use interprocess::os::unix::signal::{Signal, Signals, SignalType};
+///
+/// let signals = Signals::new(&[SignalType::Termination, SignalType::Hangup])?;
+/// for info in &signals {
+///     match info?.signal {
+///         Signal::Standard(SignalType::Hangup) => println!("Reloading the configuration"),
+///         _ => break,
+///     }
+/// }
+/// # }
+/// # Ok(()) }
+/// ```
+///
+/// # Platform-specific behavior
+/// The notifications are delivered through a pipe on all platforms. Linux's `signalfd` isn't used, since it requires the signals to be blocked in every thread of the process, which doesn't compose with handlers installed by other code.
+///
+/// [`wait`]: #method.wait " "
+/// [information about the signal]: struct.SignalInfo.html " "
+/// [signal-safe system calls]: index.html#signal-safe-system-calls " "
+/// [`set_handler`]: fn.set_handler.html " "
+/// [`HandlerOptions`]: struct.HandlerOptions.html " "
+/// [default handling method]: enum.SignalHandler.html#variant.Default " "
+/// [`Ignore`]: enum.SignalHandler.html#variant.Ignore " "
+/// [`Default`]: enum.SignalHandler.html#variant.Default " "
+pub struct Signals {
+    index: usize,
+    reader: OwnedFd,
+    writer: Option<OwnedFd>,
+    subscribed: Vec<i32>,
+}
+impl Signals {
+    /// Creates a subscription to the specified standard signals. More signals, including real-time ones, can be added with [`add_signal`] and [`add_rtsignal`].
+    ///
+    /// An error is returned if [`MAX_SIGNAL_SUBSCRIBERS`] subscriptions already exist, or if one of the signals is unsafe or can't be handled.
+    ///
+    /// # System calls
+    /// - `pipe`
+    /// - `fcntl`
+    /// - `sigaction`, for signals which are set to the default handling method
+    ///
+    /// [`add_signal`]: #method.add_signal " "
+    /// [`add_rtsignal`]: #method.add_rtsignal " "
+    /// [`MAX_SIGNAL_SUBSCRIBERS`]: constant.MAX_SIGNAL_SUBSCRIBERS.html " "
+    pub fn new<'a>(signals: impl IntoIterator<Item = &'a SignalType>) -> Result<Self, SetHandlerError> {
+        let (reader, writer) = signal_pipe()?;
+        let index = SUBSCRIBER_PIPES.iter().position(|pipe| {
+            pipe.compare_exchange(NO_PIPE, writer.as_raw_fd(), Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+        }).ok_or_else(|| io::Error::new(io::ErrorKind::Other, "too many signal subscriptions exist"))?;
+        let mut subscription = Self {
+            index,
+            reader,
+            writer: Some(writer),
+            subscribed: Vec::new(),
+        };
+        for signal in signals {
+            subscription.add_signal(*signal)?;
+        }
+        Ok(subscription)
+    }
+    /// Subscribes to the specified standard signal.
+    ///
+    /// # System calls
+    /// - `sigaction`, if the signal is set to the default handling method
+    pub fn add_signal(&mut self, signal: SignalType) -> Result<(), SetHandlerError> {
+        self.subscribe(HandlerOptions::for_signal(signal))
+    }
+    /// Subscribes to the specified real-time signal.
+    ///
+    /// # Panics
+    /// Panics if the specified real-time signal is outside the range of real-time signals supported by the OS. See [`NUM_REALTIME_SIGNALS`].
+    ///
+    /// # System calls
+    /// - `sigaction`, if the signal is set to the default handling method
+    ///
+    /// [`NUM_REALTIME_SIGNALS`]: constant.NUM_REALTIME_SIGNALS.html " "
+    pub fn add_rtsignal(&mut self, rtsignal: u32) -> Result<(), SetHandlerError> {
+        self.subscribe(HandlerOptions::for_rtsignal(rtsignal))
+    }
+    fn subscribe(&mut self, options: HandlerOptions) -> Result<(), SetHandlerError> {
+        if self.subscribed.contains(&options.signal) {
+            return Ok(());
+        }
+        let slot = &HANDLERS[options.signal as usize];
+        // Holding the lock throughout keeps anyone from setting a handler between the check and
+        // the switch to Ignore, and keeps a subscription being dropped from restoring Default
+        // before this one is registered.
+        let _guard = HANDLERS_WRITE_LOCK.lock();
+        if slot.load().0.is_default() {
+            options.check_safe()?;
+            options.check_handleable()?;
+            unsafe {options.set_new_handler(SignalHandler::Ignore).set_locked()?};
+            slot.ignored_by_subscribers.store(true, Ordering::Relaxed);
+        }
+        slot.subscribers.fetch_or(1 << self.index, Ordering::AcqRel);
+        self.subscribed.push(options.signal);
+        Ok(())
+    }
+    /// Blocks until one of the signals arrives, returning information about it.
+    ///
+    /// # System calls
+    /// - `read`
+    pub fn wait(&self) -> io::Result<SignalInfo> {
+        loop {
+            let mut record = RawSignalInfo::default();
+            let size = mem::size_of::<RawSignalInfo>();
+            // The records are written atomically, so the pipe never contains a partial one.
+            let success = unsafe {
+                libc::read(
+                    self.reader.as_raw_fd(),
+                    &mut record as *mut _ as *mut _,
+                    size,
+                ) == size as isize
+            };
+            if !success {
+                let error = io::Error::last_os_error();
+                if error.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(error);
+            }
+            // A notification for a signal this subscription isn't interested in can only come from
+            // the previous owner of the pipe slot, if a signal raced with it being dropped.
+            if self.subscribed.contains(&record.signum) {
+                if let Some(info) = record.decode() {
+                    return Ok(info);
+                }
+            }
+        }
+    }
+    /// Returns an iterator which blocks until a signal arrives on every iteration, never ending on its own.
+    #[inline(always)]
+    pub fn iter(&self) -> SignalsIter<'_> {
+        SignalsIter {signals: self}
+    }
+}
+impl<'a> IntoIterator for &'a Signals {
+    type Item = io::Result<SignalInfo>;
+    type IntoIter = SignalsIter<'a>;
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl Debug for Signals {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signals")
+            .field("fd", &self.reader.as_raw_fd())
+            .field("subscribed", &self.subscribed)
+            .finish()
+    }
+}
+impl AsRawFd for Signals {
+    /// Returns the read end of the pipe, which becomes readable whenever a signal arrives.
+    #[inline(always)]
+    fn as_raw_fd(&self) -> c_int {
+        self.reader.as_raw_fd()
+    }
+}
+impl Drop for Signals {
+    fn drop(&mut self) {
+        let guard = HANDLERS_WRITE_LOCK.lock();
+        for signum in &self.subscribed {
+            let slot = &HANDLERS[*signum as usize];
+            let bit = 1 << self.index;
+            let remaining = slot.subscribers.fetch_and(!bit, Ordering::AcqRel) & !bit;
+            // The flag is cleared whenever the handler is set by anything other than a
+            // subscription, so if it's still there, so is the Ignore which replaced Default.
+            if remaining == 0 && slot.ignored_by_subscribers.load(Ordering::Relaxed) {
+                let options = HandlerOptions {
+                    signal: *signum,
+                    handler: Some(SignalHandler::Default),
+                    ignore_child_stop_events: false,
+                    recursive_handler: false,
+                    system_call_restart: true,
+                    auto_reset_handler: false,
+                };
+                // SAFETY: the signal was checked when it was subscribed to, and restoring the
+                // default handling method doesn't run any code in the signal handler. There's no
+                // way to report an error from here, and the handler stays at Ignore if it fails.
+                let _ = unsafe {options.set_locked()};
+            }
+        }
+        drop(guard);
+        // The pipe can only be closed once no signal receiver can be writing into it anymore.
+        SUBSCRIBER_PIPES[self.index].store(NO_PIPE, Ordering::SeqCst);
+        while NOTIFICATIONS_IN_FLIGHT.load(Ordering::SeqCst) != 0 {
+            std::hint::spin_loop();
+        }
+        drop(self.writer.take());
+    }
+}
+
+/// Creates the pipe for a subscription, with the write end non-blocking so that the signal receiver never blocks.
+fn signal_pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds: [c_int; 2] = [0; 2];
+    if unsafe {libc::pipe(fds.as_mut_ptr())} != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: we just created both of those file descriptors, which means that neither of them can
+    // be in use elsewhere
+    let (reader, writer) = unsafe {(OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1]))};
+    for fd in [&reader, &writer].iter() {
+        if unsafe {libc::fcntl(fd.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC)} == -1 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    super::set_nonblocking(writer.as_raw_fd(), true)?;
+    Ok((reader, writer))
+}
+
+/// An iterator over the signals received by a [`Signals`] subscription.
+///
+/// This iterator is created by the [`iter`] method on [`Signals`] — see its documentation for more.
+///
+/// [`Signals`]: struct.Signals.html " "
+/// [`iter`]: struct.Signals.html#method.iter " "
+#[derive(Debug)]
+pub struct SignalsIter<'a> {
+    signals: &'a Signals,
+}
+impl Iterator for SignalsIter<'_> {
+    type Item = io::Result<SignalInfo>;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.signals.wait())
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+impl FusedIterator for SignalsIter<'_> {}
 
 /// Sends the specified signal to the specified process. If the specified signal is `None`, no signal is sent and only a privilege check is performed instead.
 ///
@@ -837,8 +1524,8 @@ pub fn send(signal: impl Into<Option<SignalType>>, pid: impl Into<u32>) -> io::R
     );
     let success = unsafe {
         libc::kill(
-            signal.into().map_or(0, Into::into),
             pid,
+            signal.into().map_or(0, Into::into),
         ) != -1
     };
     if success {
@@ -871,12 +1558,12 @@ pub fn send_rt(signal: impl Into<Option<u32>>, pid: impl Into<u32>) -> io::Resul
     );
     let signal = signal.into().map_or(0, |val| {
         assert!(is_valid_rtsignal(val), "invalid real-time signal");
-        val
-    }) as i32;
+        SIGRTMIN + val as i32
+    });
     let success = unsafe {
         libc::kill(
-            signal,
             pid,
+            signal,
         ) != -1
     };
     if success {
@@ -885,6 +1572,62 @@ pub fn send_rt(signal: impl Into<Option<u32>>, pid: impl Into<u32>) -> io::Resul
         Err(io::Error::last_os_error())
     }
 }
+/// Sends the specified real-time signal to the specified process, along with a value which the receiver can retrieve from [`SignalInfo`].
+///
+/// Unlike [`send_rt`], this queues the signal: multiple instances of the same real-time signal sent this way are all delivered, each with its own value, up to a system-wide limit.
+///
+/// # Panics
+/// Guaranteed to panic if the specified real-time signal is outside the range of real-time signals supported by the OS. See [`NUM_REALTIME_SIGNALS`].
+///
+/// # System calls
+/// - `sigqueue`
+///
+/// # Example
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # #[cfg(unix)] {
+/// use interprocess::os::unix::signal::{self, Signals};
+/// use std::process;
+///
+/// let mut signals = Signals::new(&[])?;
+/// signals.add_rtsignal(0)?;
+/// // Send a real-time signal 0 carrying the number 42 to the calling process.
+/// signal::send_rt_with_value(0, process::id(), 42)?;
+/// let info = signals.wait()?;
+/// assert_eq!(info.int_value(), Some(42));
+/// # }
+/// # Ok(()) }
+/// ```
+///
+/// [`SignalInfo`]: struct.SignalInfo.html " "
+/// [`send_rt`]: fn.send_rt.html " "
+/// [`NUM_REALTIME_SIGNALS`]: constant.NUM_REALTIME_SIGNALS.html " "
+pub fn send_rt_with_value(rtsignal: u32, pid: impl Into<u32>, value: impl Into<SignalValue>) -> io::Result<()> {
+    let pid = i32::try_from(pid.into())
+        .unwrap_or_else(|_| panic!("process identifier out of range"));
+    assert!(is_valid_rtsignal(rtsignal), "invalid real-time signal");
+    let signal = SIGRTMIN + rtsignal as i32;
+    let value = libc::sigval {sival_ptr: value.into().to_raw() as *mut c_void};
+    cfg_if! {
+        if #[cfg(any(
+            target_os = "dragonfly",
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "openbsd",
+        ))] {
+            // Unreachable, since those platforms don't support real-time signals at all.
+            let _ = (pid, signal, value);
+            Err(io::Error::new(io::ErrorKind::Other, "real-time signals are not supported on this platform"))
+        } else {
+            let success = unsafe {libc::sigqueue(pid, signal, value) != -1};
+            if success {
+                Ok(())
+            } else {
+                Err(io::Error::last_os_error())
+            }
+        }
+    }
+}
 /// Sends the specified signal to the specified process group. If the specified signal is `None`, no signal is sent and only a privilege check is performed instead.
 ///
 /// # Example
@@ -906,8 +1649,8 @@ pub fn send_to_group(signal: impl Into<Option<SignalType>>, pid: impl Into<u32>)
         * -1;
     let success = unsafe {
         libc::kill(
-            signal.into().map_or(0, Into::into),
             pid,
+            signal.into().map_or(0, Into::into),
         ) != -1
     };
     if success {
@@ -926,7 +1669,7 @@ pub fn send_to_group(signal: impl Into<Option<SignalType>>, pid: impl Into<u32>)
 /// use std::process;
 ///
 /// // Send a real-timne signal 0 to the process group of the calling process.
-/// signal::send_rt(0_u32, 0_u32)?;
+/// signal::send_rt_to_group(0_u32, 0_u32)?;
 /// # }
 /// # Ok(()) }
 /// ```
@@ -942,12 +1685,12 @@ pub fn send_rt_to_group(signal: impl Into<Option<u32>>, pid: impl Into<u32>) ->
     );
     let signal = signal.into().map_or(0, |val| {
         assert!(is_valid_rtsignal(val), "invalid real-time signal");
-        val
-    }) as i32;
+        SIGRTMIN + val as i32
+    });
     let success = unsafe {
         libc::kill(
-            signal,
             pid,
+            signal,
         ) != -1
     };
     if success {
@@ -1237,3 +1980,116 @@ impl fmt::Octal for UnknownSignalError {
     }
 }
 impl Error for UnknownSignalError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    static RECEIVED: AtomicU32 = AtomicU32::new(0);
+    static RT_RECEIVED: AtomicU32 = AtomicU32::new(0);
+    static INFO_RECEIVED: AtomicU32 = AtomicU32::new(0);
+
+    #[test]
+    fn dispatch() {
+        let handler = unsafe {
+            SignalHandler::from_fn(|| {RECEIVED.fetch_add(1, Ordering::SeqCst);})
+        };
+        set_handler(SignalType::UserSignal1, handler).unwrap();
+        unsafe {libc::raise(SIGUSR1)};
+        assert_eq!(RECEIVED.load(Ordering::SeqCst), 1);
+
+        HandlerOptions::for_signal(SignalType::UserSignal1)
+            .set_new_handler(handler)
+            .auto_reset_handler(true)
+            .set()
+            .unwrap();
+        unsafe {libc::raise(SIGUSR1)};
+        assert_eq!(RECEIVED.load(Ordering::SeqCst), 2);
+        assert_eq!(HANDLERS[SIGUSR1 as usize].load().0, SignalHandler::Default);
+
+        if REALTIME_SIGNALS_SUPPORTED {
+            let handler = unsafe {
+                SignalHandler::from_fn(|| {RT_RECEIVED.fetch_add(1, Ordering::SeqCst);})
+            };
+            set_rthandler(1, handler).unwrap();
+            unsafe {libc::raise(SIGRTMIN + 1)};
+            assert_eq!(RT_RECEIVED.load(Ordering::SeqCst), 1);
+            set_rthandler(1, SignalHandler::Default).unwrap();
+        }
+
+        let handler = unsafe {
+            SignalHandler::from_info_fn(|info| {
+                if info.sender_pid == Some(process::id()) {
+                    INFO_RECEIVED.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+        };
+        set_handler(SignalType::UserSignal1, handler).unwrap();
+        assert_eq!(HANDLERS[SIGUSR1 as usize].load().0, handler);
+        unsafe {libc::raise(SIGUSR1)};
+        assert_eq!(INFO_RECEIVED.load(Ordering::SeqCst), 1);
+        assert_eq!(RECEIVED.load(Ordering::SeqCst), 2);
+        set_handler(SignalType::UserSignal1, SignalHandler::Default).unwrap();
+    }
+    #[test]
+    fn null_signal() {
+        // Fails with EINVAL if the process identifier gets passed to kill as the signal number.
+        send(None, process::id()).unwrap();
+    }
+    #[test]
+    fn subscription() {
+        let mut signals = Signals::new(&[SignalType::UserSignal2]).unwrap();
+        unsafe {libc::raise(SIGUSR2)};
+        let info = signals.wait().unwrap();
+        assert_eq!(info.signal, Signal::Standard(SignalType::UserSignal2));
+        assert_eq!(info.sender_pid, Some(process::id()));
+        assert_eq!(info.sender_uid, Some(unsafe {libc::getuid()}));
+        assert_eq!(info.value, None);
+        assert_eq!(HANDLERS[SIGUSR2 as usize].load().0, SignalHandler::Ignore);
+
+        if REALTIME_SIGNALS_SUPPORTED {
+            signals.add_rtsignal(2).unwrap();
+            unsafe {libc::raise(SIGRTMIN + 2)};
+            unsafe {libc::raise(SIGUSR2)};
+            let received = signals.iter()
+                .take(2)
+                .map(|info| info.map(|info| info.signal))
+                .collect::<io::Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(received, [Signal::RealTime(2), Signal::Standard(SignalType::UserSignal2)]);
+        }
+
+        if REALTIME_SIGNALS_SUPPORTED {
+            signals.add_rtsignal(3).unwrap();
+            send_rt_with_value(3, process::id(), -42).unwrap();
+            send_rt_with_value(3, process::id(), usize::MAX).unwrap();
+            let info = signals.wait().unwrap();
+            assert_eq!(info.signal, Signal::RealTime(3));
+            assert_eq!(info.sender_pid, Some(process::id()));
+            assert_eq!(info.int_value(), Some(-42));
+            // Unlike with kill, both instances of the signal are delivered.
+            assert_eq!(signals.wait().unwrap().value, Some(usize::MAX));
+
+            send_rt(3, process::id()).unwrap();
+            assert_eq!(signals.wait().unwrap().value, None);
+        }
+
+        let other = Signals::new(&[SignalType::UserSignal2]).unwrap();
+        let index = signals.index;
+        drop(signals);
+        assert_eq!(HANDLERS[SIGUSR2 as usize].subscribers.load(Ordering::SeqCst) & (1 << index), 0);
+        // Still needed by the other subscription.
+        assert_eq!(HANDLERS[SIGUSR2 as usize].load().0, SignalHandler::Ignore);
+        drop(other);
+        assert_eq!(HANDLERS[SIGUSR2 as usize].load().0, SignalHandler::Default);
+
+        // Harmless by default, so the test doesn't depend on Ignore being kept.
+        let signals = Signals::new(&[SignalType::OutOfBandDataAvailable]).unwrap();
+        assert_eq!(HANDLERS[SIGURG as usize].load().0, SignalHandler::Ignore);
+        set_handler(SignalType::OutOfBandDataAvailable, SignalHandler::Ignore).unwrap();
+        drop(signals);
+        assert_eq!(HANDLERS[SIGURG as usize].load().0, SignalHandler::Ignore);
+        set_handler(SignalType::OutOfBandDataAvailable, SignalHandler::Default).unwrap();
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    io,
+    fmt::{self, Formatter, Debug},
+    net::Shutdown,
+    os::unix::io::AsRawFd,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use crate::local_socket::ToLocalSocketName;
+use super::super::{
+    local_socket::{
+        local_socket_name_to_ud_socket_path,
+        LocalSocketListener as SyncLocalSocketListener,
+        LocalSocketStream as SyncLocalSocketStream,
+    },
+    udsocket::tokio::{UdStreamListener, UdStream},
+};
+
+pub struct LocalSocketListener {
+    inner: UdStreamListener,
+}
+impl LocalSocketListener {
+    #[inline]
+    pub fn bind<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        let path = local_socket_name_to_ud_socket_path(name.to_local_socket_name()?)?;
+        let inner = UdStreamListener::bind(path)?;
+        Ok(Self {inner})
+    }
+    #[inline]
+    pub async fn accept(&self) -> io::Result<LocalSocketStream> {
+        let inner = self.inner.accept().await?;
+        Ok(LocalSocketStream {inner})
+    }
+    #[inline]
+    pub fn from_sync(sync: SyncLocalSocketListener) -> io::Result<Self> {
+        let inner = UdStreamListener::from_sync(sync.inner)?;
+        Ok(Self {inner})
+    }
+    #[inline]
+    pub fn into_sync(self) -> io::Result<SyncLocalSocketListener> {
+        let inner = self.inner.into_sync()?;
+        Ok(SyncLocalSocketListener {inner})
+    }
+}
+impl Debug for LocalSocketListener {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSocketListener")
+            .field("file_descriptor", &self.inner.as_raw_fd())
+            .finish()
+    }
+}
+
+pub struct LocalSocketStream {
+    inner: UdStream,
+}
+impl LocalSocketStream {
+    #[inline]
+    pub async fn connect<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        let path = local_socket_name_to_ud_socket_path(name.to_local_socket_name()?)?;
+        let inner = UdStream::connect(path).await?;
+        Ok(Self {inner})
+    }
+    #[inline(always)]
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.inner.shutdown(how)
+    }
+    #[inline]
+    pub fn from_sync(sync: SyncLocalSocketStream) -> io::Result<Self> {
+        let inner = UdStream::from_sync(sync.inner)?;
+        Ok(Self {inner})
+    }
+    #[inline]
+    pub fn into_sync(self) -> io::Result<SyncLocalSocketStream> {
+        let inner = self.inner.into_sync()?;
+        Ok(SyncLocalSocketStream {inner})
+    }
+}
+impl AsyncRead for LocalSocketStream {
+    #[inline(always)]
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        AsyncRead::poll_read(Pin::new(&mut self.inner), cx, buf)
+    }
+}
+impl AsyncWrite for LocalSocketStream {
+    #[inline(always)]
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        AsyncWrite::poll_write(Pin::new(&mut self.inner), cx, buf)
+    }
+    #[inline(always)]
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_flush(Pin::new(&mut self.inner), cx)
+    }
+    #[inline(always)]
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_shutdown(Pin::new(&mut self.inner), cx)
+    }
+}
+impl Debug for LocalSocketStream {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSocketStream")
+            .field("file_descriptor", &self.inner.as_raw_fd())
+            .finish()
+    }
+}
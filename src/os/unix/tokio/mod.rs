@@ -0,0 +1,75 @@
+//! Tokio integration shared by the Unix-specific async types.
+
+use std::{
+    io,
+    future::Future,
+    os::unix::io::AsRawFd,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::unix::AsyncFd;
+use super::set_nonblocking;
+
+pub mod local_socket;
+
+/// Polls the readiness of the descriptor and runs the specified operation once it's ready for reading, repeating until the operation stops failing with `WouldBlock`.
+pub fn poll_read_with<T: AsRawFd, R>(
+    fd: &AsyncFd<T>,
+    cx: &mut Context<'_>,
+    mut op: impl FnMut(&T) -> io::Result<R>,
+) -> Poll<io::Result<R>> {
+    loop {
+        let mut guard = match fd.poll_read_ready(cx) {
+            Poll::Ready(Ok(guard)) => guard,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        };
+        if let Ok(result) = guard.try_io(|fd| op(fd.get_ref())) {
+            return Poll::Ready(result);
+        }
+    }
+}
+/// Same as `poll_read_with`, but waits for the descriptor to become writable.
+pub fn poll_write_with<T: AsRawFd, R>(
+    fd: &AsyncFd<T>,
+    cx: &mut Context<'_>,
+    mut op: impl FnMut(&T) -> io::Result<R>,
+) -> Poll<io::Result<R>> {
+    loop {
+        let mut guard = match fd.poll_write_ready(cx) {
+            Poll::Ready(Ok(guard)) => guard,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        };
+        if let Ok(result) = guard.try_io(|fd| op(fd.get_ref())) {
+            return Poll::Ready(result);
+        }
+    }
+}
+/// Puts the descriptor into non-blocking mode and registers it with Tokio's reactor.
+#[inline]
+pub fn register<T: AsRawFd>(sync: T) -> io::Result<AsyncFd<T>> {
+    set_nonblocking(sync.as_raw_fd(), true)?;
+    AsyncFd::new(sync)
+}
+/// Unregisters the descriptor from Tokio's reactor and puts it back into blocking mode.
+#[inline]
+pub fn unregister<T: AsRawFd>(fd: AsyncFd<T>) -> io::Result<T> {
+    let sync = fd.into_inner();
+    set_nonblocking(sync.as_raw_fd(), false)?;
+    Ok(sync)
+}
+
+/// Turns a polling closure into a future. `std::future::poll_fn` is too new for us, and the `tokio` feature doesn't pull in `futures`.
+#[inline]
+pub async fn poll_fn<R>(f: impl FnMut(&mut Context<'_>) -> Poll<R>) -> R {
+    struct PollFn<F>(F);
+    impl<F> Unpin for PollFn<F> {}
+    impl<R, F: FnMut(&mut Context<'_>) -> Poll<R>> Future for PollFn<F> {
+        type Output = R;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<R> {
+            (self.0)(cx)
+        }
+    }
+    PollFn(f).await
+}
@@ -0,0 +1,565 @@
+//! Batched datagram I/O, which sends or receives many datagrams with a single system call where the platform allows it.
+//!
+//! On Linux, `recvmmsg` and `sendmmsg` are used. Everywhere else, as well as on kernels which predate those system calls, the same work is done with a loop of `recvmsg` or `sendmsg` calls, which is slower but behaves the same way.
+
+use libc::{c_int, c_uint, c_void, iovec, sockaddr_un, MSG_TRUNC, MSG_CTRUNC};
+use std::{
+    fmt::{self, Formatter, Debug},
+    io,
+    iter::FusedIterator,
+    mem::{self, zeroed},
+    os::unix::io::OwnedFd,
+};
+use super::{
+    AncillaryData,
+    AncillaryDataDecoder,
+    ToUdSocketPath,
+    UdSocketPath,
+    make_sockaddr_un,
+};
+
+#[cfg(target_os = "linux")]
+use libc::mmsghdr;
+// Mirrors the Linux structure, so that the fallback loop can work with the same headers.
+#[cfg(not(target_os = "linux"))]
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone)]
+struct mmsghdr {
+    msg_hdr: libc::msghdr,
+    msg_len: c_uint,
+}
+
+/// A reusable set of buffers for receiving many datagrams at once with [`UdSocket::recv_batch`].
+///
+/// The batch is created with a fixed capacity and a fixed maximum size for every datagram, and allocates all of its memory upfront, so receiving into it never allocates. Each call to `recv_batch` replaces the datagrams received by the previous one, which can be inspected with [`get`] or [`iter`] in between.
+///
+/// # Example
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # #[cfg(unix)] {
+/// use interprocess::os::unix::udsocket::{UdSocket, RecvBatch};
+///
+/// let socket = UdSocket::bind("/tmp/telemetry.sock")?;
+/// // Up to 64 datagrams of up to 512 bytes each per system call
+/// let mut batch = RecvBatch::new(64, 512);
+/// loop {
+///     socket.recv_batch(&mut batch)?;
+///     for datagram in &batch {
+///         println!("Received {} bytes from {:?}", datagram.data().len(), datagram.addr());
+///     }
+/// }
+/// # }
+/// # Ok(()) }
+/// ```
+///
+/// [`UdSocket::recv_batch`]: struct.UdSocket.html#method.recv_batch " "
+/// [`get`]: #method.get " "
+/// [`iter`]: #method.iter " "
+pub struct RecvBatch {
+    data: Vec<u8>,
+    msg_size: usize,
+    ancillary: Vec<u8>,
+    ancillary_size: usize,
+    addrs: Vec<sockaddr_un>,
+    // Point into the buffers above, which never reallocate, so they're set up once and reused. The
+    // I/O vectors are only ever accessed through the headers.
+    _iovs: Vec<iovec>,
+    hdrs: Vec<mmsghdr>,
+    received: usize,
+}
+// SAFETY: the raw pointers in the headers only ever point into the buffers owned by the batch, which
+// are only accessed through the batch itself.
+unsafe impl Send for RecvBatch {}
+unsafe impl Sync for RecvBatch {}
+impl RecvBatch {
+    /// Creates a batch which can receive up to `capacity` datagrams of up to `msg_size` bytes each, without room for ancillary data.
+    ///
+    /// Datagrams larger than `msg_size` are truncated, which is reported by [`ReceivedDatagram::is_truncated`].
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    ///
+    /// [`ReceivedDatagram::is_truncated`]: struct.ReceivedDatagram.html#method.is_truncated " "
+    #[inline(always)]
+    pub fn new(capacity: usize, msg_size: usize) -> Self {
+        Self::with_ancillary(capacity, msg_size, 0)
+    }
+    /// Creates a batch which can receive up to `capacity` datagrams of up to `msg_size` bytes each, along with up to `ancillary_size` bytes of ancillary data for every datagram.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    pub fn with_ancillary(capacity: usize, msg_size: usize, ancillary_size: usize) -> Self {
+        assert!(capacity != 0, "cannot create a batch with zero capacity");
+        let mut data = vec![0; capacity * msg_size];
+        let mut ancillary = vec![0; capacity * ancillary_size];
+        // SAFETY: sockaddr_un consists of integers
+        let mut addrs = vec![unsafe {zeroed::<sockaddr_un>()}; capacity];
+        let mut iovs = (0..capacity).map(|i| iovec {
+            iov_base: data[i * msg_size..].as_mut_ptr() as *mut c_void,
+            iov_len: msg_size,
+        }).collect::<Vec<_>>();
+        let hdrs = (0..capacity).map(|i| {
+            // SAFETY: mmsghdr consists of integers and pointers, all of which are nullable
+            let mut hdr = unsafe {zeroed::<mmsghdr>()};
+            hdr.msg_hdr.msg_name = &mut addrs[i] as *mut _ as *mut _;
+            hdr.msg_hdr.msg_iov = &mut iovs[i];
+            hdr.msg_hdr.msg_iovlen = 1;
+            if ancillary_size != 0 {
+                hdr.msg_hdr.msg_control = ancillary[i * ancillary_size..].as_mut_ptr() as *mut _;
+            }
+            hdr
+        }).collect::<Vec<_>>();
+        Self {
+            data,
+            msg_size,
+            ancillary,
+            ancillary_size,
+            addrs,
+            _iovs: iovs,
+            hdrs,
+            received: 0,
+        }
+    }
+    /// Returns the maximum amount of datagrams which can be received into the batch at once.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.hdrs.len()
+    }
+    /// Returns the amount of datagrams received by the last call to [`UdSocket::recv_batch`].
+    ///
+    /// [`UdSocket::recv_batch`]: struct.UdSocket.html#method.recv_batch " "
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.received
+    }
+    /// Returns `true` if the last call to [`UdSocket::recv_batch`] didn't receive anything or if there was no such call yet, `false` otherwise.
+    ///
+    /// [`UdSocket::recv_batch`]: struct.UdSocket.html#method.recv_batch " "
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.received == 0
+    }
+    /// Returns the datagram with the specified index among the ones received by the last call to [`UdSocket::recv_batch`], or `None` if there are fewer datagrams than that.
+    ///
+    /// [`UdSocket::recv_batch`]: struct.UdSocket.html#method.recv_batch " "
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<ReceivedDatagram<'_>> {
+        if index >= self.received {
+            return None;
+        }
+        let hdr = &self.hdrs[index];
+        let len = (hdr.msg_len as usize).min(self.msg_size);
+        // The type of msg_controllen differs between platforms.
+        #[allow(clippy::unnecessary_cast)]
+        let ancillary_len = (hdr.msg_hdr.msg_controllen as usize).min(self.ancillary_size);
+        let data_start = index * self.msg_size;
+        let ancillary_start = index * self.ancillary_size;
+        Some(ReceivedDatagram {
+            data: &self.data[data_start..data_start + len],
+            ancillary: &self.ancillary[ancillary_start..ancillary_start + ancillary_len],
+            addr: &self.addrs[index],
+            addrlen: hdr.msg_hdr.msg_namelen as usize,
+            flags: hdr.msg_hdr.msg_flags,
+        })
+    }
+    /// Returns an iterator over the datagrams received by the last call to [`UdSocket::recv_batch`].
+    ///
+    /// [`UdSocket::recv_batch`]: struct.UdSocket.html#method.recv_batch " "
+    #[inline(always)]
+    pub fn iter(&self) -> ReceivedDatagrams<'_> {
+        ReceivedDatagrams {batch: self, index: 0}
+    }
+
+    /// Resets the parts of the headers which the system overwrites when receiving.
+    fn reset_headers(&mut self) {
+        self.received = 0;
+        for hdr in &mut self.hdrs {
+            hdr.msg_hdr.msg_namelen = mem::size_of::<sockaddr_un>() as _;
+            hdr.msg_hdr.msg_controllen = self.ancillary_size as _;
+            hdr.msg_hdr.msg_flags = 0;
+            hdr.msg_len = 0;
+        }
+    }
+}
+impl Debug for RecvBatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecvBatch")
+            .field("capacity", &self.capacity())
+            .field("len", &self.received)
+            .field("msg_size", &self.msg_size)
+            .field("ancillary_size", &self.ancillary_size)
+            .finish()
+    }
+}
+impl<'a> IntoIterator for &'a RecvBatch {
+    type Item = ReceivedDatagram<'a>;
+    type IntoIter = ReceivedDatagrams<'a>;
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A datagram received into a [`RecvBatch`], borrowing its contents from the batch.
+///
+/// [`RecvBatch`]: struct.RecvBatch.html " "
+#[derive(Copy, Clone)]
+pub struct ReceivedDatagram<'a> {
+    data: &'a [u8],
+    ancillary: &'a [u8],
+    addr: &'a sockaddr_un,
+    addrlen: usize,
+    flags: c_int,
+}
+impl<'a> ReceivedDatagram<'a> {
+    /// Returns the contents of the datagram.
+    #[inline(always)]
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+    /// Returns `true` if a part of the datagram was discarded because it didn't fit into the buffer, `false` otherwise.
+    #[inline(always)]
+    pub fn is_truncated(&self) -> bool {
+        self.flags & MSG_TRUNC != 0
+    }
+    /// Returns the address of the sender, which is [`Unnamed`] if the sender isn't bound to any.
+    ///
+    /// [`Unnamed`]: enum.UdSocketPath.html#variant.Unnamed " "
+    #[inline]
+    pub fn addr(&self) -> UdSocketPath<'static> {
+        let mut path = UdSocketPath::Unnamed;
+        path.write_sockaddr_un_to_self(self.addr, self.addrlen);
+        path
+    }
+    /// Creates a decoder for the ancillary data which arrived along with the datagram.
+    #[inline(always)]
+    pub fn ancillary(&self) -> AncillaryDataDecoder<'a> {
+        AncillaryDataDecoder {
+            buffer: self.ancillary,
+            i: 0,
+        }
+    }
+    /// Returns `true` if some ancillary data was discarded because it didn't fit into the buffer, `false` otherwise.
+    #[inline(always)]
+    pub fn is_ancillary_truncated(&self) -> bool {
+        self.flags & MSG_CTRUNC != 0
+    }
+}
+impl Debug for ReceivedDatagram<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReceivedDatagram")
+            .field("data", &self.data)
+            .field("truncated", &self.is_truncated())
+            .field("addr", &self.addr())
+            .field("ancillary_len", &self.ancillary.len())
+            .finish()
+    }
+}
+
+/// An iterator over the datagrams in a [`RecvBatch`].
+///
+/// This iterator is created by the [`iter`] method on [`RecvBatch`] — see its documentation for more.
+///
+/// [`RecvBatch`]: struct.RecvBatch.html " "
+/// [`iter`]: struct.RecvBatch.html#method.iter " "
+#[derive(Clone, Debug)]
+pub struct ReceivedDatagrams<'a> {
+    batch: &'a RecvBatch,
+    index: usize,
+}
+impl<'a> Iterator for ReceivedDatagrams<'a> {
+    type Item = ReceivedDatagram<'a>;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let datagram = self.batch.get(self.index)?;
+        self.index += 1;
+        Some(datagram)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.batch.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+impl ExactSizeIterator for ReceivedDatagrams<'_> {}
+impl FusedIterator for ReceivedDatagrams<'_> {}
+
+struct SendEntry {
+    data_end: usize,
+    ancillary_end: usize,
+    addr: Option<(sockaddr_un, usize)>,
+}
+
+/// A reusable queue of datagrams to be sent at once with [`UdSocket::send_batch`].
+///
+/// The contents of the datagrams are copied into the batch as they're pushed, so that the memory of the batch can be reused for the next batch once it has been sent, without any allocations once the batch has grown large enough.
+///
+/// # Example
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # #[cfg(unix)] {
+/// use interprocess::os::unix::udsocket::{UdSocket, SendBatch};
+///
+/// let socket = UdSocket::connect("/tmp/telemetry.sock")?;
+/// let mut batch = SendBatch::new();
+/// for sample in 0..64_u32 {
+///     batch.push(&sample.to_ne_bytes());
+/// }
+/// while !batch.is_empty() {
+///     socket.send_batch(&mut batch)?;
+/// }
+/// # }
+/// # Ok(()) }
+/// ```
+///
+/// [`UdSocket::send_batch`]: struct.UdSocket.html#method.send_batch " "
+#[derive(Default)]
+pub struct SendBatch {
+    data: Vec<u8>,
+    ancillary: Vec<u8>,
+    entries: Vec<SendEntry>,
+    sent: usize,
+    // Directories opened to shorten destination paths, which must stay open until the datagrams are
+    // sent.
+    parent_dirs: Vec<OwnedFd>,
+    // Scratch space for the system call, rebuilt on every send since the buffers above can move.
+    iovs: Vec<iovec>,
+    hdrs: Vec<mmsghdr>,
+}
+// SAFETY: the raw pointers in the scratch space are only valid during a send and rebuilt before every
+// one.
+unsafe impl Send for SendBatch {}
+unsafe impl Sync for SendBatch {}
+impl SendBatch {
+    /// Creates an empty batch.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Creates an empty batch with room for the specified amount of datagrams, which all together take up to `total_size` bytes.
+    #[inline]
+    pub fn with_capacity(capacity: usize, total_size: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(total_size),
+            entries: Vec::with_capacity(capacity),
+            iovs: Vec::with_capacity(capacity),
+            hdrs: Vec::with_capacity(capacity),
+            ..Self::default()
+        }
+    }
+    /// Adds a datagram to the batch, to be sent to the peer the socket is connected to.
+    #[inline]
+    pub fn push(&mut self, buf: &[u8]) {
+        self.push_entry(buf, None);
+    }
+    /// Adds a datagram with ancillary data to the batch, to be sent to the peer the socket is connected to.
+    #[inline]
+    pub fn push_ancillary<'a>(
+        &mut self,
+        buf: &[u8],
+        ancillary_data: impl IntoIterator<Item = AncillaryData<'a>>,
+    ) {
+        self.push_ancillary_entry(buf, ancillary_data, None);
+    }
+    /// Adds a datagram to the batch, to be sent to the specified address.
+    ///
+    /// An error is returned if the address is invalid, in which case the batch is left unchanged.
+    #[inline]
+    pub fn push_to<'a>(&mut self, buf: &[u8], path: impl ToUdSocketPath<'a>) -> io::Result<()> {
+        let addr = self.make_addr(path)?;
+        self.push_entry(buf, Some(addr));
+        Ok(())
+    }
+    /// Adds a datagram with ancillary data to the batch, to be sent to the specified address.
+    ///
+    /// An error is returned if the address is invalid, in which case the batch is left unchanged.
+    #[inline]
+    pub fn push_to_ancillary<'a, 'b>(
+        &mut self,
+        buf: &[u8],
+        ancillary_data: impl IntoIterator<Item = AncillaryData<'a>>,
+        path: impl ToUdSocketPath<'b>,
+    ) -> io::Result<()> {
+        let addr = self.make_addr(path)?;
+        self.push_ancillary_entry(buf, ancillary_data, Some(addr));
+        Ok(())
+    }
+    /// Returns the amount of datagrams in the batch which haven't been sent yet.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len() - self.sent
+    }
+    /// Returns `true` if all datagrams in the batch have been sent or if there were none to begin with, `false` otherwise.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Removes all datagrams from the batch, keeping the allocated memory for reuse.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.ancillary.clear();
+        self.entries.clear();
+        self.sent = 0;
+        self.parent_dirs.clear();
+    }
+
+    fn make_addr<'a>(&mut self, path: impl ToUdSocketPath<'a>) -> io::Result<(sockaddr_un, usize)> {
+        let (addr, addrlen, parent_dir) = make_sockaddr_un(&path.to_socket_path()?)?;
+        self.parent_dirs.extend(parent_dir);
+        Ok((addr, addrlen))
+    }
+    fn push_ancillary_entry<'a>(
+        &mut self,
+        buf: &[u8],
+        ancillary_data: impl IntoIterator<Item = AncillaryData<'a>>,
+        addr: Option<(sockaddr_un, usize)>,
+    ) {
+        let encoded = AncillaryData::encode(ancillary_data);
+        self.ancillary.extend_from_slice(encoded.as_ref());
+        self.push_entry(buf, addr);
+    }
+    fn push_entry(&mut self, buf: &[u8], addr: Option<(sockaddr_un, usize)>) {
+        self.data.extend_from_slice(buf);
+        self.entries.push(SendEntry {
+            data_end: self.data.len(),
+            ancillary_end: self.ancillary.len(),
+            addr,
+        });
+    }
+    /// Fills out the scratch space with headers for the datagrams which haven't been sent yet.
+    fn prepare_headers(&mut self) {
+        self.iovs.clear();
+        self.hdrs.clear();
+        let (mut data_start, mut ancillary_start) = match self.sent.checked_sub(1) {
+            Some(last_sent) => {
+                let entry = &self.entries[last_sent];
+                (entry.data_end, entry.ancillary_end)
+            },
+            None => (0, 0),
+        };
+        for entry in &self.entries[self.sent..] {
+            self.iovs.push(iovec {
+                iov_base: self.data[data_start..].as_ptr() as *mut c_void,
+                iov_len: entry.data_end - data_start,
+            });
+            data_start = entry.data_end;
+        }
+        for (i, entry) in self.entries[self.sent..].iter().enumerate() {
+            // SAFETY: as in RecvBatch
+            let mut hdr = unsafe {zeroed::<mmsghdr>()};
+            if let Some((addr, addrlen)) = &entry.addr {
+                hdr.msg_hdr.msg_name = addr as *const _ as *mut _;
+                hdr.msg_hdr.msg_namelen = *addrlen as _;
+            }
+            hdr.msg_hdr.msg_iov = &mut self.iovs[i];
+            hdr.msg_hdr.msg_iovlen = 1;
+            let ancillary_len = entry.ancillary_end - ancillary_start;
+            if ancillary_len != 0 {
+                hdr.msg_hdr.msg_control = self.ancillary[ancillary_start..].as_ptr() as *mut _;
+                hdr.msg_hdr.msg_controllen = ancillary_len as _;
+            }
+            ancillary_start = entry.ancillary_end;
+            self.hdrs.push(hdr);
+        }
+    }
+    /// Marks the specified amount of datagrams as sent, clearing the batch if all of them are.
+    fn advance(&mut self, amount: usize) {
+        self.sent += amount;
+        if self.sent == self.entries.len() {
+            self.clear();
+        }
+    }
+}
+impl Debug for SendBatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendBatch")
+            .field("len", &self.len())
+            .field("sent", &self.sent)
+            .finish()
+    }
+}
+
+/// Receives as many datagrams as are available into the batch, waiting for the first one to arrive.
+pub(super) fn recv_batch(fd: c_int, batch: &mut RecvBatch) -> io::Result<usize> {
+    batch.reset_headers();
+    #[cfg(target_os = "linux")]
+    {
+        let result = unsafe {
+            libc::recvmmsg(
+                fd,
+                batch.hdrs.as_mut_ptr(),
+                batch.hdrs.len().min(c_uint::MAX as usize) as c_uint,
+                libc::MSG_WAITFORONE as _,
+                std::ptr::null_mut(),
+            )
+        };
+        if result != -1 {
+            batch.received = result as usize;
+            return Ok(batch.received);
+        }
+        let error = io::Error::last_os_error();
+        if error.raw_os_error() != Some(libc::ENOSYS) {
+            return Err(error);
+        }
+    }
+    for i in 0..batch.hdrs.len() {
+        // Only wait for the first datagram, just like MSG_WAITFORONE does.
+        let flags = if i == 0 {0} else {libc::MSG_DONTWAIT};
+        let hdr = &mut batch.hdrs[i];
+        let result = unsafe {libc::recvmsg(fd, &mut hdr.msg_hdr, flags)};
+        if result == -1 {
+            let error = io::Error::last_os_error();
+            if i == 0 {
+                return Err(error);
+            }
+            // Whatever went wrong, the datagrams which did arrive are reported first, and the error
+            // will show up again on the next call if it persists.
+            break;
+        }
+        hdr.msg_len = result as c_uint;
+        batch.received = i + 1;
+    }
+    Ok(batch.received)
+}
+/// Sends the datagrams in the batch which haven't been sent yet, returning how many were sent.
+pub(super) fn send_batch(fd: c_int, batch: &mut SendBatch) -> io::Result<usize> {
+    if batch.is_empty() {
+        return Ok(0);
+    }
+    batch.prepare_headers();
+    #[cfg(target_os = "linux")]
+    {
+        let result = unsafe {
+            libc::sendmmsg(
+                fd,
+                batch.hdrs.as_mut_ptr(),
+                batch.hdrs.len().min(c_uint::MAX as usize) as c_uint,
+                0,
+            )
+        };
+        if result != -1 {
+            batch.advance(result as usize);
+            return Ok(result as usize);
+        }
+        let error = io::Error::last_os_error();
+        if error.raw_os_error() != Some(libc::ENOSYS) {
+            return Err(error);
+        }
+    }
+    let mut sent = 0;
+    for hdr in &batch.hdrs {
+        let result = unsafe {libc::sendmsg(fd, &hdr.msg_hdr, 0)};
+        if result == -1 {
+            let error = io::Error::last_os_error();
+            if sent == 0 {
+                return Err(error);
+            }
+            // Same as with receiving, the error will come up again on the next call.
+            break;
+        }
+        sent += 1;
+    }
+    batch.advance(sent);
+    Ok(sent)
+}
@@ -0,0 +1,161 @@
+//! Passing ownership of file descriptors through Ud-sockets, shared by the stream and datagram socket types.
+//!
+//! Unlike the raw ancillary data interface, everything here deals in `OwnedFd` and `BorrowedFd`, so that received file descriptors are closed as soon as they're dropped and can never be leaked by accident.
+
+use libc::{c_int, c_uint, msghdr, SOL_SOCKET, SCM_RIGHTS, MSG_CTRUNC};
+use std::{
+    convert::TryFrom,
+    io::{self, IoSlice, IoSliceMut},
+    mem::{self, zeroed},
+    os::unix::io::{AsRawFd, BorrowedFd, FromRawFd, OwnedFd},
+    ptr,
+};
+
+/// The amount of file descriptors which can be received in one call, equal to the limit Linux imposes on the amount of file descriptors sent in one message (`SCM_MAX_FD`).
+pub(super) const MAX_FDS: usize = 253;
+
+// Platforms which can atomically set the close-on-exec flag on received file descriptors.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+))]
+const RECV_FLAGS: c_int = libc::MSG_CMSG_CLOEXEC;
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "illumos",
+)))]
+const RECV_FLAGS: c_int = 0;
+
+/// Allocates a suitably aligned buffer for control messages with the specified size in bytes.
+// usize::div_ceil is too new for our minimum supported version.
+#[allow(clippy::manual_div_ceil)]
+fn control_buffer(size: usize) -> Vec<usize> {
+    let word = mem::size_of::<usize>();
+    vec![0; (size + word - 1) / word]
+}
+
+/// Sends the specified bytes along with the specified file descriptors, returning how many bytes were sent.
+pub(super) fn send_with_fds(
+    socket: c_int,
+    buf: &[u8],
+    fds: &[BorrowedFd<'_>],
+) -> io::Result<usize> {
+    let payload_size = c_uint::try_from(fds.len() * mem::size_of::<c_int>())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many file descriptors"))?;
+    let control_size = unsafe {libc::CMSG_SPACE(payload_size)} as usize;
+    let mut control = control_buffer(control_size);
+    let iov = [IoSlice::new(buf)];
+    // SAFETY: msghdr consists of integers and pointers, all of which are nullable
+    let mut hdr = unsafe {zeroed::<msghdr>()};
+    hdr.msg_iov = iov.as_ptr() as *mut _;
+    hdr.msg_iovlen = 1;
+    if !fds.is_empty() {
+        hdr.msg_control = control.as_mut_ptr() as *mut _;
+        hdr.msg_controllen = control_size as _;
+        // SAFETY: the control buffer is aligned and big enough for one control message with the
+        // file descriptors, which is what CMSG_SPACE has calculated for us
+        unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&hdr);
+            (*cmsg).cmsg_level = SOL_SOCKET;
+            (*cmsg).cmsg_type = SCM_RIGHTS;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(payload_size) as _;
+            let data = libc::CMSG_DATA(cmsg) as *mut c_int;
+            for (i, fd) in fds.iter().enumerate() {
+                ptr::write_unaligned(data.add(i), fd.as_raw_fd());
+            }
+        }
+    }
+    let result = unsafe {libc::sendmsg(socket, &hdr, 0)};
+    if result != -1 {
+        Ok(result as usize)
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Receives bytes into the specified buffer along with up to [`MAX_FDS`] file descriptors, returning how many bytes were received, the flags reported by `recvmsg` and the file descriptors.
+///
+/// If the control data was truncated, all file descriptors which did arrive are closed and an error is returned.
+pub(super) fn recv_with_fds(
+    socket: c_int,
+    buf: &mut [u8],
+) -> io::Result<(usize, c_int, Vec<OwnedFd>)> {
+    let control_size = unsafe {
+        libc::CMSG_SPACE((MAX_FDS * mem::size_of::<c_int>()) as c_uint)
+    } as usize;
+    // With SO_PASSCRED enabled, every message also carries credentials, which must not crowd out
+    // the file descriptors.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    let control_size = control_size + unsafe {
+        libc::CMSG_SPACE(mem::size_of::<libc::ucred>() as c_uint)
+    } as usize;
+    let mut control = control_buffer(control_size);
+    let mut iov = [IoSliceMut::new(buf)];
+    // SAFETY: as above
+    let mut hdr = unsafe {zeroed::<msghdr>()};
+    hdr.msg_iov = iov.as_mut_ptr() as *mut _;
+    hdr.msg_iovlen = 1;
+    hdr.msg_control = control.as_mut_ptr() as *mut _;
+    hdr.msg_controllen = control_size as _;
+    let result = unsafe {libc::recvmsg(socket, &mut hdr, RECV_FLAGS)};
+    if result == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut fds = Vec::new();
+    // SAFETY: the kernel has filled out the control buffer with valid control messages, and every
+    // SCM_RIGHTS one contains file descriptors which now belong to us
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&hdr);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == SOL_SOCKET && (*cmsg).cmsg_type == SCM_RIGHTS {
+                let data = libc::CMSG_DATA(cmsg) as *const c_int;
+                let payload_size = (*cmsg).cmsg_len as usize - (data as usize - cmsg as usize);
+                for i in 0..payload_size / mem::size_of::<c_int>() {
+                    fds.push(OwnedFd::from_raw_fd(ptr::read_unaligned(data.add(i))));
+                }
+            }
+            cmsg = libc::CMSG_NXTHDR(&hdr, cmsg);
+        }
+    }
+    if RECV_FLAGS == 0 {
+        for fd in &fds {
+            set_cloexec(fd.as_raw_fd())?;
+        }
+    }
+    if hdr.msg_flags & MSG_CTRUNC != 0 {
+        // Dropping the Vec closes all of them.
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "ancillary data was truncated, received file descriptors have been closed",
+        ));
+    }
+    Ok((result as usize, hdr.msg_flags, fds))
+}
+
+fn set_cloexec(fd: c_int) -> io::Result<()> {
+    let success = unsafe {libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC)} != -1;
+    if success {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Picks the only file descriptor out of the ones which were received, closing the rest.
+pub(super) fn single_fd(fds: Vec<OwnedFd>) -> io::Result<OwnedFd> {
+    fds.into_iter().next().ok_or_else(|| io::Error::new(
+        io::ErrorKind::InvalidData,
+        "expected a file descriptor, but the message contained none",
+    ))
+}
@@ -0,0 +1,4526 @@
+//! Support for Unix domain sockets, abbreviated here as "Ud-sockets".
+//!
+//! Ud-sockets are a special kind of sockets which work in the scope of only one system and use file paths instead of IPv4/IPv6 addresses and 16-bit socket numbers. Aside from their high reliability and convenience for the purposes of IPC (such as filesystem-level privelege management and the similarity to named pipes), they have a unique feature which cannot be replaced by any other form of IPC: **ancillary data**.
+//!
+//! # Ancillary data
+//! Thanks to this feature, Ud-sockets can transfer ownership of a file descriptor to another process, even if it doesn't have a parent-child relationship with the file descriptor owner and thus does not inherit anything via `fork()`. Aside from that, ancillary data can contain credentials of a process, which are validated by the kernel unless the sender is the superuser, meaning that this way of retrieving credentials can be used for authentification.
+//!
+//! For passing file descriptors, the `send_fds`/`recv_fds` family of methods on [`UdStream`] and [`UdSocket`] is the easiest to get right: it deals in `OwnedFd` and `BorrowedFd` rather than raw integers, so received file descriptors are closed automatically unless they're used, and can even be received directly as the objects which own them, such as `File`s or unnamed pipes.
+//!
+//! # Usage
+//! The [`UdStreamListener`] and [`UdSocket`] types are two starting points, depending on whether you intend to use UDP-like datagrams or TCP-like byte streams. If you need connections like with byte streams but with message boundaries preserved, use [`UdSeqpacketListener`] instead, which is available everywhere except for macOS and iOS.
+//!
+//! [`UdStreamListener`]: struct.UdStreamListener.html " "
+//! [`UdSeqpacketListener`]: struct.UdSeqpacketListener.html " "
+//! [`UdStream`]: struct.UdStream.html " "
+//! [`UdSocket`]: struct.UdSocket.html " "
+
+#[cfg(all(unix, feature = "tokio"))]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "tokio")))]
+pub mod tokio;
+#[cfg(unix)]
+mod batch;
+mod fd_passing;
+mod options;
+mod seqpacket;
+pub use seqpacket::{UdSeqpacketListener, UdSeqpacket};
+pub use batch::{RecvBatch, SendBatch, ReceivedDatagram, ReceivedDatagrams};
+
+#[cfg(unix)]
+use libc::{
+    c_int,
+    pid_t, uid_t, gid_t, mode_t,
+    AF_UNIX,
+    SOCK_STREAM, SOCK_DGRAM, SOCK_SEQPACKET,
+    SOL_SOCKET, SO_PASSCRED,
+    SCM_RIGHTS, SCM_CREDENTIALS,
+    MSG_TRUNC, MSG_CTRUNC, MSG_PEEK,
+    sockaddr_un,
+    msghdr, cmsghdr,
+    ucred,
+};
+#[cfg(not(unix))]
+#[doc(hidden)]
+#[allow(non_camel_case_types)]
+pub type c_int = i32;
+#[cfg(not(unix))]
+#[doc(hidden)]
+#[allow(non_camel_case_types)]
+pub type pid_t = i32;
+#[cfg(not(unix))]
+#[doc(hidden)]
+#[allow(non_camel_case_types)]
+pub type uid_t = i32;
+#[cfg(not(unix))]
+#[doc(hidden)]
+#[allow(non_camel_case_types)]
+pub type gid_t = i32;
+#[cfg(not(unix))]
+#[doc(hidden)]
+#[allow(non_camel_case_types)]
+pub type mode_t = u32;
+#[cfg(unix)]
+use cfg_if::cfg_if;
+use std::{
+    io::{self, Read, Write, IoSlice, IoSliceMut},
+    fmt::{self, Formatter, Debug},
+    iter::{self, FromIterator, FusedIterator},
+    path::{Path, PathBuf},
+    mem::{self, zeroed, ManuallyDrop},
+    ffi::{CStr, CString, OsStr, OsString, NulError},
+    net::Shutdown,
+    ptr,
+    slice,
+    borrow::Cow,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+#[cfg(unix)]
+use std::os::unix::{
+    io::{AsRawFd, IntoRawFd, FromRawFd, AsFd, BorrowedFd, OwnedFd},
+    ffi::{OsStrExt, OsStringExt},
+};
+#[cfg(unix)]
+use super::{FdOps, set_nonblocking, get_nonblocking};
+#[cfg(not(unix))]
+type FdOps = ();
+#[cfg(unix)]
+use crate::{ReliableReadMsg, WriteMsg, PartialMsgWriteError, ReuniteError, Sealed};
+
+#[cfg(unix)]
+cfg_if! {
+    if #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "emscripten",
+        target_os = "solaris",
+        target_os = "illumos",
+        target_os = "hermit",
+        target_os = "redox",
+        // For some unknown reason, Newlib only declares sockaddr_un on Xtensa
+        all(target_env = "newlib", target_arch = "xtensa"),
+        target_env = "uclibc",
+    ))] {
+        const _MAX_UDSOCKET_PATH_LEN: usize = 108;
+    } else if #[cfg(any( // why are those a thing
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly",
+        target_os = "macos",
+        target_os = "ios",
+    ))] {
+        const _MAX_UDSOCKET_PATH_LEN: usize = 104;
+    } else {
+        compile_error("Please fill out MAX_UDSOCKET_PATH_LEN in interprocess/src/os/unix/udsocket.rs for your platform if you with to enable Unix domain socket support for it")
+    }
+}
+#[cfg(not(unix))]
+const _MAX_UDSOCKET_PATH_LEN: usize = 0;
+
+/// The maximum path length for Unix domain sockets. Binding or connecting to a path which exceeds this value fails with an error of kind [`InvalidInput`], with the exception described below.
+///
+/// When using the [socket namespace], this value is reduced by 1, since enabling the usage of that namespace takes up one character.
+///
+/// # Longer paths on Linux
+/// On Linux, file paths which exceed this value are supported as long as their file name (the part after the last `/`) is short enough. The parent directory of the socket is opened and the socket is then addressed through the `/proc/self/fd` entry of the directory, which only takes around 20 bytes of the limit. This requires `/proc` to be mounted. The address reported by `getsockname` for sockets bound this way, as well as by `getpeername` and `recv_from` for their peers, is that `/proc/self/fd` path, which is meaningless outside of the process which bound the socket, and even there refers to a file descriptor which is closed right after binding. [`UdStreamListener::local_addr`] accounts for that and reports the original path.
+///
+/// ## Value
+/// The following platforms define the value of this constant as **108**:
+/// - Linux
+///     - includes Android
+/// - uClibc
+/// - Newlib
+///     - *Only supported on Xtensa*
+/// - Emscripten
+/// - Redox
+/// - HermitCore
+/// - Solaris
+/// - Illumos
+///
+/// The following platforms define the value of this constant as **104**:
+/// - FreeBSD
+/// - OpenBSD
+/// - NetBSD
+/// - DragonflyBSD
+/// - macOS
+/// - iOS
+///
+/// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+/// [`UdStreamListener::local_addr`]: struct.UdStreamListener.html#method.local_addr " "
+/// [socket namespace]: enum.UdSocketPath.html#namespaced " "
+// The reason why this constant wraps the underscored one instead of being defined directly is
+// because that'd require documenting both branches separately. This way, the user-faced
+// constant has only one definition and one documentation comment block.
+pub const MAX_UDSOCKET_PATH_LEN: usize = _MAX_UDSOCKET_PATH_LEN;
+
+/// A Unix domain byte stream socket server, listening for connections.
+///
+/// All such sockets have the `SOCK_STREAM` socket type; in other words, this is the Unix domain version of a TCP server.
+///
+/// # Examples
+/// Basic server:
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # #[cfg(unix)] {
+/// use interprocess::os::unix::udsocket::{UdStream, UdStreamListener};
+/// use std::io::{self, prelude::*};
+///
+/// fn handle_error(result: io::Result<UdStream>) -> Option<UdStream> {
+///     match result {
+///         Ok(val) => Some(val),
+///         Err(error) => {
+///             eprintln!("There was an error with an incoming connection: {}", error);
+///             None
+///         }
+///     }
+/// }
+///
+/// let listener = UdStreamListener::bind("/tmp/example.sock")?;
+/// for mut connection in listener.incoming()
+///     // Use filter_map to report all errors with connections and skip those connections in the loop,
+///     // making the actual server loop part much cleaner than if it contained error handling as well.
+///     .filter_map(handle_error) {
+///     connection.write_all(b"Hello from server!");
+///     let mut input_string = String::new();
+///     connection.read_to_string(&mut input_string);
+///     println!("Client answered: {}", input_string);
+/// }
+/// # }
+/// # Ok(()) }
+/// ```
+///
+/// Sending and receiving ancillary data:
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # #[cfg(unix)] {
+/// use interprocess::{
+///     unnamed_pipe::{pipe, UnnamedPipeReader},
+///     os::unix::udsocket::{UdStreamListener, UdStream, AncillaryData, AncillaryDataBuf},
+/// };
+/// use std::{
+///     io::{self, prelude::*},
+///     fs,
+///     iter,
+///     borrow::Cow,
+///     os::unix::io::{FromRawFd, IntoRawFd},
+/// };
+///
+/// fn handle_error(result: io::Result<UdStream>) -> Option<UdStream> {
+///     match result {
+///         Ok(val) => Some(val),
+///         Err(error) => {
+///             eprintln!("There was an error with an incoming connection: {}", error);
+///             None
+///         }
+///     }
+/// }
+///
+/// let listener = UdStreamListener::bind("/tmp/example.sock")?;
+///
+/// // Allocate a sufficient buffer for receiving ancillary data.
+/// let mut ancillary_buffer = AncillaryDataBuf::owned_with_capacity(
+///     AncillaryData::ENCODED_SIZE_OF_CREDENTIALS
+///   + AncillaryData::encoded_size_of_file_descriptors(1),
+/// );
+/// // Prepare valid credentials.
+/// let credentials = AncillaryData::credentials();
+///
+/// for mut connection in listener.incoming()
+///     .filter_map(handle_error) {
+///     // Create the file descriptor which we will be sending.
+///     let (own_fd, fd_to_send) = pipe()?;
+///     // Borrow the file descriptor in a slice right away to send it later.
+///     let fds = [fd_to_send.into_raw_fd()];
+///     let fd_ancillary = AncillaryData::FileDescriptors(
+///         Cow::Borrowed(&fds),
+///     );
+///     
+///     connection.send_ancillary(
+///         b"File descriptor and credentials from the server!",
+///         iter::once(fd_ancillary),
+///     );
+///     
+///     // The receive buffer size depends on the situation, but since this example
+///     // mirrors the second one from UdSocket, 64 is sufficient.
+///     let mut recv_buffer = [0; 64];
+///     connection.recv_ancillary(
+///         &mut recv_buffer,
+///         &mut ancillary_buffer,
+///     );
+///     
+///     println!("Client answered: {}", String::from_utf8_lossy(&recv_buffer));
+/// 
+///     // Decode the received ancillary data.
+///     let (mut file_descriptors, mut cred) = (None, None);
+///     for element in ancillary_buffer.decode() {
+///         match element {
+///             AncillaryData::FileDescriptors(fds) => file_descriptors = Some(fds),
+///             AncillaryData::Credentials {pid, uid, gid} => cred = Some((pid, uid, gid)),
+///         }
+///     }
+///     let mut files = Vec::new();
+///     if let Some(fds) = file_descriptors {
+///         // There is a possibility that zero file descriptors were sent — let's account for that.
+///         for fd in fds.iter().copied() {
+///             // This is normally unsafe, but since we know that the descriptor is not owned somewhere
+///             // else in the current process, it's fine to do this:
+///             let file = unsafe {fs::File::from_raw_fd(fd)};
+///             files.push(file);
+///         }
+///     }
+///     for mut file in files {
+///         file.write(b"Hello foreign file descriptor!");
+///     }
+///     if let Some(credentials) = cred {
+///         println!("Client\tPID: {}", credentials.0);
+///         println!(      "\tUID: {}", credentials.1);
+///         println!(      "\tGID: {}", credentials.2);
+///     }
+/// }
+/// # }
+/// # Ok(()) }
+/// ```
+pub struct UdStreamListener {
+    fd: FdOps,
+    // Only present if the listener was created with bind_reclaiming, in which case the socket file
+    // is unlinked when the listener is dropped.
+    unlink_guard: Option<UnlinkGuard>,
+    // Only present if the listener was bound to a file path, which is what we report instead of
+    // asking the system, since the system only knows about the temporary path if the listener was
+    // bound in a staging directory and about the /proc/self/fd one if the path had to be shortened.
+    bound_path: Option<CString>,
+}
+impl UdStreamListener {
+    /// Creates a new listener socket at the specified address.
+    ///
+    /// If the socket path exceeds the [maximum socket path length] (which includes the first 0 byte when using the [socket namespace]), an error is returned, unless it's a file path on Linux, where longer paths are handled as described in the documentation of the constant. Errors can also be produced for different reasons, i.e. errors should always be handled regardless of whether the path is known to be short enough or not.
+    ///
+    /// The listener is created with the default options, i.e. a backlog of 128 connections and `SO_PASSCRED` enabled. Use [`UdStreamListenerOptions`] to change those, as well as to set the permissions and owner of the socket file.
+    ///
+    /// # Example
+    /// See [`ToUdSocketPath`].
+    ///
+    /// # System calls
+    /// - `socket`
+    /// - `bind`
+    /// - `open`, if the path has to be shortened
+    /// - `listen`
+    /// - `setsockopt`
+    ///
+    /// [maximum socket path length]: const.MAX_UDSOCKET_PATH_LEN.html " "
+    /// [socket namespace]: enum.UdSocketPath.html#namespaced " "
+    /// [`ToUdSocketPath`]: trait.ToUdSocketPath.html " "
+    /// [`UdStreamListenerOptions`]: struct.UdStreamListenerOptions.html " "
+    #[inline]
+    pub fn bind<'a>(path: impl ToUdSocketPath<'a>) -> io::Result<Self> {
+        UdStreamListenerOptions::new().create(path)
+    }
+
+    /// Listens for incoming connections to the socket, blocking until a client is connected.
+    ///
+    /// See [`incoming`] for a convenient way to create a main loop for a server.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::udsocket::UdStreamListener;
+    ///
+    /// let listener = UdStreamListener::bind("/tmp/example.sock")?;
+    /// loop {
+    ///     match listener.accept() {
+    ///         Ok(connection) => {
+    ///             println!("New client!");
+    ///         },
+    ///         Err(error) => {
+    ///             println!("Incoming connection failed: {}", error);
+    ///         },
+    ///     }
+    /// }
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # System calls
+    /// - `accept`
+    ///
+    /// [`incoming`]: #method.incoming " "
+    #[inline]
+    pub fn accept(&self) -> io::Result<UdStream> {
+        self.accept_nonblocking()
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+    /// Same as [`accept`], but returns `WouldBlock` as is instead of checking whether it was caused by a timeout, since the async wrappers keep the listener in non-blocking mode anyway.
+    ///
+    /// [`accept`]: #method.accept " "
+    #[inline]
+    pub(crate) fn accept_nonblocking(&self) -> io::Result<UdStream> {
+        let (success, fd) = unsafe {
+            let result = libc::accept(
+                self.as_raw_fd(),
+                zeroed(),
+                zeroed(),
+            );
+            (result != -1, result)
+        };
+        if success {
+            Ok(
+                unsafe {
+                    // SAFETY: we just created the file descriptor, meaning that it's guaranteeed
+                    // not to be used elsewhere
+                    UdStream::from_raw_fd(fd)
+                }
+            )
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    /// Creates an infinite iterator which calls `accept()` with each iteration. Used together with `for` loops to conveniently create a main loop for a socket server.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::udsocket::UdStreamListener;
+    ///
+    /// let listener = UdStreamListener::bind("/tmp/example.sock")?;
+    /// // Thanks to incoming(), you get a simple self-documenting infinite server loop
+    /// for connection in listener.incoming()
+    ///     .map(|conn| if let Err(error) = conn {
+    ///         eprintln!("Incoming connection failed: {}", error);
+    ///     }) {
+    ///     eprintln!("New client!");
+    /// }
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    #[inline(always)]
+    pub fn incoming(&self) -> Incoming<'_> {
+        Incoming::from(self)
+    }
+    /// Listens for incoming connections to the socket, blocking until a client is connected or the specified amount of time passes.
+    ///
+    /// If no client connects within the timeout, an error of kind [`TimedOut`] is returned. The listener's mode is left untouched, since it's shared by all duplicates of the socket descriptor.
+    ///
+    /// The OS has no way of accepting a single connection without blocking, so the behavior when another thread or process accepts the connection which woke up the wait before this call gets to it depends on the mode. In [non-blocking mode], the wait simply continues until the deadline. In blocking mode, the call blocks until the next connection arrives, regardless of the timeout — use non-blocking mode if `accept_timeout` or [`accept`] can run concurrently on the same listener.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::udsocket::UdStreamListener;
+    /// use std::{io, time::Duration};
+    ///
+    /// let listener = UdStreamListener::bind("/tmp/example.sock")?;
+    /// match listener.accept_timeout(Duration::from_secs(5)) {
+    ///     Ok(connection) => println!("New client!"),
+    ///     Err(error) if error.kind() == io::ErrorKind::TimedOut => println!("Nobody came"),
+    ///     Err(error) => return Err(error.into()),
+    /// }
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # System calls
+    /// - `poll`
+    /// - `accept`
+    ///
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    /// [non-blocking mode]: #method.set_nonblocking " "
+    /// [`accept`]: #method.accept " "
+    pub fn accept_timeout(&self, timeout: Duration) -> io::Result<UdStream> {
+        let fd = accept_with_timeout(self.as_raw_fd(), timeout)?;
+        // SAFETY: we just created the file descriptor, meaning that it's guaranteeed not to be
+        // used elsewhere
+        Ok(unsafe {UdStream::from_raw_fd(fd)})
+    }
+    /// Enables or disables the non-blocking mode for the listener. By default, it is disabled.
+    ///
+    /// In non-blocking mode, calling [`accept`] when there are no pending connections immediately returns an error of kind [`WouldBlock`] instead of blocking until a client connects. The non-blocking mode is not inherited by the streams produced by the listener.
+    ///
+    /// # System calls
+    /// - `fcntl`
+    ///
+    /// [`accept`]: #method.accept " "
+    /// [`WouldBlock`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.WouldBlock " "
+    #[inline]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        set_nonblocking(self.as_raw_fd(), nonblocking)
+    }
+    /// Returns the address the listener is bound to.
+    ///
+    /// For listeners bound to file paths, this is the path the listener was created with, even when the system knows the socket by another path: the temporary path the socket was bound to before being moved to its final path if the listener was created with any of the file ownership options of [`UdStreamListenerOptions`], or the `/proc/self/fd` path used to shorten a path which exceeds the [maximum socket path length].
+    ///
+    /// # System calls
+    /// - `getsockname`, unless the listener was bound to a file path
+    ///
+    /// [`UdStreamListenerOptions`]: struct.UdStreamListenerOptions.html " "
+    /// [maximum socket path length]: const.MAX_UDSOCKET_PATH_LEN.html " "
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<UdSocketPath<'static>> {
+        match &self.bound_path {
+            Some(path) => Ok(UdSocketPath::File(Cow::Owned(path.clone()))),
+            None => socket_addr(self.as_raw_fd(), false),
+        }
+    }
+
+    /// Creates a new listener socket at the specified address, replacing the socket file left behind by a previous server if that server is no longer running, and removing the socket file when the listener is dropped.
+    ///
+    /// If [`bind`] fails because the socket file already exists, this probes the file by trying to connect to it. If the connection is refused, nobody is listening on the other side, so the file is removed and binding is attempted once more. If the connection succeeds, another server is using the path and the original error (of kind [`AddrInUse`]) is returned. Files which aren't sockets are never removed.
+    ///
+    /// When the listener is dropped, the socket file is unlinked, but only if the path still refers to the very socket file the listener created — if another server has replaced it in the meantime, it is left alone. Namespaced sockets don't have files associated with them, so this method behaves exactly like [`bind`] for them.
+    ///
+    /// Note that there is an unavoidable race between two servers starting at the same time and both finding the same stale socket file, in which case one of them may remove the freshly created socket file of the other one. Use a lock file if that's a concern.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::udsocket::UdStreamListener;
+    ///
+    /// // Works even if the previous instance of the server crashed without cleaning up.
+    /// let listener = UdStreamListener::bind_reclaiming("/tmp/example.sock")?;
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # System calls
+    /// - `socket`
+    /// - `bind`
+    /// - `listen`
+    /// - `connect`, if the path is occupied
+    /// - `lstat`
+    /// - `unlink`, if the path is occupied by a dead socket and on drop
+    ///
+    /// [`bind`]: #method.bind " "
+    /// [`AddrInUse`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.AddrInUse " "
+    #[inline]
+    pub fn bind_reclaiming<'a>(path: impl ToUdSocketPath<'a>) -> io::Result<Self> {
+        UdStreamListenerOptions::new().reclaim(true).create(path)
+    }
+}
+impl Debug for UdStreamListener {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UdStreamListener")
+            .field("file_descriptor", &self.as_raw_fd())
+            .field("unlink_on_drop", &self.unlink_guard.is_some())
+            .finish()
+    }
+}
+impl Drop for UdStreamListener {
+    fn drop(&mut self) {
+        if let Some(guard) = &self.unlink_guard {
+            // Make sure not to remove the file of another server which has reclaimed the path
+            // after us.
+            if file_id(&guard.path).ok() == Some((guard.dev, guard.ino)) {
+                let _ = unlink(&guard.path);
+            }
+        }
+    }
+}
+
+/// The path to the socket file of a listener created by `bind_reclaiming`, along with the identity of the file to check whether it's still ours.
+struct UnlinkGuard {
+    path: CString,
+    dev: libc::dev_t,
+    ino: libc::ino_t,
+}
+/// Checks whether the socket file at the specified path is left over from a dead server.
+fn is_stale_socket(path: &UdSocketPath<'_>, file_path: &CStr, socket_type: c_int) -> io::Result<bool> {
+    let is_socket = unsafe {
+        let mut stat: libc::stat = zeroed();
+        libc::lstat(file_path.as_ptr(), &mut stat as *mut _) != -1
+            && stat.st_mode & libc::S_IFMT == libc::S_IFSOCK
+    };
+    if !is_socket {
+        return Ok(false);
+    }
+    let probe = if socket_type == SOCK_SEQPACKET {
+        UdSeqpacket::connect(path.clone()).map(drop)
+    } else {
+        UdStream::connect(path.clone()).map(drop)
+    };
+    match probe {
+        Ok(()) => Ok(false),
+        Err(error) if error.raw_os_error() == Some(libc::ECONNREFUSED) => Ok(true),
+        // Someone else removed the file while we were looking at it, so the path is free now.
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(true),
+        Err(error) => Err(error),
+    }
+}
+fn file_id(path: &CStr) -> io::Result<(libc::dev_t, libc::ino_t)> {
+    let (success, stat) = unsafe {
+        let mut stat: libc::stat = zeroed();
+        let result = libc::lstat(path.as_ptr(), &mut stat as *mut _);
+        (result != -1, stat)
+    };
+    if success {
+        Ok((stat.st_dev, stat.st_ino))
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+fn unlink(path: &CStr) -> io::Result<()> {
+    let success = unsafe {
+        libc::unlink(path.as_ptr()) != -1
+    };
+    if success {
+        Ok(())
+    } else {
+        let error = io::Error::last_os_error();
+        if error.kind() == io::ErrorKind::NotFound {
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+}
+impl_owned_fd!(UdStreamListener);
+#[cfg(unix)]
+impl AsRawFd for UdStreamListener {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> c_int {
+        self.fd.as_raw_fd()
+    }
+}
+#[cfg(unix)]
+impl IntoRawFd for UdStreamListener {
+    #[inline]
+    fn into_raw_fd(self) -> c_int {
+        let mut listener = ManuallyDrop::new(self);
+        // The socket file stays around, since the caller is taking over the socket, but the
+        // memory for the path still has to be freed. The descriptor must not be closed.
+        unsafe {
+            ptr::drop_in_place(&mut listener.unlink_guard);
+            ptr::drop_in_place(&mut listener.bound_path);
+        }
+        listener.fd.0
+    }
+}
+#[cfg(unix)]
+impl FromRawFd for UdStreamListener {
+    #[inline(always)]
+    unsafe fn from_raw_fd(fd: c_int) -> Self {
+        Self {fd: FdOps(fd), unlink_guard: None, bound_path: None}
+    }
+}
+
+/// The builder which can be used to create a [`UdStreamListener`] with non-default settings.
+///
+/// # Example
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # #[cfg(unix)] {
+/// use interprocess::os::unix::udsocket::UdStreamListenerOptions;
+///
+/// // Only the owner of the server process will be able to connect.
+/// let listener = UdStreamListenerOptions::new()
+///     .mode(0o600)
+///     .backlog(16)
+///     .close_on_exec(true)
+///     .create("/tmp/example.sock")?;
+/// # }
+/// # Ok(()) }
+/// ```
+///
+/// [`UdStreamListener`]: struct.UdStreamListener.html " "
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct UdStreamListenerOptions {
+    /// The maximum number of pending connections which haven't been accepted yet. When the queue is full, new clients either block or get refused, depending on the platform. The system may silently clip the value — on Linux, to the value of `/proc/sys/net/core/somaxconn`.
+    pub backlog: c_int,
+    /// The permissions of the socket file, as in `chmod`, or `None` to let them be derived from the process umask. Connecting to a socket requires write permission on its file.
+    ///
+    /// The permissions are applied atomically: the socket is bound in a private temporary directory next to the final path, gets its permissions and ownership set there, and is then hard-linked to the final path, so that there is no window during which the socket is reachable at the final path with the wrong permissions. As a consequence, the address reported by `getsockname` for such a listener and the streams it accepts is the temporary path, which no longer exists, and so is the address reported by `getpeername` for the clients connected to it — [`UdStreamListener::local_addr`] accounts for that, but [`UdStream::local_addr`] and [`UdStream::peer_addr`] can't. Ignored for namespaced sockets, which don't have files.
+    ///
+    /// [`UdStreamListener::local_addr`]: struct.UdStreamListener.html#method.local_addr " "
+    /// [`UdStream::local_addr`]: struct.UdStream.html#method.local_addr " "
+    /// [`UdStream::peer_addr`]: struct.UdStream.html#method.peer_addr " "
+    pub mode: Option<mode_t>,
+    /// The user which will own the socket file, or `None` to keep the effective user of the process. Changing the owner usually requires superuser privileges. Applied atomically, just like [`mode`], and ignored for namespaced sockets.
+    ///
+    /// [`mode`]: #structfield.mode " "
+    pub owner: Option<uid_t>,
+    /// The group which will own the socket file, or `None` to let it be chosen by the system. A process can only change the group to one it's a member of unless it has superuser privileges. Applied atomically, just like [`mode`], and ignored for namespaced sockets.
+    ///
+    /// [`mode`]: #structfield.mode " "
+    pub group: Option<gid_t>,
+    /// Enables the `SO_PASSCRED` option on the listening socket, which makes the streams produced by it receive the credentials of the client with every message, allowing them to be retrieved via ancillary data without the client explicitly sending them.
+    pub pass_credentials: bool,
+    /// Makes the listening socket file descriptor close on `exec`, preventing it from being leaked into child processes. Where supported, this is done at the moment the socket is created, so that other threads forking concurrently can't observe a descriptor without the flag.
+    pub close_on_exec: bool,
+    /// Replaces the socket file left behind by a previous server if nobody is listening on it anymore, and removes the socket file when the listener is dropped. See [`bind_reclaiming`] for the details.
+    ///
+    /// [`bind_reclaiming`]: struct.UdStreamListener.html#method.bind_reclaiming " "
+    pub reclaim: bool,
+}
+impl UdStreamListenerOptions {
+    /// Creates a new builder with default options, which are the ones used by [`UdStreamListener::bind`].
+    ///
+    /// [`UdStreamListener::bind`]: struct.UdStreamListener.html#method.bind " "
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            backlog: 128,
+            mode: None,
+            owner: None,
+            group: None,
+            pass_credentials: true,
+            close_on_exec: false,
+            reclaim: false,
+        }
+    }
+    /// Sets the [`backlog`] parameter to the specified value.
+    ///
+    /// [`backlog`]: #structfield.backlog " "
+    #[inline]
+    #[must_use = "builder setters take the entire structure and return the result"]
+    pub fn backlog(mut self, backlog: c_int) -> Self {
+        self.backlog = backlog;
+        self
+    }
+    /// Sets the [`mode`] parameter to the specified value.
+    ///
+    /// [`mode`]: #structfield.mode " "
+    #[inline]
+    #[must_use = "builder setters take the entire structure and return the result"]
+    pub fn mode(mut self, mode: impl Into<Option<mode_t>>) -> Self {
+        self.mode = mode.into();
+        self
+    }
+    /// Sets the [`owner`] parameter to the specified value.
+    ///
+    /// [`owner`]: #structfield.owner " "
+    #[inline]
+    #[must_use = "builder setters take the entire structure and return the result"]
+    pub fn owner(mut self, owner: impl Into<Option<uid_t>>) -> Self {
+        self.owner = owner.into();
+        self
+    }
+    /// Sets the [`group`] parameter to the specified value.
+    ///
+    /// [`group`]: #structfield.group " "
+    #[inline]
+    #[must_use = "builder setters take the entire structure and return the result"]
+    pub fn group(mut self, group: impl Into<Option<gid_t>>) -> Self {
+        self.group = group.into();
+        self
+    }
+    /// Sets the [`pass_credentials`] parameter to the specified value.
+    ///
+    /// [`pass_credentials`]: #structfield.pass_credentials " "
+    #[inline]
+    #[must_use = "builder setters take the entire structure and return the result"]
+    pub fn pass_credentials(mut self, pass_credentials: bool) -> Self {
+        self.pass_credentials = pass_credentials;
+        self
+    }
+    /// Sets the [`close_on_exec`] parameter to the specified value.
+    ///
+    /// [`close_on_exec`]: #structfield.close_on_exec " "
+    #[inline]
+    #[must_use = "builder setters take the entire structure and return the result"]
+    pub fn close_on_exec(mut self, close_on_exec: bool) -> Self {
+        self.close_on_exec = close_on_exec;
+        self
+    }
+    /// Sets the [`reclaim`] parameter to the specified value.
+    ///
+    /// [`reclaim`]: #structfield.reclaim " "
+    #[inline]
+    #[must_use = "builder setters take the entire structure and return the result"]
+    pub fn reclaim(mut self, reclaim: bool) -> Self {
+        self.reclaim = reclaim;
+        self
+    }
+    /// Creates a listener socket at the specified address using the options from the builder.
+    ///
+    /// # System calls
+    /// - `socket`
+    /// - `fcntl`, if `close_on_exec` is enabled on platforms which can't create the socket with the flag already set
+    /// - `bind`
+    /// - `mkdtemp`, `chmod`, `chown`, `link`, `unlink` and `rmdir`, if the mode or the owner of the socket file is set
+    /// - `listen`
+    /// - `setsockopt`, if `pass_credentials` is enabled
+    /// - `connect`, `lstat` and `unlink`, if `reclaim` is enabled
+    #[inline]
+    pub fn create<'a>(&self, path: impl ToUdSocketPath<'a>) -> io::Result<UdStreamListener> {
+        self.create_listener(path.to_socket_path()?, SOCK_STREAM)
+    }
+    /// Creates a `SOCK_SEQPACKET` listener socket at the specified address using the options from the builder. All options apply to it exactly as they do to byte stream listeners.
+    ///
+    /// # Platform-specific behavior
+    /// macOS and iOS don't support `SOCK_SEQPACKET` Ud-sockets, so an error is always returned there.
+    ///
+    /// # System calls
+    /// Same as for [`create`].
+    ///
+    /// [`create`]: #method.create " "
+    #[inline]
+    pub fn create_seqpacket<'a>(&self, path: impl ToUdSocketPath<'a>) -> io::Result<UdSeqpacketListener> {
+        self.create_listener(path.to_socket_path()?, SOCK_SEQPACKET)
+            .map(|inner| UdSeqpacketListener {inner})
+    }
+    fn create_listener(&self, path: UdSocketPath<'_>, socket_type: c_int) -> io::Result<UdStreamListener> {
+        let file_path = match &path {
+            UdSocketPath::File(file_path) => Some(file_path.clone().into_owned()),
+            _ => None,
+        };
+        // A socket which was bound in a staging directory can't be bound again, so every attempt
+        // needs a fresh one.
+        let bind = || -> io::Result<UdStreamListener> {
+            let mut listener = self.create_socket(socket_type)?;
+            match &file_path {
+                Some(file_path) if self.mode.is_some() || self.owner.is_some() || self.group.is_some() => {
+                    self.bind_staged(listener.as_raw_fd(), file_path)?
+                },
+                _ => bind_to_path(listener.as_raw_fd(), &path)?,
+            }
+            listener.bound_path = file_path.clone();
+            Ok(listener)
+        };
+        let mut listener = match bind() {
+            Ok(listener) => listener,
+            Err(error) if error.kind() == io::ErrorKind::AddrInUse && self.reclaim => {
+                // Namespaced sockets never have stale files, so there's nothing to reclaim.
+                let file_path = match &file_path {
+                    Some(file_path) => file_path,
+                    None => return Err(error),
+                };
+                if !is_stale_socket(&path, file_path, socket_type)? {
+                    return Err(error);
+                }
+                unlink(file_path)?;
+                bind()?
+            },
+            Err(error) => return Err(error),
+        };
+        let fd = listener.as_raw_fd();
+        let success = unsafe {
+            libc::listen(fd, self.backlog) != -1
+        };
+        if !success {
+            return Err(io::Error::last_os_error());
+        }
+        if self.pass_credentials {
+            let passcred: c_int = 1;
+            let success = unsafe {
+                libc::setsockopt(
+                    fd,
+                    SOL_SOCKET,
+                    SO_PASSCRED,
+                    &passcred as *const _ as *const _,
+                    mem::size_of_val(&passcred) as u32,
+                ) != -1
+            };
+            if !success {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        if let (true, Some(file_path)) = (self.reclaim, file_path) {
+            let (dev, ino) = file_id(&file_path)?;
+            listener.unlink_guard = Some(UnlinkGuard {path: file_path, dev, ino});
+        }
+        Ok(listener)
+    }
+    fn create_socket(&self, socket_type: c_int) -> io::Result<UdStreamListener> {
+        #[cfg(target_os = "linux")]
+        let socket_type = if self.close_on_exec {
+            socket_type | libc::SOCK_CLOEXEC
+        } else {
+            socket_type
+        };
+        let (success, fd) = unsafe {
+            let result = libc::socket(AF_UNIX, socket_type, 0);
+            (result != -1, result)
+        };
+        if !success {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: we just created the file descriptor, meaning that it's guaranteeed not to be used
+        // elsewhere; wrapping it right away makes sure that it's closed if anything below fails
+        let listener = unsafe {UdStreamListener::from_raw_fd(fd)};
+        #[cfg(not(target_os = "linux"))]
+        {
+            if self.close_on_exec {
+                let success = unsafe {
+                    libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) != -1
+                };
+                if !success {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+        }
+        Ok(listener)
+    }
+    /// Binds the socket in a private temporary directory, applies the mode and owner and only then links it to the final path.
+    fn bind_staged(&self, fd: c_int, file_path: &CStr) -> io::Result<()> {
+        let file_path = file_path.to_bytes();
+        let parent: &[u8] = match file_path.iter().rposition(|x| *x == b'/') {
+            Some(0) => b"",
+            Some(index) => &file_path[..index],
+            None => b".",
+        };
+        let mut template = Vec::with_capacity(parent.len() + 13);
+        template.extend_from_slice(parent);
+        template.extend_from_slice(b"/.ipc-XXXXXX\0");
+        let success = unsafe {
+            !libc::mkdtemp(template.as_mut_ptr() as *mut _).is_null()
+        };
+        if !success {
+            return Err(io::Error::last_os_error());
+        }
+        template.pop(); // Remove the nul terminator
+        let temp_dir = CString::new(template)
+            .expect("mkdtemp produced a path with interior nul bytes");
+        let temp_path = {
+            let mut temp_path = temp_dir.clone().into_bytes();
+            temp_path.extend_from_slice(b"/s");
+            CString::new(temp_path).expect("unreachable interior nul byte")
+        };
+        let final_path = CString::new(file_path).expect("unreachable interior nul byte");
+        let result = (|| {
+            bind_to_path(fd, &UdSocketPath::File(Cow::Borrowed(&temp_path)))?;
+            if let Some(mode) = self.mode {
+                let success = unsafe {
+                    libc::chmod(temp_path.as_ptr(), mode) != -1
+                };
+                if !success {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            if self.owner.is_some() || self.group.is_some() {
+                // The all-ones value tells chown to leave the corresponding ID unchanged.
+                let success = unsafe {
+                    libc::chown(
+                        temp_path.as_ptr(),
+                        self.owner.unwrap_or(!0),
+                        self.group.unwrap_or(!0),
+                    ) != -1
+                };
+                if !success {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            let success = unsafe {
+                libc::link(temp_path.as_ptr(), final_path.as_ptr()) != -1
+            };
+            if success {
+                Ok(())
+            } else {
+                let error = io::Error::last_os_error();
+                if error.raw_os_error() == Some(libc::EEXIST) {
+                    // Report the same error as a plain bind would.
+                    Err(io::Error::from_raw_os_error(libc::EADDRINUSE))
+                } else {
+                    Err(error)
+                }
+            }
+        })();
+        // The socket stays reachable through the final path, so the temporary one can go away
+        // regardless of whether linking succeeded.
+        let _ = unlink(&temp_path);
+        unsafe {libc::rmdir(temp_dir.as_ptr())};
+        result
+    }
+}
+impl Default for UdStreamListenerOptions {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+/// Fills out a `sockaddr_un` for the specified path, returning it along with its length.
+///
+/// On Linux, file paths which don't fit into `sun_path` are shortened with [`shorten_path`], in which case the file descriptor of the parent directory is returned as well and has to be kept open until the address is no longer needed.
+#[cfg(unix)]
+fn make_sockaddr_un(path: &UdSocketPath<'_>) -> io::Result<(sockaddr_un, usize, Option<OwnedFd>)> {
+    // SAFETY: sockaddr_un consists of integers
+    let mut addr = unsafe {zeroed::<sockaddr_un>()};
+    addr.sun_family = AF_UNIX as _;
+    #[cfg(target_os = "linux")]
+    if let UdSocketPath::File(file_path) = path {
+        if file_path.to_bytes_with_nul().len() > MAX_UDSOCKET_PATH_LEN {
+            let (short_path, parent_dir) = shorten_path(file_path)?;
+            let addrlen = UdSocketPath::File(Cow::Owned(short_path))
+                .write_self_to_sockaddr_un(&mut addr)?;
+            return Ok((addr, addrlen, Some(parent_dir)));
+        }
+    }
+    let addrlen = path.write_self_to_sockaddr_un(&mut addr)?;
+    Ok((addr, addrlen, None))
+}
+/// Opens the parent directory of a socket path which is too long for `sun_path` and returns an equivalent path which goes through `/proc/self/fd` instead, along with the file descriptor of the directory, which must stay open for the path to remain valid.
+///
+/// Only the file name itself is kept, so this works for arbitrarily deep directories as long as the file name is short enough to fit after the `/proc/self/fd/N/` prefix.
+#[cfg(target_os = "linux")]
+fn shorten_path(path: &CStr) -> io::Result<(CString, OwnedFd)> {
+    let no_workaround = |reason: &str| io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!(
+            "socket path is longer than {} bytes and cannot be shortened: {}",
+            MAX_UDSOCKET_PATH_LEN, reason,
+        ),
+    );
+    let path = path.to_bytes();
+    let (parent, file_name): (&[u8], &[u8]) = match path.iter().rposition(|x| *x == b'/') {
+        Some(0) => (b"/", &path[1..]),
+        Some(index) => (&path[..index], &path[index + 1..]),
+        None => return Err(no_workaround("the file name alone is too long")),
+    };
+    let parent = CString::new(parent).expect("unreachable interior nul byte");
+    let (success, fd) = unsafe {
+        let result = libc::open(
+            parent.as_ptr(),
+            libc::O_PATH | libc::O_DIRECTORY | libc::O_CLOEXEC,
+        );
+        (result != -1, result)
+    };
+    if !success {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: we just opened the file descriptor
+    let parent_dir = unsafe {OwnedFd::from_raw_fd(fd)};
+    let fd_path = format!("/proc/self/fd/{}", fd);
+    if !Path::new(&fd_path).is_dir() {
+        return Err(no_workaround("/proc is not available"));
+    }
+    let mut short_path = fd_path.into_bytes();
+    short_path.push(b'/');
+    short_path.extend_from_slice(file_name);
+    if short_path.len() + 1 > MAX_UDSOCKET_PATH_LEN {
+        return Err(no_workaround("the file name alone is too long"));
+    }
+    let short_path = CString::new(short_path).expect("unreachable interior nul byte");
+    Ok((short_path, parent_dir))
+}
+/// Binds the socket file descriptor to the specified path.
+#[cfg(unix)]
+fn bind_to_path(fd: c_int, path: &UdSocketPath<'_>) -> io::Result<()> {
+    let (addr, addrlen, _parent_dir) = make_sockaddr_un(path)?;
+    let success = unsafe {
+        libc::bind(
+            fd,
+            // Double cast because you cannot cast a reference to a pointer of arbitrary type
+            // but you can cast any narrow pointer to any other narrow pointer
+            &addr as *const _ as *const _,
+            addrlen as u32,
+        ) != -1
+    };
+    if success {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+/// Connects the socket file descriptor to the specified path.
+#[cfg(unix)]
+fn connect_to_path(fd: c_int, path: &UdSocketPath<'_>) -> io::Result<()> {
+    let (addr, addrlen, _parent_dir) = make_sockaddr_un(path)?;
+    let success = unsafe {
+        libc::connect(
+            fd,
+            // Same as in bind_to_path()
+            &addr as *const _ as *const _,
+            addrlen as u32,
+        ) != -1
+    };
+    if success {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+/// Creates a pair of connected sockets of the specified type, enabling `SO_PASSCRED` on both of them just like connecting does.
+#[cfg(unix)]
+fn socketpair(socket_type: c_int) -> io::Result<(FdOps, FdOps)> {
+    let mut fds: [c_int; 2] = [0; 2];
+    let success = unsafe {
+        libc::socketpair(AF_UNIX, socket_type, 0, fds.as_mut_ptr()) != -1
+    };
+    if !success {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: we just created both file descriptors, meaning that they're guaranteed not to be
+    // used elsewhere; wrapping them right away makes sure that they're closed if anything below fails
+    let fds = unsafe {(FdOps::from_raw_fd(fds[0]), FdOps::from_raw_fd(fds[1]))};
+    for fd in [&fds.0, &fds.1].iter() {
+        let passcred: c_int = 1;
+        let success = unsafe {
+            libc::setsockopt(
+                fd.as_raw_fd(),
+                SOL_SOCKET,
+                SO_PASSCRED,
+                &passcred as *const _ as *const _,
+                mem::size_of_val(&passcred) as u32,
+            ) != -1
+        };
+        if !success {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(fds)
+}
+
+/// Receives one message along with ancillary data, returning how many bytes of the message were received, whether the message was truncated, how many bytes of ancillary data were received and whether the ancillary data was truncated.
+#[cfg(unix)]
+fn recv_msg_ancillary(
+    fd: c_int,
+    bufs: &mut [IoSliceMut<'_>],
+    abuf: &mut [u8],
+) -> io::Result<(usize, bool, usize, bool)> {
+    // SAFETY: msghdr consists of integers and pointers, all of which are nullable
+    let mut hdr = unsafe {zeroed::<msghdr>()};
+    hdr.msg_iov = bufs.as_ptr() as *mut _;
+    hdr.msg_iovlen = bufs.len();
+    hdr.msg_control = abuf.as_mut_ptr() as *mut _;
+    hdr.msg_controllen = abuf.len();
+    let (success, bytes_read) = unsafe {
+        let result = libc::recvmsg(
+            fd,
+            &mut hdr as *mut _,
+            0,
+        );
+        (result != -1, result as usize)
+    };
+    if success {
+        Ok((
+            bytes_read,
+            hdr.msg_flags & MSG_TRUNC != 0,
+            hdr.msg_controllen,
+            hdr.msg_flags & MSG_CTRUNC != 0,
+        ))
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+/// Same as `recv_msg_ancillary`, but also stores the address of the sender in the specified buffer.
+#[cfg(unix)]
+fn recv_from_msg_ancillary(
+    fd: c_int,
+    bufs: &mut [IoSliceMut<'_>],
+    abuf: &mut [u8],
+    addr_buf: &mut UdSocketPath<'_>,
+) -> io::Result<(usize, bool, usize, bool)> {
+    // SAFETY: msghdr consists of integers and pointers, all of which are nullable
+    let mut hdr = unsafe {zeroed::<msghdr>()};
+    // Same goes for sockaddr_un
+    let mut addr_buf_staging = unsafe {zeroed()};
+    // It's a void* so the doublecast is mandatory
+    hdr.msg_name = &mut addr_buf_staging as *mut _ as *mut _;
+    hdr.msg_namelen = mem::size_of_val(&addr_buf_staging) as u32;
+    hdr.msg_iov = bufs.as_ptr() as *mut _;
+    hdr.msg_iovlen = bufs.len();
+    hdr.msg_control = abuf.as_mut_ptr() as *mut _;
+    hdr.msg_controllen = abuf.len();
+    let (success, bytes_read) = unsafe {
+        let result = libc::recvmsg(
+            fd,
+            &mut hdr as *mut _,
+            0,
+        );
+        (result != -1, result as usize)
+    };
+    let path_length = hdr.msg_namelen as usize;
+    if success {
+        addr_buf.write_sockaddr_un_to_self(&addr_buf_staging, path_length);
+        Ok((
+            bytes_read,
+            hdr.msg_flags & MSG_TRUNC != 0,
+            hdr.msg_controllen,
+            hdr.msg_flags & MSG_CTRUNC != 0,
+        ))
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+/// Sends one message along with encoded ancillary data, returning how many bytes of the message and of the ancillary data were sent.
+///
+/// If a destination is specified, the message is sent there instead of to the peer the socket is connected to, which is only possible with datagram sockets.
+#[cfg(unix)]
+fn send_msg_ancillary(
+    fd: c_int,
+    bufs: &[IoSlice<'_>],
+    abuf: &[u8],
+    dest: Option<&UdSocketPath<'_>>,
+) -> io::Result<(usize, usize)> {
+    // SAFETY: msghdr consists of integers and pointers, all of which are nullable
+    let mut hdr = unsafe {zeroed::<msghdr>()};
+    let mut dest = dest.map(make_sockaddr_un).transpose()?;
+    if let Some((addr, addrlen, _)) = &mut dest {
+        // It's a void* so the doublecast is mandatory
+        hdr.msg_name = addr as *mut _ as *mut _;
+        hdr.msg_namelen = *addrlen as u32;
+    }
+    hdr.msg_iov = bufs.as_ptr() as *mut _;
+    hdr.msg_iovlen = bufs.len();
+    hdr.msg_control = abuf.as_ptr() as *mut _;
+    hdr.msg_controllen = abuf.len();
+    let (success, bytes_written) = unsafe {
+        let result = libc::sendmsg(
+            fd,
+            &hdr as *const _,
+            0,
+        );
+        (result != -1, result as usize)
+    };
+    if success {
+        Ok((
+            bytes_written,
+            hdr.msg_controllen,
+        ))
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+/// Returns the size of the next message available on the socket without discarding it.
+#[cfg(target_os = "linux")]
+fn peek_msg_size(fd: c_int) -> io::Result<usize> {
+    let mut buffer = [0_u8; 0];
+    let (success, size) = unsafe {
+        let size = libc::recv(
+            fd,
+            buffer.as_mut_ptr() as *mut _,
+            buffer.len(),
+            MSG_TRUNC | MSG_PEEK,
+        );
+        (size != -1, size as usize)
+    };
+    if success {
+        Ok(size)
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// A Unix domain socket byte stream, obtained either from [`UdStreamListener`] or by connecting to an existing server.
+///
+/// # Examples
+/// Basic example:
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # #[cfg(unix)] {
+/// use interprocess::os::unix::udsocket::UdStream;
+/// use std::io::prelude::*;
+///
+/// let mut conn = UdStream::connect("/tmp/example1.sock")?;
+/// conn.write(b"Hello from client!");
+/// let mut string_buffer = String::new();
+/// conn.read_to_string(&mut string_buffer);
+/// println!("Server answered: {}", string_buffer);
+/// # }
+/// # Ok(()) }
+/// ```
+///
+/// Receiving and sending ancillary data:
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # #[cfg(unix)] {
+/// use interprocess::os::unix::udsocket::{UdStream, AncillaryData, AncillaryDataBuf};
+/// use std::{
+///     io::{self, prelude::*},
+///     borrow::Cow,
+///     fs,
+///     os::unix::io::{IntoRawFd, FromRawFd},
+/// };
+///
+/// // Create one file descriptor which we will be sending.
+/// let fd = fs::File::open("/tmp/example_file.mfa")?.into_raw_fd();
+/// // Borrow the file descriptor in a slice right away to send it later.
+/// let fds = [fd];
+/// let fd_ancillary = AncillaryData::FileDescriptors(
+///     Cow::Borrowed(&fds),
+/// );
+/// // Prepare valid credentials.
+/// let credentials = AncillaryData::credentials();
+/// // Allocate a sufficient buffer for receiving ancillary data.
+/// let mut ancillary_buffer = AncillaryDataBuf::owned_with_capacity(
+///     AncillaryData::ENCODED_SIZE_OF_CREDENTIALS
+///   + AncillaryData::encoded_size_of_file_descriptors(1),
+/// );
+///
+/// let conn = UdStream::connect("/tmp/example2.sock")?;
+///
+/// conn.send_ancillary(
+///     b"File descriptor and credentials from client!",
+///     [fd_ancillary, credentials].iter().map(|x| x.clone_ref()),
+/// )?;
+/// // The receive buffer size depends on the situation, but since this example
+/// // mirrors the second one from UdSocketListener, 64 is sufficient.
+/// let mut recv_buffer = [0; 64];
+///
+/// conn.recv_ancillary(
+///     &mut recv_buffer,
+///     &mut ancillary_buffer,
+/// )?;
+/// println!("Server answered: {}", String::from_utf8_lossy(&recv_buffer));
+/// // Decode the received ancillary data.
+/// let (mut file_descriptors, mut cred) = (None, None);
+/// for element in ancillary_buffer.decode() {
+///     match element {
+///         AncillaryData::FileDescriptors(fds) => file_descriptors = Some(fds),
+///         AncillaryData::Credentials {pid, uid, gid} => cred = Some((pid, uid, gid)),
+///     }
+/// }
+/// let mut files = Vec::new();
+/// if let Some(fds) = file_descriptors {
+///     // There is a possibility that zero file descriptors were sent — let's account for that.
+///     for fd in fds.iter().copied() {
+///         // This is normally unsafe, but since we know that the descriptor is not owned somewhere
+///         // else in the current process, it's fine to do this:
+///         let file = unsafe {fs::File::from_raw_fd(fd)};
+///         files.push(file);
+///     }
+/// }
+/// for mut file in files {
+///     file.write(b"Hello foreign file descriptor!");
+/// }
+/// if let Some(credentials) = cred {
+///     println!("Server\tPID: {}", credentials.0);
+///     println!(      "\tUID: {}", credentials.1);
+///     println!(      "\tGID: {}", credentials.2);
+/// }
+/// # }
+/// # Ok(()) }
+/// ```
+///
+/// [`UdStreamListener`]: struct.UdStreamListener.html " "
+pub struct UdStream {
+    fd: FdOps,
+}
+impl UdStream {
+    /// Connect to a Unix domain socket server at the specified path.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::udsocket::UdStream;
+    ///
+    /// let conn = UdStream::connect("/tmp/example.sock")?;
+    /// // Handle the connection to the server
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    /// See [`ToUdSocketPath`] for an example of using various string types to specify socket paths.
+    ///
+    /// On Linux, paths longer than the [maximum socket path length] are supported as described in the documentation of the constant.
+    ///
+    /// # System calls
+    /// - `socket`
+    /// - `connect`
+    /// - `open`, if the path has to be shortened
+    ///
+    /// [`ToUdSocketPath`]: trait.ToUdSocketPath.html " "
+    /// [maximum socket path length]: const.MAX_UDSOCKET_PATH_LEN.html " "
+    #[inline]
+    pub fn connect<'a>(path: impl ToUdSocketPath<'a>) -> io::Result<Self> {
+        let path = path.to_socket_path()?; // Shadow original by conversion
+        let (addr, addrlen, _parent_dir) = make_sockaddr_un(&path)?;
+        let socket = {
+            let (success, fd) = unsafe {
+                let result = libc::socket(AF_UNIX, SOCK_STREAM, 0);
+                (result != -1, result)
+            };
+            if success { fd }
+            else {
+                return Err(io::Error::last_os_error())
+            }
+        };
+        let success = unsafe {
+            if libc::connect(
+                socket,
+                // Same as in UdSocketListener::bind()
+                &addr as *const _ as *const _,
+                addrlen as u32,
+            ) != -1 {
+                let passcred: c_int = 1;
+                libc::setsockopt(
+                    socket,
+                    SOL_SOCKET,
+                    SO_PASSCRED,
+                    &passcred as *const _ as *const _,
+                    mem::size_of_val(&passcred) as u32,
+                ) != -1
+            } else {
+                false
+            }
+        };
+        if success {
+            Ok(unsafe {
+                Self::from_raw_fd(socket)
+            })
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+    /// Creates an unconnected socket in non-blocking mode, with `SO_PASSCRED` enabled just like on the streams returned by [`connect`], for the async wrappers to connect with [`connect_nonblocking`] without blocking the executor.
+    ///
+    /// [`connect`]: #method.connect " "
+    /// [`connect_nonblocking`]: #method.connect_nonblocking " "
+    #[cfg(any(feature = "nonblocking", feature = "tokio"))]
+    pub(crate) fn new_nonblocking() -> io::Result<Self> {
+        let fd = unsafe {libc::socket(AF_UNIX, SOCK_STREAM, 0)};
+        if fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: we just created the file descriptor, and wrapping it right away makes sure that
+        // it's closed if anything below fails
+        let stream = unsafe {Self::from_raw_fd(fd)};
+        stream.set_nonblocking(true)?;
+        let passcred: c_int = 1;
+        let success = unsafe {
+            libc::setsockopt(
+                fd,
+                SOL_SOCKET,
+                SO_PASSCRED,
+                &passcred as *const _ as *const _,
+                mem::size_of_val(&passcred) as u32,
+            ) != -1
+        };
+        if success {
+            Ok(stream)
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+    /// Makes one attempt at connecting a socket created by [`new_nonblocking`] to the specified path.
+    ///
+    /// Returns `true` if the stream is connected and `false` if the connection is in progress, in which case the caller should wait for the stream to become writable and then check [`take_error`]. If the server's backlog is full, a `WouldBlock` error is returned; the connection isn't queued in that case, so the attempt has to be repeated later.
+    ///
+    /// [`new_nonblocking`]: #method.new_nonblocking " "
+    /// [`take_error`]: #method.take_error " "
+    #[cfg(any(feature = "nonblocking", feature = "tokio"))]
+    pub(crate) fn connect_nonblocking(&self, path: &UdSocketPath<'_>) -> io::Result<bool> {
+        match connect_to_path(self.as_raw_fd(), path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.raw_os_error() == Some(libc::EINPROGRESS) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+    /// Creates a pair of connected Ud-socket streams which aren't bound to any path, useful for communicating with a child process or between threads without touching the filesystem.
+    ///
+    /// Both streams support everything a stream connected to a listener does, including passing credentials and file descriptors. To pass one of them to a child process, make sure that it's inherited by the child (the streams are created without the close-on-exec flag) and communicate its file descriptor number, or send it to an already running process with [`send_fd`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::udsocket::UdStream;
+    /// use std::{io::prelude::*, thread};
+    ///
+    /// let (mut left, mut right) = UdStream::pair()?;
+    /// thread::spawn(move || right.write_all(b"Hello from the other side!"));
+    /// let mut message = String::new();
+    /// left.read_to_string(&mut message)?;
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # System calls
+    /// - `socketpair`
+    /// - `setsockopt`
+    ///
+    /// [`send_fd`]: #method.send_fd " "
+    #[inline]
+    pub fn pair() -> io::Result<(Self, Self)> {
+        let (fd1, fd2) = socketpair(SOCK_STREAM)?;
+        Ok((Self {fd: fd1}, Self {fd: fd2}))
+    }
+
+    /// Receives bytes from the socket stream.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    ///     - Future versions may use `read` instead; for now, this method is a wrapper around [`recv_vectored`].
+    ///
+    /// [`recv_vectored`]: #method.recv_vectored " "
+    // TODO use read
+    #[inline(always)]
+    pub fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.recv_nonblocking(buf)
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+    /// Same as [`recv`], but without telling timeouts apart from the non-blocking mode, for the async wrappers, which always use the latter.
+    ///
+    /// [`recv`]: #method.recv " "
+    #[inline(always)]
+    pub(crate) fn recv_nonblocking(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.fd.read(buf)
+    }
+    /// Receives bytes from the socket stream, making use of [scatter input] for the main data.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    ///     - Future versions may use `readv` instead; for now, this method is a wrapper around [`recv_ancillary_vectored`].
+    ///
+    /// [scatter input]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    /// [`recv_ancillary_vectored`]: #method.recv_ancillary_vectored " "
+    // TODO use readv
+    #[inline(always)]
+    pub fn recv_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.recv_vectored_nonblocking(bufs)
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+    /// Same as [`recv_vectored`], but without telling timeouts apart from the non-blocking mode.
+    ///
+    /// [`recv_vectored`]: #method.recv_vectored " "
+    #[inline(always)]
+    pub(crate) fn recv_vectored_nonblocking(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        recv_msg_ancillary(self.as_raw_fd(), bufs, &mut [])
+            .map(|x| x.0)
+    }
+    /// Receives both bytes and ancillary data from the socket stream.
+    ///
+    /// The ancillary data buffer is automatically converted from the supplied value, if possible. For that reason, mutable slices of bytes (`u8` values) can be passed directly.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    #[inline(always)]
+    pub fn recv_ancillary<'a: 'b, 'b>(
+        &self,
+        buf: &mut [u8],
+        abuf: &'b mut AncillaryDataBuf<'a>,
+    ) -> io::Result<(usize, usize)> {
+        self.recv_ancillary_vectored(&[IoSliceMut::new(buf)], abuf)
+    }
+    /// Receives bytes and ancillary data from the socket stream, making use of [scatter input] for the main data.
+    ///
+    /// The ancillary data buffer is automatically converted from the supplied value, if possible. For that reason, mutable slices of bytes (`u8` values) can be passed directly.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    ///
+    /// [scatter input]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    #[inline]
+    pub fn recv_ancillary_vectored<'a: 'b, 'b> (
+            &self,
+            bufs: &[IoSliceMut<'_>],
+            abuf: &'b mut AncillaryDataBuf<'a>,
+        ) -> io::Result<(usize, usize)> {
+        let abuf: &mut [u8] = abuf.as_mut();
+        // SAFETY: msghdr consists of integers and pointers, all of which are nullable
+        let mut hdr = unsafe {zeroed::<msghdr>()};
+        hdr.msg_iov = bufs.as_ptr() as *mut _;
+        hdr.msg_iovlen = bufs.len();
+        hdr.msg_control = abuf.as_mut_ptr() as *mut _;
+        hdr.msg_controllen = abuf.len();
+        let (success, bytes_read) = unsafe {
+            let result = libc::recvmsg(
+                self.as_raw_fd(),
+                &mut hdr as *mut _,
+                0,
+            );
+            (result != -1, result as usize)
+        };
+        if success {
+            Ok((
+                bytes_read,
+                hdr.msg_controllen,
+            ))
+        } else {
+            Err(timeout_to_timed_out(self.as_raw_fd(), io::Error::last_os_error()))
+        }
+    }
+
+    /// Sends bytes into the socket stream.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///     - Future versions of `interprocess` may use `write` instead; for now, this method is a wrapper around [`send_vectored`].
+    ///
+    /// [`send_vectored`]: #method.send_vectored " "
+    // TODO use write
+    #[inline(always)]
+    pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        self.send_nonblocking(buf)
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+    /// Same as [`send`], but without telling timeouts apart from the non-blocking mode.
+    ///
+    /// [`send`]: #method.send " "
+    #[inline(always)]
+    pub(crate) fn send_nonblocking(&self, buf: &[u8]) -> io::Result<usize> {
+        self.fd.write(buf)
+    }
+    /// Sends bytes into the socket stream, making use of [gather output] for the main data.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///     - Future versions of `interprocess` may use `writev` instead; for now, this method is a wrapper around [`send_ancillary_vectored`].
+    ///
+    /// [gather output]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    /// [`send_ancillary_vectored`]: #method.send_ancillary_vectored " "
+    // TODO use writev
+    #[inline(always)]
+    pub fn send_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.send_vectored_nonblocking(bufs)
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+    /// Same as [`send_vectored`], but without telling timeouts apart from the non-blocking mode.
+    ///
+    /// [`send_vectored`]: #method.send_vectored " "
+    #[inline(always)]
+    pub(crate) fn send_vectored_nonblocking(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        send_msg_ancillary(self.as_raw_fd(), bufs, &[], None)
+            .map(|x| x.0)
+    }
+    /// Sends bytes and ancillary data into the socket stream.
+    ///
+    /// The ancillary data buffer is automatically converted from the supplied value, if possible. For that reason, slices and `Vec`s of `AncillaryData` can be passed directly.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    #[inline(always)]
+    pub fn send_ancillary<'a> (
+        &self,
+        buf: &[u8],
+        ancillary_data: impl IntoIterator<Item = AncillaryData<'a>>,
+    ) -> io::Result<(usize, usize)> {
+        self.send_ancillary_vectored(&[IoSlice::new(buf)], ancillary_data)
+    }
+
+    /// Sends bytes and ancillary data into the socket stream, making use of [gather output] for the main data.
+    ///
+    /// The ancillary data buffer is automatically converted from the supplied value, if possible. For that reason, slices and `Vec`s of `AncillaryData` can be passed directly.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///
+    /// [gather output]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    #[inline]
+    pub fn send_ancillary_vectored<'a> (
+        &self,
+        bufs: &[IoSlice<'_>],
+        ancillary_data: impl IntoIterator<Item = AncillaryData<'a>>,
+    ) -> io::Result<(usize, usize)> {
+        let abuf_value = ancillary_data.into_iter().collect::<EncodedAncillaryData<'_>>();
+        let abuf: &[u8] = abuf_value.as_ref();
+        // SAFETY: msghdr consists of integers and pointers, all of which are nullable
+        let mut hdr = unsafe {zeroed::<msghdr>()};
+        hdr.msg_iov = bufs.as_ptr() as *mut _;
+        hdr.msg_iovlen = bufs.len();
+        hdr.msg_control = abuf.as_ptr() as *mut _;
+        hdr.msg_controllen = abuf.len();
+        let (success, bytes_written) = unsafe {
+            let result = libc::sendmsg(
+                self.as_raw_fd(),
+                &hdr as *const _,
+                0,
+            );
+            (result != -1, result as usize)
+        };
+        if success {
+            Ok((
+                bytes_written,
+                hdr.msg_controllen,
+            ))
+        } else {
+            Err(timeout_to_timed_out(self.as_raw_fd(), io::Error::last_os_error()))
+        }
+    }
+
+    /// Sends the specified file descriptors to the other side of the connection, duplicating them into the receiving process. The file descriptors stay open in this process.
+    ///
+    /// A single placeholder byte is sent along with the file descriptors, since they can't be sent with no data. The other side must receive them using [`recv_fds`] or [`recv_fd`], and not by reading from the stream — otherwise, the placeholder byte ends up in the received data and the file descriptors are closed. To attach file descriptors to regular data instead, use [`send_with_fds`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::udsocket::UdStream;
+    /// use std::{fs::File, os::unix::io::AsFd};
+    ///
+    /// let conn = UdStream::connect("/tmp/example.sock")?;
+    /// let file = File::open("/etc/hostname")?;
+    /// conn.send_fds(&[file.as_fd()])?;
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///
+    /// [`recv_fds`]: #method.recv_fds " "
+    /// [`recv_fd`]: #method.recv_fd " "
+    /// [`send_with_fds`]: #method.send_with_fds " "
+    #[inline]
+    pub fn send_fds(&self, fds: &[BorrowedFd<'_>]) -> io::Result<()> {
+        if self.send_with_fds(&[0], fds)? == 1 {
+            Ok(())
+        } else {
+            Err(io::Error::new(io::ErrorKind::WriteZero, "failed to send file descriptors"))
+        }
+    }
+    /// Sends the file descriptor owned by the specified object, such as a [`File`], an [`UnnamedPipeReader`] or another `UdStream`, to the other side of the connection, which can then receive it as an object of the same type using [`recv_fd`]. The object stays usable in this process.
+    ///
+    /// See [`send_fds`] for more on how the file descriptor is sent.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///
+    /// [`File`]: https://doc.rust-lang.org/std/fs/struct.File.html " "
+    /// [`UnnamedPipeReader`]: ../../../unnamed_pipe/struct.UnnamedPipeReader.html " "
+    /// [`recv_fd`]: #method.recv_fd " "
+    /// [`send_fds`]: #method.send_fds " "
+    #[inline(always)]
+    pub fn send_fd(&self, fd: impl AsFd) -> io::Result<()> {
+        self.send_fds(&[fd.as_fd()])
+    }
+    /// Sends bytes into the socket stream along with the specified file descriptors, returning how many bytes were sent. The file descriptors stay open in this process.
+    ///
+    /// The file descriptors are attached to the first byte sent, which is why `buf` must not be empty if there are file descriptors to send. The other side must receive that byte using [`recv_with_fds`], [`recv_fds`] or [`recv_fd`] for the file descriptors to arrive.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///
+    /// [`recv_with_fds`]: #method.recv_with_fds " "
+    /// [`recv_fds`]: #method.recv_fds " "
+    /// [`recv_fd`]: #method.recv_fd " "
+    #[inline]
+    pub fn send_with_fds(&self, buf: &[u8], fds: &[BorrowedFd<'_>]) -> io::Result<usize> {
+        fd_passing::send_with_fds(self.as_raw_fd(), buf, fds)
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+    /// Receives file descriptors sent by the other side of the connection using [`send_fds`] or [`send_fd`], consuming the placeholder byte sent along with them.
+    ///
+    /// Up to 253 file descriptors can be received at once, which is the maximum which Linux allows to send in one message. All of them have the close-on-exec flag set, atomically on platforms which support `MSG_CMSG_CLOEXEC` and right after receiving them on others. If the system had to discard some of the file descriptors because there was not enough space for them, the ones which did arrive are closed and an error is returned. If the other side has closed the connection, an error of kind [`UnexpectedEof`] is returned.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::udsocket::UdStreamListener;
+    /// use std::fs::File;
+    ///
+    /// let listener = UdStreamListener::bind("/tmp/example.sock")?;
+    /// let conn = listener.accept()?;
+    /// for fd in conn.recv_fds()? {
+    ///     let file = File::from(fd);
+    ///     // Use the file...
+    /// }
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    /// - `fcntl` (only on platforms without `MSG_CMSG_CLOEXEC`)
+    ///
+    /// [`send_fds`]: #method.send_fds " "
+    /// [`send_fd`]: #method.send_fd " "
+    /// [`UnexpectedEof`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof " "
+    #[inline]
+    pub fn recv_fds(&self) -> io::Result<Vec<OwnedFd>> {
+        match self.recv_with_fds(&mut [0])? {
+            (0, fds) if fds.is_empty() => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed while waiting for file descriptors",
+            )),
+            (_, fds) => Ok(fds),
+        }
+    }
+    /// Receives a file descriptor sent by the other side of the connection using [`send_fd`] or [`send_fds`] as an object of the specified type, such as a [`File`], an [`UnnamedPipeReader`] or a `UdStream`.
+    ///
+    /// If no file descriptors were sent, an error of kind [`InvalidData`] is returned. If more than one was sent, the first one is used and the rest are closed. See [`recv_fds`] for more on how the file descriptor is received.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::{
+    ///     os::unix::udsocket::UdStream,
+    ///     unnamed_pipe::UnnamedPipeReader,
+    /// };
+    /// use std::io::Read;
+    ///
+    /// let conn = UdStream::connect("/tmp/example.sock")?;
+    /// let mut reader: UnnamedPipeReader = conn.recv_fd()?;
+    /// let mut buffer = String::new();
+    /// reader.read_to_string(&mut buffer)?;
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    /// - `fcntl` (only on platforms without `MSG_CMSG_CLOEXEC`)
+    ///
+    /// [`send_fd`]: #method.send_fd " "
+    /// [`send_fds`]: #method.send_fds " "
+    /// [`recv_fds`]: #method.recv_fds " "
+    /// [`File`]: https://doc.rust-lang.org/std/fs/struct.File.html " "
+    /// [`UnnamedPipeReader`]: ../../../unnamed_pipe/struct.UnnamedPipeReader.html " "
+    /// [`InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData " "
+    #[inline]
+    pub fn recv_fd<T: From<OwnedFd>>(&self) -> io::Result<T> {
+        fd_passing::single_fd(self.recv_fds()?).map(T::from)
+    }
+    /// Receives bytes from the socket stream along with any file descriptors attached to them, returning how many bytes were received and the file descriptors.
+    ///
+    /// See [`recv_fds`] for how the file descriptors are received.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    /// - `fcntl` (only on platforms without `MSG_CMSG_CLOEXEC`)
+    ///
+    /// [`recv_fds`]: #method.recv_fds " "
+    #[inline]
+    pub fn recv_with_fds(&self, buf: &mut [u8]) -> io::Result<(usize, Vec<OwnedFd>)> {
+        fd_passing::recv_with_fds(self.as_raw_fd(), buf)
+            .map(|(bytes_read, _, fds)| (bytes_read, fds))
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+
+    /// Enables or disables the non-blocking mode for the stream. By default, it is disabled.
+    ///
+    /// In non-blocking mode, receiving from the stream when no data is available or sending into it when the send buffer is full immediately returns an error of kind [`WouldBlock`] instead of blocking.
+    ///
+    /// # System calls
+    /// - `fcntl`
+    ///
+    /// [`WouldBlock`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.WouldBlock " "
+    #[inline]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        set_nonblocking(self.as_raw_fd(), nonblocking)
+    }
+    /// Sets the timeout for receive operations on the stream. `None` disables the timeout, which is the default.
+    ///
+    /// If a receive operation doesn't complete within the timeout, it fails with an error of kind [`TimedOut`]. Passing a zero duration is an error of kind [`InvalidInput`], since it would be indistinguishable from disabling the timeout.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    /// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+    #[inline]
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        set_timeout(self.as_raw_fd(), libc::SO_RCVTIMEO, timeout)
+    }
+    /// Sets the timeout for send operations on the stream. `None` disables the timeout, which is the default.
+    ///
+    /// If a send operation doesn't complete within the timeout, it fails with an error of kind [`TimedOut`]. Passing a zero duration is an error of kind [`InvalidInput`], since it would be indistinguishable from disabling the timeout.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    /// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+    #[inline]
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        set_timeout(self.as_raw_fd(), libc::SO_SNDTIMEO, timeout)
+    }
+    /// Returns the timeout for receive operations on the stream, or `None` if there is none.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        get_timeout(self.as_raw_fd(), libc::SO_RCVTIMEO)
+    }
+    /// Returns the timeout for send operations on the stream, or `None` if there is none.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn write_timeout(&self) -> io::Result<Option<Duration>> {
+        get_timeout(self.as_raw_fd(), libc::SO_SNDTIMEO)
+    }
+    /// Shuts down the read half, the write half or both halves of the stream.
+    ///
+    /// Once the write half is shut down, the other side reads EOF as soon as it receives all the data sent before that, while still being able to send data back, which is commonly used to signal the end of a request. Shutting down the read half makes further reads on this side return EOF. The shutdown applies to the connection itself and thus affects all handles to it, including ones created by [`try_clone`] and ones held by other processes.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::udsocket::UdStream;
+    /// use std::{io::prelude::*, net::Shutdown};
+    ///
+    /// let mut conn = UdStream::connect("/tmp/example.sock")?;
+    /// conn.write_all(b"Hello from client!")?;
+    /// // Let the server know that the request is complete.
+    /// conn.shutdown(Shutdown::Write)?;
+    /// let mut response = String::new();
+    /// conn.read_to_string(&mut response)?;
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # System calls
+    /// - `shutdown`
+    ///
+    /// [`try_clone`]: #method.try_clone " "
+    #[inline]
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        let how = match how {
+            Shutdown::Read => libc::SHUT_RD,
+            Shutdown::Write => libc::SHUT_WR,
+            Shutdown::Both => libc::SHUT_RDWR,
+        };
+        let success = unsafe {
+            libc::shutdown(self.as_raw_fd(), how) != -1
+        };
+        if success {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+    /// Creates a new handle to the same stream, backed by a duplicate of the file descriptor.
+    ///
+    /// Both handles refer to the same connection, which means that the non-blocking mode and the timeouts are shared between them: changing those settings via one of the handles affects the other one as well. The connection is only closed once all of the handles are dropped.
+    ///
+    /// # System calls
+    /// - `fcntl` with `F_DUPFD_CLOEXEC`
+    pub fn try_clone(&self) -> io::Result<Self> {
+        let (success, fd) = unsafe {
+            let result = libc::fcntl(self.as_raw_fd(), libc::F_DUPFD_CLOEXEC, 0);
+            (result != -1, result)
+        };
+        if success {
+            // SAFETY: we just created the file descriptor, meaning that it's guaranteeed not to be
+            // used elsewhere
+            Ok(unsafe {Self::from_raw_fd(fd)})
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+    /// Splits the stream into a read half and a write half, which can be moved to different threads and used concurrently, and which can later be put back together with [`ReadHalf::reunite`].
+    ///
+    /// Both halves share the same file descriptor, which is closed once both of them are dropped. To use a stream from two places without moving it, use the `Read` and `Write` implementations on `&UdStream` instead.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::udsocket::UdStream;
+    /// use std::{io::prelude::*, thread};
+    ///
+    /// let conn = UdStream::connect("/tmp/example.sock")?;
+    /// let (mut reader, mut writer) = conn.into_split();
+    /// let writer_thread = thread::spawn(move || {
+    ///     writer.write_all(b"Hello from client!\n").map(|_| writer)
+    /// });
+    /// let mut buffer = String::new();
+    /// reader.read_to_string(&mut buffer)?;
+    /// let writer = writer_thread.join().unwrap()?;
+    /// let conn = reader.reunite(writer)?;
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`ReadHalf::reunite`]: struct.ReadHalf.html#method.reunite " "
+    #[inline]
+    pub fn into_split(self) -> (ReadHalf, WriteHalf) {
+        let stream = Arc::new(self);
+        (ReadHalf(Arc::clone(&stream)), WriteHalf(stream))
+    }
+
+    /// Returns the address the stream is bound to. Streams created by connecting to a server aren't bound to any address, which is reported as [`Unnamed`], while streams accepted by a listener have the address of the listener.
+    ///
+    /// Streams accepted by a listener which was created with any of the file ownership options of [`UdStreamListenerOptions`] report the temporary path the listener was bound to before it was moved to its final path, since that's what the system remembers.
+    ///
+    /// # System calls
+    /// - `getsockname`
+    ///
+    /// [`Unnamed`]: enum.UdSocketPath.html#variant.Unnamed " "
+    /// [`UdStreamListenerOptions`]: struct.UdStreamListenerOptions.html " "
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<UdSocketPath<'static>> {
+        socket_addr(self.as_raw_fd(), false)
+    }
+    /// Returns the address of the other end of the stream: the address of the server for streams created by connecting, and usually [`Unnamed`] for streams accepted by a listener, since clients seldom bind their sockets.
+    ///
+    /// The temporary path caveat described for [`local_addr`] also applies to clients of listeners created with file ownership options, which report the temporary path as the address of the server.
+    ///
+    /// # System calls
+    /// - `getpeername`
+    ///
+    /// [`Unnamed`]: enum.UdSocketPath.html#variant.Unnamed " "
+    /// [`local_addr`]: #method.local_addr " "
+    #[inline]
+    pub fn peer_addr(&self) -> io::Result<UdSocketPath<'static>> {
+        socket_addr(self.as_raw_fd(), true)
+    }
+
+    /// Retrieves the credentials of the process on the other end of the stream, as recorded by the kernel when the connection was established.
+    ///
+    /// Unlike [`AncillaryData::Credentials`], this does not require any cooperation from the peer, which makes it suitable for authorization decisions on the server side.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(target_os = "linux")] {
+    /// use interprocess::os::unix::udsocket::UdStreamListener;
+    ///
+    /// let listener = UdStreamListener::bind("/tmp/example.sock")?;
+    /// let conn = listener.accept()?;
+    /// let credentials = conn.peer_credentials()?;
+    /// if credentials.uid != 0 && !credentials.groups.contains(&1000) {
+    ///     eprintln!("Rejecting client with PID {}", credentials.pid);
+    /// }
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # System calls
+    /// - `getsockopt` with `SO_PEERCRED`
+    /// - `getsockopt` with `SO_PEERGROUPS`, possibly twice if the peer is in a lot of groups
+    ///
+    /// [`AncillaryData::Credentials`]: enum.AncillaryData.html#variant.Credentials " "
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(target_os = "linux")))]
+    pub fn peer_credentials(&self) -> io::Result<PeerCredentials> {
+        let (success, cred) = unsafe {
+            let mut cred: ucred = zeroed();
+            let mut size = mem::size_of::<ucred>() as u32;
+            let result = libc::getsockopt(
+                self.as_raw_fd(),
+                SOL_SOCKET,
+                libc::SO_PEERCRED,
+                &mut cred as *mut _ as *mut _,
+                &mut size as *mut _,
+            );
+            (result != -1, cred)
+        };
+        if !success {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(PeerCredentials {
+            pid: cred.pid,
+            uid: cred.uid,
+            gid: cred.gid,
+            groups: peer_groups(self.as_raw_fd())?,
+        })
+    }
+}
+impl Read for &UdStream {
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.recv(buf)
+    }
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        let mut abuf = AncillaryDataBuf::Owned(Vec::new());
+        self.recv_ancillary_vectored(bufs, &mut abuf)
+            .map(|x| x.0)
+    }
+}
+impl Write for &UdStream {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.send(buf)
+    }
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.send_ancillary_vectored(bufs, iter::empty())
+            .map(|x| x.0)
+    }
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        // You cannot flush a socket
+        Ok(())
+    }
+}
+impl Read for UdStream {
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self).read(buf)
+    }
+    #[inline(always)]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (&*self).read_vectored(bufs)
+    }
+}
+impl Write for UdStream {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self).write(buf)
+    }
+    #[inline(always)]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (&*self).write_vectored(bufs)
+    }
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self).flush()
+    }
+}
+impl Debug for UdStream {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UdStream")
+            .field("file_descriptor", &self.as_raw_fd())
+            .finish()
+    }
+}
+impl_owned_fd!(UdStream);
+#[cfg(unix)]
+impl AsRawFd for UdStream {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> c_int {
+        self.fd.as_raw_fd()
+    }
+}
+#[cfg(unix)]
+impl IntoRawFd for UdStream {
+    #[inline(always)]
+    fn into_raw_fd(self) -> c_int {
+        self.fd.into_raw_fd()
+    }
+}
+#[cfg(unix)]
+impl FromRawFd for UdStream {
+    #[inline(always)]
+    unsafe fn from_raw_fd(fd: c_int) -> Self {
+        Self {fd: FdOps(fd)}
+    }
+}
+
+/// The read half of a [`UdStream`], created by [`into_split`].
+///
+/// [`UdStream`]: struct.UdStream.html " "
+/// [`into_split`]: struct.UdStream.html#method.into_split " "
+pub struct ReadHalf(Arc<UdStream>);
+impl ReadHalf {
+    /// Puts the stream back together from the two halves, failing if the write half was split off a different stream.
+    #[inline]
+    pub fn reunite(self, write_half: WriteHalf) -> Result<UdStream, ReuniteError<Self, WriteHalf>> {
+        if !Arc::ptr_eq(&self.0, &write_half.0) {
+            return Err(ReuniteError {read_half: self, write_half});
+        }
+        drop(write_half);
+        Ok(Arc::try_unwrap(self.0)
+            .expect("the halves of a stream are the only owners of the stream"))
+    }
+}
+impl Read for ReadHalf {
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self.0).read(buf)
+    }
+    #[inline(always)]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (&*self.0).read_vectored(bufs)
+    }
+}
+impl Debug for ReadHalf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ReadHalf")
+            .field(&self.0)
+            .finish()
+    }
+}
+#[cfg(unix)]
+impl AsRawFd for ReadHalf {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> c_int {
+        self.0.as_raw_fd()
+    }
+}
+
+/// The write half of a [`UdStream`], created by [`into_split`].
+///
+/// [`UdStream`]: struct.UdStream.html " "
+/// [`into_split`]: struct.UdStream.html#method.into_split " "
+pub struct WriteHalf(Arc<UdStream>);
+impl WriteHalf {
+    /// Puts the stream back together from the two halves, failing if the read half was split off a different stream.
+    #[inline(always)]
+    pub fn reunite(self, read_half: ReadHalf) -> Result<UdStream, ReuniteError<ReadHalf, Self>> {
+        read_half.reunite(self)
+    }
+}
+impl Write for WriteHalf {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self.0).write(buf)
+    }
+    #[inline(always)]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (&*self.0).write_vectored(bufs)
+    }
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self.0).flush()
+    }
+}
+impl Debug for WriteHalf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WriteHalf")
+            .field(&self.0)
+            .finish()
+    }
+}
+#[cfg(unix)]
+impl AsRawFd for WriteHalf {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> c_int {
+        self.0.as_raw_fd()
+    }
+}
+
+/// Credentials of the process on the other end of a Ud-socket stream, as returned by [`UdStream::peer_credentials`].
+///
+/// The values are captured by the kernel at the time the connection is established (i.e. when the client calls `connect`), and do not change afterwards even if the peer process changes its credentials or passes the socket to another process.
+///
+/// [`UdStream::peer_credentials`]: struct.UdStream.html#method.peer_credentials " "
+#[cfg(target_os = "linux")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(target_os = "linux")))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PeerCredentials {
+    /// The process ID of the peer.
+    pub pid: pid_t,
+    /// The effective user ID of the peer.
+    pub uid: uid_t,
+    /// The effective group ID of the peer.
+    pub gid: gid_t,
+    /// The supplementary groups of the peer. Empty if the kernel is older than Linux 4.13, which introduced `SO_PEERGROUPS`.
+    pub groups: Vec<gid_t>,
+}
+
+#[cfg(all(target_os = "linux", any(target_arch = "sparc", target_arch = "sparc64")))]
+const SO_PEERGROUPS: c_int = 0x3d;
+#[cfg(all(target_os = "linux", not(any(target_arch = "sparc", target_arch = "sparc64"))))]
+const SO_PEERGROUPS: c_int = 59;
+
+#[cfg(target_os = "linux")]
+fn peer_groups(fd: c_int) -> io::Result<Vec<gid_t>> {
+    // Enough for the vast majority of users; if it isn't, the kernel tells us how much it needs.
+    let mut groups = vec![0 as gid_t; 16];
+    loop {
+        let mut size = (groups.len() * mem::size_of::<gid_t>()) as u32;
+        let result = unsafe {
+            libc::getsockopt(
+                fd,
+                SOL_SOCKET,
+                SO_PEERGROUPS,
+                groups.as_mut_ptr() as *mut _,
+                &mut size as *mut _,
+            )
+        };
+        let count = size as usize / mem::size_of::<gid_t>();
+        if result != -1 {
+            groups.truncate(count);
+            return Ok(groups);
+        }
+        let error = io::Error::last_os_error();
+        match error.raw_os_error() {
+            Some(libc::ERANGE) if count > groups.len() => groups.resize(count, 0),
+            Some(libc::ENOPROTOOPT) => return Ok(Vec::new()),
+            _ => return Err(error),
+        }
+    }
+}
+
+/// Retrieves the address the socket is bound to with `getsockname`, or the address of its peer with `getpeername`.
+#[cfg(unix)]
+fn socket_addr(fd: c_int, peer: bool) -> io::Result<UdSocketPath<'static>> {
+    // SAFETY: sockaddr_un consists of integers
+    let mut addr = unsafe {zeroed::<sockaddr_un>()};
+    let mut addrlen = mem::size_of_val(&addr) as libc::socklen_t;
+    let success = unsafe {
+        let addr_ptr = &mut addr as *mut _ as *mut _;
+        if peer {
+            libc::getpeername(fd, addr_ptr, &mut addrlen) != -1
+        } else {
+            libc::getsockname(fd, addr_ptr, &mut addrlen) != -1
+        }
+    };
+    if success {
+        let mut path = UdSocketPath::Unnamed;
+        path.write_sockaddr_un_to_self(&addr, addrlen as usize);
+        Ok(path)
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Converts `EAGAIN` errors caused by a socket timeout expiring into errors of kind `TimedOut`, leaving those which come from the socket being in non-blocking mode as `WouldBlock`.
+///
+/// This costs an extra `fcntl` call per `WouldBlock`, so it's only applied by the public synchronous methods and never by the raw helpers or the `_nonblocking` methods which the async wrappers use.
+#[cfg(unix)]
+fn timeout_to_timed_out(fd: c_int, error: io::Error) -> io::Error {
+    if error.kind() == io::ErrorKind::WouldBlock && get_nonblocking(fd).ok() == Some(false) {
+        io::Error::new(io::ErrorKind::TimedOut, error)
+    } else {
+        error
+    }
+}
+#[cfg(unix)]
+fn set_timeout(fd: c_int, option: c_int, timeout: Option<Duration>) -> io::Result<()> {
+    let timeval = match timeout {
+        Some(timeout) if timeout == Duration::from_secs(0) => return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cannot set a zero duration timeout",
+        )),
+        Some(timeout) => libc::timeval {
+            tv_sec: timeout.as_secs().min(libc::time_t::MAX as u64) as libc::time_t,
+            // A timeout of less than one microsecond must not be rounded down to zero, since
+            // that would disable the timeout entirely.
+            tv_usec: if timeout.as_secs() == 0 {
+                (timeout.subsec_micros().max(1)) as libc::suseconds_t
+            } else {
+                timeout.subsec_micros() as libc::suseconds_t
+            },
+        },
+        None => libc::timeval {tv_sec: 0, tv_usec: 0},
+    };
+    let success = unsafe {
+        libc::setsockopt(
+            fd,
+            SOL_SOCKET,
+            option,
+            &timeval as *const _ as *const _,
+            mem::size_of_val(&timeval) as u32,
+        ) != -1
+    };
+    if success {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+#[cfg(unix)]
+fn get_timeout(fd: c_int, option: c_int) -> io::Result<Option<Duration>> {
+    let (success, timeval) = unsafe {
+        let mut timeval: libc::timeval = zeroed();
+        let mut size = mem::size_of_val(&timeval) as u32;
+        let result = libc::getsockopt(
+            fd,
+            SOL_SOCKET,
+            option,
+            &mut timeval as *mut _ as *mut _,
+            &mut size as *mut _,
+        );
+        (result != -1, timeval)
+    };
+    if !success {
+        return Err(io::Error::last_os_error());
+    }
+    if timeval.tv_sec == 0 && timeval.tv_usec == 0 {
+        Ok(None)
+    } else {
+        Ok(Some(
+            Duration::from_secs(timeval.tv_sec as u64)
+                + Duration::from_micros(timeval.tv_usec as u64)
+        ))
+    }
+}
+/// Accepts a connection on the listener socket, failing with `TimedOut` if none arrives in time.
+///
+/// The file status flags of the descriptor are shared with every other user of the socket, so they're never changed here. If the listener is in non-blocking mode, losing the connection which woke up the wait to someone else leads to another wait; in blocking mode, the `accept` call blocks instead, which callers must document.
+#[cfg(unix)]
+fn accept_with_timeout(fd: c_int, timeout: Duration) -> io::Result<c_int> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        wait_for_readiness(fd, libc::POLLIN, remaining)?;
+        let accepted = unsafe {libc::accept(fd, ptr::null_mut(), ptr::null_mut())};
+        if accepted != -1 {
+            return Ok(accepted);
+        }
+        let error = io::Error::last_os_error();
+        match error.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted => {},
+            _ => return Err(error),
+        }
+    }
+}
+/// Waits until the specified events are reported for the descriptor, failing with `TimedOut` if that doesn't happen in time.
+#[cfg(unix)]
+fn wait_for_readiness(fd: c_int, events: libc::c_short, timeout: Duration) -> io::Result<()> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        // Round up so that we never wake up before the deadline and spin.
+        let mut timeout_ms = remaining.as_millis();
+        if Duration::from_millis(timeout_ms as u64) < remaining {
+            timeout_ms += 1;
+        }
+        let mut pollfd = libc::pollfd {fd, events, revents: 0};
+        let result = unsafe {
+            libc::poll(&mut pollfd as *mut _, 1, timeout_ms.min(c_int::MAX as u128) as c_int)
+        };
+        match result {
+            -1 => {
+                let error = io::Error::last_os_error();
+                if error.kind() != io::ErrorKind::Interrupted {
+                    return Err(error);
+                }
+            },
+            0 => {
+                if Instant::now() >= deadline {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "timed out waiting for the socket to become ready",
+                    ));
+                }
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+/// A datagram socket in the Unix domain.
+///
+/// All such sockets have the `SOCK_DGRAM` socket type; in other words, this is the Unix domain version of a UCP socket.
+pub struct UdSocket {
+    fd: FdOps,
+}
+impl UdSocket {
+    /// Creates a new server socket at the specified address.
+    ///
+    /// If the socket path exceeds the [maximum socket path length] (which includes the first 0 byte when using the [socket namespace]), an error is returned, unless it's a file path on Linux, where longer paths are handled as described in the documentation of the constant. Errors can also be produced for different reasons, i.e. errors should always be handled regardless of whether the path is known to be short enough or not.
+    ///
+    /// # Example
+    /// See [`ToUdSocketPath`] for an example of using various string types to specify socket paths.
+    ///
+    /// # System calls
+    /// - `socket`
+    /// - `bind`
+    /// - `open`, if the path has to be shortened
+    ///
+    /// [maximum socket path length]: const.MAX_UDSOCKET_PATH_LEN.html " "
+    /// [socket namespace]: enum.UdSocketPath.html#namespaced " "
+    /// [`ToUdSocketPath`]: trait.ToUdSocketPath.html " "
+    pub fn bind<'a>(path: impl ToUdSocketPath<'a>) -> io::Result<Self> {
+        let path = path.to_socket_path()?; // Shadow original by conversion
+        let (addr, addrlen, _parent_dir) = make_sockaddr_un(&path)?;
+        let socket = {
+            let (success, fd) = unsafe {
+                let result = libc::socket(AF_UNIX, SOCK_DGRAM, 0);
+                (result != -1, result)
+            };
+            if success { fd }
+            else {
+                return Err(io::Error::last_os_error())
+            }
+        };
+        let success = unsafe {
+            if libc::bind(
+                socket,
+                // Double cast because you cannot cast a reference to a pointer of arbitrary type
+                // but you can cast any narrow pointer to any other narrow pointer
+                &addr as *const _ as *const _,
+                addrlen as u32,
+            ) != -1 {
+                let passcred: c_int = 1;
+                libc::setsockopt(
+                    socket,
+                    SOL_SOCKET,
+                    SO_PASSCRED,
+                    &passcred as *const _ as *const _,
+                    mem::size_of_val(&passcred) as u32,
+                ) != -1
+            } else {
+                false
+            }
+        };
+        if success {
+            Ok(
+                unsafe {
+                    // SAFETY: we just created the file descriptor, meaning that it's guaranteeed
+                    // not to be used elsewhere
+                    Self::from_raw_fd(socket)
+                }
+            )
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+    /// Connect to a Unix domain socket server at the specified path.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::udsocket::UdSocket;
+    ///
+    /// let conn = UdSocket::connect("/tmp/example.sock")?;
+    /// // Handle the connection to the server
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    /// See [`ToUdSocketPath`] for an example of using various string types to specify socket paths.
+    ///
+    /// On Linux, paths longer than the [maximum socket path length] are supported as described in the documentation of the constant.
+    ///
+    /// # System calls
+    /// - `socket`
+    /// - `connect`
+    /// - `open`, if the path has to be shortened
+    ///
+    /// [`ToUdSocketPath`]: trait.ToUdSocketPath.html " "
+    /// [maximum socket path length]: const.MAX_UDSOCKET_PATH_LEN.html " "
+    #[inline]
+    pub fn connect<'a>(path: impl ToUdSocketPath<'a>) -> io::Result<Self> {
+        let path = path.to_socket_path()?; // Shadow original by conversion
+        let (addr, addrlen, _parent_dir) = make_sockaddr_un(&path)?;
+        let socket = {
+            let (success, fd) = unsafe {
+                let result = libc::socket(AF_UNIX, SOCK_DGRAM, 0);
+                (result != -1, result)
+            };
+            if success { fd }
+            else {
+                return Err(io::Error::last_os_error())
+            }
+        };
+        let success = unsafe {
+            if libc::connect(
+                socket,
+                // Same as in UdSocketListener::bind()
+                &addr as *const _ as *const _,
+                addrlen as u32,
+            ) != -1 {
+                let passcred: c_int = 1;
+                libc::setsockopt(
+                    socket,
+                    SOL_SOCKET,
+                    SO_PASSCRED,
+                    &passcred as *const _ as *const _,
+                    mem::size_of_val(&passcred) as u32,
+                ) != -1
+            } else {
+                false
+            }
+        };
+        if success {
+            Ok(unsafe {
+                Self::from_raw_fd(socket)
+            })
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+    /// Creates a pair of Ud-socket datagram sockets which aren't bound to any path and are connected to each other, useful for exchanging messages with a child process or between threads without touching the filesystem.
+    ///
+    /// Both sockets support everything a socket connected to a bound one does, including passing credentials and file descriptors. To pass one of them to a child process, make sure that it's inherited by the child (the sockets are created without the close-on-exec flag) and communicate its file descriptor number.
+    ///
+    /// # System calls
+    /// - `socketpair`
+    /// - `setsockopt`
+    #[inline]
+    pub fn pair() -> io::Result<(Self, Self)> {
+        let (fd1, fd2) = socketpair(SOCK_DGRAM)?;
+        Ok((Self {fd: fd1}, Self {fd: fd2}))
+    }
+    /// Creates a new socket bound to the specified local path and connected to the specified peer, so that the peer can reply to the datagrams it receives from this socket by sending its replies to the address reported by [`recv_from`].
+    ///
+    /// A socket created with [`connect`] isn't bound to any path, meaning that the peer sees an unnamed address and can't send anything back. If connecting fails, the socket file created by binding is removed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::udsocket::UdSocket;
+    ///
+    /// let conn = UdSocket::bind_and_connect("/tmp/client.sock", "/tmp/server.sock")?;
+    /// conn.send(b"request")?;
+    /// let mut buffer = [0; 128];
+    /// let (size, _) = conn.recv(&mut buffer)?;
+    /// println!("Server replied: {}", String::from_utf8_lossy(&buffer[..size]));
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # System calls
+    /// - `socket`
+    /// - `bind`
+    /// - `setsockopt`
+    /// - `connect`
+    ///
+    /// [`recv_from`]: #method.recv_from " "
+    /// [`connect`]: #method.connect " "
+    pub fn bind_and_connect<'a, 'b>(
+        local_path: impl ToUdSocketPath<'a>,
+        peer_path: impl ToUdSocketPath<'b>,
+    ) -> io::Result<Self> {
+        let local_path = local_path.to_socket_path()?;
+        let peer_path = peer_path.to_socket_path()?;
+        let socket = Self::bind(local_path.clone())?;
+        if let Err(error) = connect_to_path(socket.as_raw_fd(), &peer_path) {
+            if let UdSocketPath::File(file_path) = &local_path {
+                let _ = unlink(file_path);
+            }
+            return Err(error);
+        }
+        Ok(socket)
+    }
+    /// Creates a new socket bound to a unique name in the socket namespace which is chosen by the kernel, returned by [`local_addr`] as a [`NamespacedBytes`] name.
+    ///
+    /// Such a name can't be claimed by any other socket for as long as this one is open, meaning that it can't be spoofed, which makes it a good reply address for a client which doesn't want to choose a name by itself: servers can simply send replies to the address reported by [`recv_from`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # #[cfg(target_os = "linux")] {
+    /// use interprocess::os::unix::udsocket::UdSocket;
+    ///
+    /// let socket = UdSocket::autobind()?;
+    /// println!("Bound to {:?}", socket.local_addr()?);
+    /// socket.send_to(b"request", "/tmp/server.sock")?;
+    /// let mut buffer = [0; 128];
+    /// let (size, _) = socket.recv(&mut buffer)?;
+    /// # }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # System calls
+    /// - `socket`
+    /// - `bind`
+    /// - `setsockopt`
+    ///
+    /// [`local_addr`]: #method.local_addr " "
+    /// [`NamespacedBytes`]: enum.UdSocketPath.html#variant.NamespacedBytes " "
+    /// [`recv_from`]: #method.recv_from " "
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(target_os = "linux")))]
+    pub fn autobind() -> io::Result<Self> {
+        let (success, fd) = unsafe {
+            let result = libc::socket(AF_UNIX, SOCK_DGRAM, 0);
+            (result != -1, result)
+        };
+        if !success {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: we just created the file descriptor, meaning that it's guaranteed not to be used
+        // elsewhere; wrapping it right away makes sure that it's closed if anything below fails
+        let socket = unsafe {Self::from_raw_fd(fd)};
+        // SAFETY: sockaddr_un consists of integers
+        let mut addr = unsafe {zeroed::<sockaddr_un>()};
+        addr.sun_family = AF_UNIX as _;
+        let success = unsafe {
+            // Binding to an address which consists of nothing but the address family is what asks
+            // the kernel to pick a name.
+            if libc::bind(
+                fd,
+                &addr as *const _ as *const _,
+                mem::size_of::<libc::sa_family_t>() as u32,
+            ) != -1 {
+                let passcred: c_int = 1;
+                libc::setsockopt(
+                    fd,
+                    SOL_SOCKET,
+                    SO_PASSCRED,
+                    &passcred as *const _ as *const _,
+                    mem::size_of_val(&passcred) as u32,
+                ) != -1
+            } else {
+                false
+            }
+        };
+        if success {
+            Ok(socket)
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+    /// Dissolves the association between the socket and the peer it's connected to, if any, so that [`send`] fails until a destination is specified with [`send_to`] and datagrams from any sender can be received again. The local address of the socket, if it's bound, stays the same.
+    ///
+    /// # Platform-specific behavior
+    /// The socket is disconnected by connecting it to an `AF_UNSPEC` address. Linux handles this explicitly, while BSD-derived systems, including macOS, disconnect the socket first and then fail to connect it to the unsupported address — the resulting `EAFNOSUPPORT` error is ignored there.
+    ///
+    /// # System calls
+    /// - `connect`
+    ///
+    /// [`send`]: #method.send " "
+    /// [`send_to`]: #method.send_to " "
+    pub fn disconnect(&self) -> io::Result<()> {
+        // SAFETY: sockaddr consists of integers
+        let mut addr = unsafe {zeroed::<libc::sockaddr>()};
+        addr.sa_family = libc::AF_UNSPEC as _;
+        let success = unsafe {
+            libc::connect(
+                self.as_raw_fd(),
+                &addr as *const _,
+                mem::size_of_val(&addr) as u32,
+            ) != -1
+        };
+        if success {
+            return Ok(());
+        }
+        let error = io::Error::last_os_error();
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        if error.raw_os_error() == Some(libc::EAFNOSUPPORT) {
+            return Ok(());
+        }
+        Err(error)
+    }
+    /// Returns the address the socket is bound to, which is [`Unnamed`] if it isn't bound to any.
+    ///
+    /// Sockets bound to a file path exceeding the [maximum socket path length] report the `/proc/self/fd` path which was used to shorten it.
+    ///
+    /// # System calls
+    /// - `getsockname`
+    ///
+    /// [`Unnamed`]: enum.UdSocketPath.html#variant.Unnamed " "
+    /// [maximum socket path length]: const.MAX_UDSOCKET_PATH_LEN.html " "
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<UdSocketPath<'static>> {
+        socket_addr(self.as_raw_fd(), false)
+    }
+    /// Returns the address of the peer the socket is connected to, which is [`Unnamed`] if the peer isn't bound to any address.
+    ///
+    /// If the socket isn't connected, an error of kind [`NotConnected`] is returned.
+    ///
+    /// # System calls
+    /// - `getpeername`
+    ///
+    /// [`Unnamed`]: enum.UdSocketPath.html#variant.Unnamed " "
+    /// [`NotConnected`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.NotConnected " "
+    #[inline]
+    pub fn peer_addr(&self) -> io::Result<UdSocketPath<'static>> {
+        socket_addr(self.as_raw_fd(), true)
+    }
+
+    /// Receives a single datagram from the socket, returning how much of the buffer was filled out and whether a part of the datagram was discarded because the buffer was too small.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    ///     - Future versions of `interprocess` may use `read` instead; for now, this method is a wrapper around [`recv_vectored`].
+    ///
+    /// [scatter input]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    /// [`recv_vectored`]: #method.recv_vectored " "
+    // TODO use read
+    #[inline(always)]
+    pub fn recv(&self, buf: &mut [u8]) -> io::Result<(usize, bool)> {
+        self.recv_vectored(&mut [IoSliceMut::new(buf)])
+    }
+    /// Same as [`recv`], but without telling timeouts apart from the non-blocking mode, for the async wrappers, which always use the latter.
+    ///
+    /// [`recv`]: #method.recv " "
+    #[cfg(feature = "tokio")]
+    #[inline(always)]
+    pub(crate) fn recv_nonblocking(&self, buf: &mut [u8]) -> io::Result<(usize, bool)> {
+        recv_msg_ancillary(self.as_raw_fd(), &mut [IoSliceMut::new(buf)], &mut [])
+            .map(|x| (x.0, x.1))
+    }
+
+    /// Receives a single datagram from the socket, making use of [scatter input] and returning how much of the buffer was filled out and whether a part of the datagram was discarded because the buffer was too small.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    ///     - Future versions of `interprocess` may use `readv` instead; for now, this method is a wrapper around [`recv_ancillary_vectored`].
+    ///
+    /// [scatter input]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    /// [`recv_ancillary_vectored`]: #method.recv_ancillary_vectored " "
+    // TODO use readv
+    #[inline(always)]
+    pub fn recv_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<(usize, bool)> {
+        self.recv_ancillary_vectored(bufs, &mut AncillaryDataBuf::Owned(Vec::new()))
+            .map(|x| (x.0, x.1))
+    }
+
+    /// Receives a single datagram and ancillary data from the socket. The return value is in the following order:
+    /// - How many bytes of the datagram were received
+    /// - Whether a part of the datagram was discarded because the buffer was too small
+    /// - How many bytes of ancillary data were received
+    /// - Whether some ancillary data was discarded because the buffer was too small
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    ///
+    /// [scatter input]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    #[inline(always)]
+    pub fn recv_ancillary<'a: 'b, 'b>(
+        &self,
+        buf: &mut [u8],
+        abuf: &'b mut AncillaryDataBuf<'a>,
+    ) -> io::Result<(usize, bool, usize, bool)> {
+        self.recv_ancillary_vectored(&mut [IoSliceMut::new(buf)], abuf)
+    }
+
+    /// Receives a single datagram and ancillary data from the socket, making use of [scatter input]. The return value is in the following order:
+    /// - How many bytes of the datagram were received
+    /// - Whether a part of the datagram was discarded because the buffer was too small
+    /// - How many bytes of ancillary data were received
+    /// - Whether some ancillary data was discarded because the buffer was too small
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    ///
+    /// [scatter input]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    pub fn recv_ancillary_vectored<'a: 'b, 'b>(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        abuf: &'b mut AncillaryDataBuf<'a>,
+    ) -> io::Result<(usize, bool, usize, bool)> {
+        recv_msg_ancillary(self.as_raw_fd(), bufs, abuf.as_mut())
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+
+    /// Receives a single datagram and the source address from the socket, returning how much of the buffer was filled out and whether a part of the datagram was discarded because the buffer was too small.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    ///     - Future versions of `interprocess` may use `recvfrom` instead; for now, this method is a wrapper around [`recv_from_vectored`].
+    ///
+    /// [`recv_from_vectored`]: #method.recv_from_vectored " "
+    // TODO use recvfrom
+    #[inline(always)]
+    pub fn recv_from<'a: 'b, 'b>(
+        &self,
+        buf: &mut [u8],
+        addr_buf: &'b mut UdSocketPath<'a>,
+    ) -> io::Result<(usize, bool)> {
+        self.recv_from_vectored(&mut [IoSliceMut::new(buf)], addr_buf)
+    }
+    /// Same as [`recv_from`], but without telling timeouts apart from the non-blocking mode.
+    ///
+    /// [`recv_from`]: #method.recv_from " "
+    #[cfg(feature = "tokio")]
+    #[inline(always)]
+    pub(crate) fn recv_from_nonblocking<'a: 'b, 'b>(
+        &self,
+        buf: &mut [u8],
+        addr_buf: &'b mut UdSocketPath<'a>,
+    ) -> io::Result<(usize, bool)> {
+        recv_from_msg_ancillary(self.as_raw_fd(), &mut [IoSliceMut::new(buf)], &mut [], addr_buf)
+            .map(|x| (x.0, x.1))
+    }
+
+    /// Receives a single datagram and the source address from the socket, making use of [scatter input] and returning how much of the buffer was filled out and whether a part of the datagram was discarded because the buffer was too small.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    ///
+    /// [scatter input]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    #[inline(always)]
+    pub fn recv_from_vectored<'a: 'b, 'b>(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        addr_buf: &'b mut UdSocketPath<'a>,
+    ) -> io::Result<(usize, bool)> {
+        self.recv_from_ancillary_vectored(bufs, &mut AncillaryDataBuf::Owned(Vec::new()), addr_buf)
+            .map(|x| (x.0, x.1))
+    }
+
+    /// Receives a single datagram, ancillary data and the source address from the socket. The return value is in the following order:
+    /// - How many bytes of the datagram were received
+    /// - Whether a part of the datagram was discarded because the buffer was too small
+    /// - How many bytes of ancillary data were received
+    /// - Whether some ancillary data was discarded because the buffer was too small
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    #[inline(always)]
+    pub fn recv_from_ancillary<'a: 'b, 'b, 'c: 'd, 'd>(
+        &self,
+        buf: &mut [u8],
+        abuf: &'b mut AncillaryDataBuf<'a>,
+        addr_buf: &'d mut UdSocketPath<'c>,
+    ) -> io::Result<(usize, bool, usize, bool)> {
+        self.recv_from_ancillary_vectored(&mut [IoSliceMut::new(buf)], abuf, addr_buf)
+    }
+
+    /// Receives a single datagram, ancillary data and the source address from the socket, making use of [scatter input]. The return value is in the following order:
+    /// - How many bytes of the datagram were received
+    /// - Whether a part of the datagram was discarded because the buffer was too small
+    /// - How many bytes of ancillary data were received
+    /// - Whether some ancillary data was discarded because the buffer was too small
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    ///
+    /// [scatter input]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    pub fn recv_from_ancillary_vectored<'a: 'b, 'b, 'c: 'd, 'd> (
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        abuf: &'b mut AncillaryDataBuf<'a>,
+        addr_buf: &'d mut UdSocketPath<'c>,
+    ) -> io::Result<(usize, bool, usize, bool)> {
+        recv_from_msg_ancillary(self.as_raw_fd(), bufs, abuf.as_mut(), addr_buf)
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+
+    /// Returns the size of the next datagram available on the socket without discarding it.
+    ///
+    /// This method is only available on Linux since kernel version 2.2. On lower kernel versions, it will fail; on other platforms, it's absent and thus any usage of it will result in a compile-time error.
+    ///
+    /// # System calls
+    /// - `recv`
+    #[cfg(target_os = "linux")]
+    #[inline(always)]
+    pub fn peek_msg_size(&self) -> io::Result<usize> {
+        peek_msg_size(self.as_raw_fd())
+    }
+
+    /// Sends a datagram into the socket.
+    ///
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///     - Future versions of `interprocess` may use `write` instead; for now, this method is a wrapper around [`send_vectored`].
+    ///
+    /// [`send_vectored`]: #method.send_vectored " "
+    #[inline(always)]
+    pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        self.send_vectored(&[IoSlice::new(buf)])
+    }
+    /// Same as [`send`], but without telling timeouts apart from the non-blocking mode.
+    ///
+    /// [`send`]: #method.send " "
+    #[cfg(feature = "tokio")]
+    #[inline(always)]
+    pub(crate) fn send_nonblocking(&self, buf: &[u8]) -> io::Result<usize> {
+        send_msg_ancillary(self.as_raw_fd(), &[IoSlice::new(buf)], &[], None)
+            .map(|x| x.0)
+    }
+    /// Sends a datagram into the socket, making use of [gather output] for the main data.
+    ///
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///     - Future versions of `interprocess` may use `writev` instead; for now, this method is a wrapper around [`send_ancillary_vectored`].
+    ///
+    /// [gather output]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    /// [`send_ancillary_vectored`]: #method.send_ancillary_vectored " "
+    #[inline(always)]
+    pub fn send_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.send_ancillary_vectored(bufs, iter::empty())
+            .map(|x| x.0)
+    }
+    /// Sends a datagram and ancillary data into the socket.
+    ///
+    /// The ancillary data buffer is automatically converted from the supplied value, if possible. For that reason, slices and `Vec`s of `AncillaryData` can be passed directly.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    #[inline(always)]
+    pub fn send_ancillary<'a>(
+        &self,
+        buf: &[u8],
+        ancillary_data: impl IntoIterator<Item = AncillaryData<'a>>,
+    ) -> io::Result<(usize, usize)> {
+        self.send_ancillary_vectored(&[IoSlice::new(buf)], ancillary_data)
+    }
+    /// Sends a datagram and ancillary data into the socket, making use of [gather output] for the main data.
+    ///
+    /// The ancillary data buffer is automatically converted from the supplied value, if possible. For that reason, slices and `Vec`s of `AncillaryData` can be passed directly.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///
+    /// [gather output]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    pub fn send_ancillary_vectored<'a>(
+        &self,
+        bufs: &[IoSlice<'_>],
+        ancillary_data: impl IntoIterator<Item = AncillaryData<'a>>,
+    ) -> io::Result<(usize, usize)> {
+        let abuf = ancillary_data.into_iter().collect::<EncodedAncillaryData<'_>>();
+        send_msg_ancillary(self.as_raw_fd(), bufs, abuf.as_ref(), None)
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+
+    /// Sends a datagram to the specified address, which is possible regardless of whether the socket is connected.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///     - Future versions of `interprocess` may use `sendto` instead; for now, this method is a wrapper around [`send_to_vectored`].
+    ///
+    /// [`send_to_vectored`]: #method.send_to_vectored " "
+    #[inline(always)]
+    pub fn send_to<'a>(&self, buf: &[u8], path: impl ToUdSocketPath<'a>) -> io::Result<usize> {
+        self.send_to_vectored(&[IoSlice::new(buf)], path)
+    }
+    /// Sends a datagram to the specified address, making use of [gather output] for the main data.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///
+    /// [gather output]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    #[inline(always)]
+    pub fn send_to_vectored<'a>(
+        &self,
+        bufs: &[IoSlice<'_>],
+        path: impl ToUdSocketPath<'a>,
+    ) -> io::Result<usize> {
+        self.send_to_ancillary_vectored(bufs, iter::empty(), path)
+            .map(|x| x.0)
+    }
+    /// Sends a datagram and ancillary data to the specified address.
+    ///
+    /// The ancillary data buffer is automatically converted from the supplied value, if possible. For that reason, slices and `Vec`s of `AncillaryData` can be passed directly.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    #[inline(always)]
+    pub fn send_to_ancillary<'a, 'b>(
+        &self,
+        buf: &[u8],
+        ancillary_data: impl IntoIterator<Item = AncillaryData<'a>>,
+        path: impl ToUdSocketPath<'b>,
+    ) -> io::Result<(usize, usize)> {
+        self.send_to_ancillary_vectored(&[IoSlice::new(buf)], ancillary_data, path)
+    }
+    /// Sends a datagram and ancillary data to the specified address, making use of [gather output] for the main data.
+    ///
+    /// The ancillary data buffer is automatically converted from the supplied value, if possible. For that reason, slices and `Vec`s of `AncillaryData` can be passed directly.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///
+    /// [gather output]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    pub fn send_to_ancillary_vectored<'a, 'b>(
+        &self,
+        bufs: &[IoSlice<'_>],
+        ancillary_data: impl IntoIterator<Item = AncillaryData<'a>>,
+        path: impl ToUdSocketPath<'b>,
+    ) -> io::Result<(usize, usize)> {
+        let path = path.to_socket_path()?;
+        let abuf = ancillary_data.into_iter().collect::<EncodedAncillaryData<'_>>();
+        send_msg_ancillary(self.as_raw_fd(), bufs, abuf.as_ref(), Some(&path))
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+    /// Receives as many datagrams as are available into the specified batch, up to its capacity, waiting for at least one to arrive. Returns the amount of datagrams received, which can then be inspected through the batch.
+    ///
+    /// Only the first datagram is waited for: once it has arrived, the call returns with whatever else has already been queued up, which makes this suitable for draining a busy socket with as few system calls as possible.
+    ///
+    /// # System calls
+    /// - `recvmmsg` on Linux
+    /// - `recvmsg`, repeatedly, on other platforms and on Linux kernels which lack `recvmmsg`
+    #[inline]
+    pub fn recv_batch(&self, batch: &mut RecvBatch) -> io::Result<usize> {
+        batch::recv_batch(self.as_raw_fd(), batch)
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+    /// Sends the datagrams queued up in the specified batch, returning how many of them were sent.
+    ///
+    /// The datagrams which were sent are removed from the batch, so if not all of them could be sent at once, calling this method again with the same batch continues with the rest. An error is only returned if not even the first datagram could be sent.
+    ///
+    /// # System calls
+    /// - `sendmmsg` on Linux
+    /// - `sendmsg`, repeatedly, on other platforms and on Linux kernels which lack `sendmmsg`
+    #[inline]
+    pub fn send_batch(&self, batch: &mut SendBatch) -> io::Result<usize> {
+        batch::send_batch(self.as_raw_fd(), batch)
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+
+    /// Sends the specified file descriptors in a datagram, duplicating them into the receiving process. The file descriptors stay open in this process.
+    ///
+    /// The datagram contains a single placeholder byte, since file descriptors can't be sent with no data. The other side must receive it using [`recv_fds`] or [`recv_fd`] for the file descriptors to arrive. To attach file descriptors to a datagram with regular data instead, use [`send_with_fds`].
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///
+    /// [`recv_fds`]: #method.recv_fds " "
+    /// [`recv_fd`]: #method.recv_fd " "
+    /// [`send_with_fds`]: #method.send_with_fds " "
+    #[inline]
+    pub fn send_fds(&self, fds: &[BorrowedFd<'_>]) -> io::Result<()> {
+        if self.send_with_fds(&[0], fds)? == 1 {
+            Ok(())
+        } else {
+            Err(io::Error::new(io::ErrorKind::Other, PartialMsgWriteError))
+        }
+    }
+    /// Sends the file descriptor owned by the specified object, such as a [`File`], an [`UnnamedPipeReader`] or a [`UdStream`], in a datagram, so that the other side can receive it as an object of the same type using [`recv_fd`]. The object stays usable in this process.
+    ///
+    /// See [`send_fds`] for more on how the file descriptor is sent.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///
+    /// [`File`]: https://doc.rust-lang.org/std/fs/struct.File.html " "
+    /// [`UnnamedPipeReader`]: ../../../unnamed_pipe/struct.UnnamedPipeReader.html " "
+    /// [`UdStream`]: struct.UdStream.html " "
+    /// [`recv_fd`]: #method.recv_fd " "
+    /// [`send_fds`]: #method.send_fds " "
+    #[inline(always)]
+    pub fn send_fd(&self, fd: impl AsFd) -> io::Result<()> {
+        self.send_fds(&[fd.as_fd()])
+    }
+    /// Sends a datagram into the socket along with the specified file descriptors, returning how many bytes were sent. The file descriptors stay open in this process.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    #[inline]
+    pub fn send_with_fds(&self, buf: &[u8], fds: &[BorrowedFd<'_>]) -> io::Result<usize> {
+        fd_passing::send_with_fds(self.as_raw_fd(), buf, fds)
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+    /// Receives a datagram sent using [`send_fds`] or [`send_fd`] and returns the file descriptors it carried.
+    ///
+    /// Up to 253 file descriptors can be received at once, which is the maximum which Linux allows to send in one message. All of them have the close-on-exec flag set, atomically on platforms which support `MSG_CMSG_CLOEXEC` and right after receiving them on others. If the system had to discard some of the file descriptors because there was not enough space for them, the ones which did arrive are closed and an error is returned.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    /// - `fcntl` (only on platforms without `MSG_CMSG_CLOEXEC`)
+    ///
+    /// [`send_fds`]: #method.send_fds " "
+    /// [`send_fd`]: #method.send_fd " "
+    #[inline]
+    pub fn recv_fds(&self) -> io::Result<Vec<OwnedFd>> {
+        self.recv_with_fds(&mut [0]).map(|x| x.2)
+    }
+    /// Receives a datagram carrying a file descriptor, sent using [`send_fd`] or [`send_fds`], and returns the file descriptor as an object of the specified type, such as a [`File`], an [`UnnamedPipeReader`] or a [`UdStream`].
+    ///
+    /// If no file descriptors were sent, an error of kind [`InvalidData`] is returned. If more than one was sent, the first one is used and the rest are closed. See [`recv_fds`] for more on how the file descriptor is received.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    /// - `fcntl` (only on platforms without `MSG_CMSG_CLOEXEC`)
+    ///
+    /// [`send_fd`]: #method.send_fd " "
+    /// [`send_fds`]: #method.send_fds " "
+    /// [`recv_fds`]: #method.recv_fds " "
+    /// [`File`]: https://doc.rust-lang.org/std/fs/struct.File.html " "
+    /// [`UnnamedPipeReader`]: ../../../unnamed_pipe/struct.UnnamedPipeReader.html " "
+    /// [`UdStream`]: struct.UdStream.html " "
+    /// [`InvalidData`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData " "
+    #[inline]
+    pub fn recv_fd<T: From<OwnedFd>>(&self) -> io::Result<T> {
+        fd_passing::single_fd(self.recv_fds()?).map(T::from)
+    }
+    /// Receives a single datagram from the socket along with any file descriptors attached to it. The return value is in the following order:
+    /// - How many bytes of the datagram were received
+    /// - Whether a part of the datagram was discarded because the buffer was too small
+    /// - The file descriptors
+    ///
+    /// See [`recv_fds`] for how the file descriptors are received.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    /// - `fcntl` (only on platforms without `MSG_CMSG_CLOEXEC`)
+    ///
+    /// [`recv_fds`]: #method.recv_fds " "
+    #[inline]
+    pub fn recv_with_fds(&self, buf: &mut [u8]) -> io::Result<(usize, bool, Vec<OwnedFd>)> {
+        fd_passing::recv_with_fds(self.as_raw_fd(), buf)
+            .map(|(bytes_read, flags, fds)| (bytes_read, flags & MSG_TRUNC != 0, fds))
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+}
+impl Debug for UdSocket {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UdSocket")
+            .field("file_descriptor", &self.as_raw_fd())
+            .finish()
+    }
+}
+#[cfg(target_os = "linux")]
+impl ReliableReadMsg for UdSocket {
+    #[inline]
+    fn read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, Vec<u8>>> {
+        let msg_size = self.peek_msg_size()?;
+        if msg_size > buf.len() {
+            let mut new_buffer = vec![0; msg_size];
+            self.recv(&mut new_buffer)
+                    .map(|x| x.0)?;
+            Ok( Err(new_buffer) )
+        } else {
+            Ok(Ok(
+                self.recv(buf)
+                    .map(|x| x.0)?
+            ))
+        }
+    }
+    #[inline]
+    fn try_read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, usize>> {
+        let msg_size = self.peek_msg_size()?;
+        if msg_size > buf.len() {
+            Ok(Err(msg_size))
+        } else {
+            Ok(Ok(
+                self.recv(buf)
+                    .map(|x| x.0)?
+            ))
+        }
+    }
+}
+#[cfg(unix)]
+impl WriteMsg for UdSocket {
+    #[inline]
+    fn write_msg(&mut self, msg: &[u8]) -> io::Result<()> {
+        if self.send(msg)? == msg.len() {
+            Ok(())
+        } else {
+            Err(io::Error::new(io::ErrorKind::Other, PartialMsgWriteError))
+        }
+    }
+}
+#[cfg(unix)]
+impl Sealed for UdSocket {}
+impl_owned_fd!(UdSocket);
+#[cfg(unix)]
+impl AsRawFd for UdSocket {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> c_int {
+        self.fd.as_raw_fd()
+    }
+}
+#[cfg(unix)]
+impl IntoRawFd for UdSocket {
+    #[inline(always)]
+    fn into_raw_fd(self) -> c_int {
+        self.fd.into_raw_fd()
+    }
+}
+#[cfg(unix)]
+impl FromRawFd for UdSocket {
+    #[inline(always)]
+    unsafe fn from_raw_fd(fd: c_int) -> Self {
+        Self {fd: FdOps(fd)}
+    }
+}
+
+/// Represents a name for a Unix domain socket.
+///
+/// The main purpose for this enumeration is to conditionally support the dedicated socket namespace on systems which implement it — for that, the `Namespaced` variant is used. Depending on your system, you might not be seeing it, which is when you'd need the `File` fallback variant, which works on all POSIX-compliant systems.
+///
+/// ## `Namespaced`
+/// This variant refers to sockets in a dedicated socket namespace, which is fully isolated from the main filesystem and closes sockets automatically when the server which opened the socket shuts down. **This variant is only implemented on Linux, which is why it is not available on other POSIX-conformant systems at compile time, resulting in a compile-time error if usage is attempted.**
+///
+/// ## `NamespacedBytes`
+/// Also refers to sockets in the dedicated socket namespace, but identifies them by an exact sequence of bytes, which can contain nul bytes and is used as is, without a nul terminator or padding. Names assigned by the kernel with [`UdSocket::autobind`] and names chosen by programs not using this crate are received in this form, which makes it possible to send datagrams back to their owners. `Namespaced` names are padded with nul bytes up to the maximum length, which is why `Namespaced` and `NamespacedBytes` values with the same bytes refer to different sockets. **Like `Namespaced`, this variant is only implemented on Linux.**
+///
+/// ## `File`
+/// All sockets identified this way are located on the main filesystem and exist as persistent files until deletion, preventing servers from using the same socket without deleting it from the filesystem first. This variant is available on all POSIX-compilant systems.
+///
+/// [`UdSocket::autobind`]: struct.UdSocket.html#method.autobind " "
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UdSocketPath<'a> {
+    /// An unnamed socket, identified only by its file descriptor. This is an invalid path value for creating sockets — all attempts to use such a value will result in an error.
+    Unnamed,
+    /// Identifies a socket which is located in the filesystem tree, existing as a file. See the [enum-level documentation] for more.
+    ///
+    /// [enum-level documentation]: #file " "
+    File(Cow<'a, CStr>),
+    /// Identifies a socket in the dedicated socket namespace, where it exists until the server closes it rather than persisting as a file. See the [enum-level documentation] for more.
+    ///
+    /// [enum-level documentation]: #namespaced " "
+    #[cfg(any(target_os = "linux", doc))]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(target_os = "linux")))]
+    Namespaced(Cow<'a, CStr>),
+    /// Identifies a socket in the dedicated socket namespace by the exact bytes of its name, which may include nul bytes. See the [enum-level documentation] for more.
+    ///
+    /// [enum-level documentation]: #namespacedbytes " "
+    #[cfg(any(target_os = "linux", doc))]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(target_os = "linux")))]
+    NamespacedBytes(Cow<'a, [u8]>),
+}
+impl<'a> UdSocketPath<'a> {
+    /// Returns the path as a `CStr`. The resulting value does not include any indication of whether it's a namespaced socket name or a filesystem path.
+    ///
+    /// A [`NamespacedBytes`] name can only be borrowed as a `CStr` up to its first nul byte, which is why an empty `CStr` is returned if it doesn't contain one. Use [`as_bytes`] to get the entire name.
+    ///
+    /// [`NamespacedBytes`]: #variant.NamespacedBytes " "
+    /// [`as_bytes`]: #method.as_bytes " "
+    #[inline]
+    pub fn as_cstr(&'a self) -> &'a CStr {
+        match self {
+            Self::File(cow) => cow,
+            Self::Namespaced(cow) => cow,
+            #[cfg(target_os = "linux")]
+            Self::NamespacedBytes(cow) => cow
+                .iter()
+                .position(|&byte| byte == 0)
+                .and_then(|end| CStr::from_bytes_with_nul(&cow[..=end]).ok())
+                .unwrap_or_default(),
+            Self::Unnamed => unsafe {CStr::from_bytes_with_nul_unchecked(&[0])},
+        }
+    }
+    /// Returns the path as a byte slice without the nul terminator. The resulting value does not include any indication of whether it's a namespaced socket name or a filesystem path.
+    ///
+    /// Unlike [`as_cstr`], this returns the whole name for [`NamespacedBytes`], including any nul bytes it contains.
+    ///
+    /// [`as_cstr`]: #method.as_cstr " "
+    /// [`NamespacedBytes`]: #variant.NamespacedBytes " "
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::File(cow) => cow.to_bytes(),
+            #[cfg(target_os = "linux")]
+            Self::Namespaced(cow) => cow.to_bytes(),
+            #[cfg(target_os = "linux")]
+            Self::NamespacedBytes(cow) => cow,
+            Self::Unnamed => &[],
+        }
+    }
+    /// Returns the path as a `CString`. The resulting value does not include any indication of whether it's a namespaced socket name or a filesystem path.
+    ///
+    /// A [`NamespacedBytes`] name is cut off at its first nul byte, if it contains any.
+    ///
+    /// [`NamespacedBytes`]: #variant.NamespacedBytes " "
+    #[inline]
+    pub fn into_cstring(self) -> CString {
+        match self {
+            Self::File(cow) => cow.into_owned(),
+            #[cfg(target_os = "linux")]
+            Self::Namespaced(cow) => cow.into_owned(),
+            #[cfg(target_os = "linux")]
+            Self::NamespacedBytes(cow) => {
+                let mut vec = cow.into_owned();
+                if let Some(nul_position) = vec.iter().position(|&byte| byte == 0) {
+                    vec.truncate(nul_position);
+                }
+                CString::new(vec).expect("unexpected nul in socket name after cutting it off at the first one")
+            },
+            Self::Unnamed => CString::new(Vec::new())
+                .unwrap_or_else(|_| unsafe {std::hint::unreachable_unchecked()})
+        }
+    }
+
+    /// Ensures that the path is stored as an owned `CString` in place, and returns whether that required cloning or not. If `self` was not referring to any socket ([`Unnamed` variant]), the value is set to an empty `CString` (only nul terminator) of type [`File`].
+    ///
+    /// [`Unnamed` variant]: #variant.Unnamed " "
+    /// [`File`]: #file " "
+    #[inline]
+    pub fn make_owned(&mut self) -> bool {
+        match self {
+            Self::File(cow) => {
+                match cow {
+                    Cow::Owned(..) => false,
+                    Cow::Borrowed(slice) => {
+                        *self = Self::File(Cow::Owned(slice.to_owned()));
+                        true
+                    },
+                }
+            },
+            #[cfg(target_os = "linux")]
+            Self::Namespaced(cow) => {
+                match cow {
+                    Cow::Owned(..) => false,
+                    Cow::Borrowed(slice) => {
+                        *self = Self::Namespaced(Cow::Owned(slice.to_owned()));
+                        true
+                    },
+                }
+            },
+            #[cfg(target_os = "linux")]
+            Self::NamespacedBytes(cow) => {
+                match cow {
+                    Cow::Owned(..) => false,
+                    Cow::Borrowed(slice) => {
+                        *self = Self::NamespacedBytes(Cow::Owned(slice.to_owned()));
+                        true
+                    },
+                }
+            },
+            Self::Unnamed => {
+                *self = Self::File(
+                    Cow::Owned(
+                        CString::new(Vec::new())
+                            .expect("unexpected unrecoverable CString creation error")
+                    )
+                );
+                true
+            },
+        }
+    }
+
+    /// Returns a mutable reference to the underlying `CString`, cloning the borrowed path if it wasn't owned before.
+    ///
+    /// A [`NamespacedBytes`] name is converted to a [`Namespaced`] one first, as if by [`into_cstring`].
+    ///
+    /// [`NamespacedBytes`]: #variant.NamespacedBytes " "
+    /// [`Namespaced`]: #variant.Namespaced " "
+    /// [`into_cstring`]: #method.into_cstring " "
+    #[inline]
+    pub fn get_cstring_mut(&mut self) -> &mut CString {
+        #[cfg(target_os = "linux")]
+        if let Self::NamespacedBytes(..) = self {
+            let cstring = mem::replace(self, Self::Unnamed).into_cstring();
+            *self = Self::Namespaced(Cow::Owned(cstring));
+        }
+        self.make_owned();
+        self.try_get_cstring_mut()
+            .unwrap_or_else(|| unsafe {
+                // SAFETY: the call to make_owned ensured that there is a CString
+                std::hint::unreachable_unchecked()
+            })
+    }
+    /// Returns a mutable reference to the underlying `CString` if it's available as owned, otherwise returns `None`. [`NamespacedBytes`] names aren't stored as a `CString` and thus also produce `None`.
+    ///
+    /// [`NamespacedBytes`]: #variant.NamespacedBytes " "
+    #[inline]
+    pub fn try_get_cstring_mut(&mut self) -> Option<&mut CString> {
+        match self {
+            Self::File(cow)
+          | Self::Namespaced(cow)
+          => match cow {
+                Cow::Owned(cstring) => Some(cstring),
+                Cow::Borrowed(..) => None,
+            },
+            #[cfg(target_os = "linux")]
+            Self::NamespacedBytes(..) => None,
+            Self::Unnamed => None,
+        }
+    }
+
+    /// Returns `true` if the path to the socket is stored as an owned `CString`, i.e. if `into_cstring` doesn't require cloning the path; `false` otherwise.
+    #[inline]
+    pub fn is_owned(&self) -> bool {
+        match self {
+            Self::File(cow)
+          | Self::Namespaced(cow)
+          => matches!(cow, Cow::Owned(..)),
+            #[cfg(target_os = "linux")]
+            Self::NamespacedBytes(cow) => matches!(cow, Cow::Owned(..)),
+            Self::Unnamed => false,
+        }
+    }
+
+    #[inline]
+    #[cfg(unix)]
+    fn write_sockaddr_un_to_self(&mut self, addr: &sockaddr_un, addrlen: usize) {
+        // The length covers everything up to the end of the path, which doesn't necessarily start
+        // right after the address family since some systems also store the length in the structure.
+        let path_offset = addr.sun_path.as_ptr() as usize - addr as *const _ as usize;
+        let sun_path_length = match addrlen.checked_sub(path_offset) {
+            Some(0) | None => {
+                *self = Self::Unnamed;
+                return;
+            },
+            Some(val) => val.min(addr.sun_path.len()),
+        };
+        // SAFETY: c_char and u8 have the same size, and the length is clamped to the path array
+        let path = unsafe {
+            slice::from_raw_parts(addr.sun_path.as_ptr() as *const u8, sun_path_length)
+        };
+        let (namespaced, path) = match path.split_first() {
+            Some((0, rest)) => (true, rest),
+            _ => (false, path),
+        };
+        // Namespaced names, as opposed to NamespacedBytes ones, are always padded with nuls up to the
+        // maximum length, so anything else must be kept exactly as it is.
+        #[cfg(target_os = "linux")]
+        if namespaced {
+            let mut parts = path.splitn(2, |&byte| byte == 0);
+            let name = parts.next().unwrap_or(&[]);
+            let padded = sun_path_length == addr.sun_path.len()
+                && parts.next().map_or(false, |padding| padding.iter().all(|&byte| byte == 0));
+            if !padded {
+                *self = Self::NamespacedBytes(Cow::Owned(path.to_vec()));
+                return;
+            }
+            *self = Self::Namespaced(Cow::Owned(
+                CString::new(name).expect("unexpected nul in socket name after stopping at the first one"),
+            ));
+            return;
+        }
+        // Whether the system counts the nul terminator as part of the length depends on the system
+        // and on how the address was specified, so we stop at the first nul either way.
+        let path = path.split(|&byte| byte == 0).next().unwrap_or(&[]);
+        // Reuse the allocation of the buffer if it has one.
+        let mut vec = match self.try_get_cstring_mut() {
+            Some(cstring) => mem::take(cstring).into_bytes(),
+            None => Vec::new(),
+        };
+        vec.clear();
+        vec.extend_from_slice(path);
+        let cstring = CString::new(vec)
+            .expect("unexpected nul in socket path after stopping at the first one");
+        // Only Linux has the namespace, elsewhere a leading nul means there's no name at all.
+        *self = if namespaced {
+            UdSocketPath::Unnamed
+        } else {
+            UdSocketPath::File(Cow::Owned(cstring))
+        };
+    }
+    /// Returns `addr_len` to pass to `bind`/`connect`.
+    #[inline]
+    #[cfg(unix)]
+    fn write_self_to_sockaddr_un(&self, addr: &mut sockaddr_un) -> io::Result<usize> {
+        #[cfg(target_os = "linux")]
+        if let UdSocketPath::NamespacedBytes(name) = self {
+            if name.len() > addr.sun_path.len() - 1 {
+                return Err(
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("namespaced socket name should not be longer than {} bytes", addr.sun_path.len() - 1),
+                    ),
+                );
+            }
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    name.as_ptr() as *const _,
+                    addr.sun_path.as_mut_ptr().offset(1),
+                    name.len(),
+                );
+            }
+            // The length of the address is what delimits the name, not a nul terminator.
+            let path_offset = addr.sun_path.as_ptr() as usize - addr as *const _ as usize;
+            return Ok(path_offset + 1 + name.len());
+        }
+        let is_namespaced;
+        let len_of_self = self.as_cstr().to_bytes_with_nul().len();
+        match self {
+            UdSocketPath::File(..) => {
+                is_namespaced = false;
+                if len_of_self > MAX_UDSOCKET_PATH_LEN {
+                    return Err(
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("socket path should not be longer than {} bytes", MAX_UDSOCKET_PATH_LEN),
+                        ),
+                    );
+                }
+            },
+            #[cfg(target_os = "linux")]
+            UdSocketPath::Namespaced(..) => {
+                is_namespaced = true;
+                if len_of_self > (MAX_UDSOCKET_PATH_LEN - 1) {
+                    return Err(
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("namespaced socket name should not be longer than {} bytes", MAX_UDSOCKET_PATH_LEN - 1),
+                        ),
+                    );
+                }
+            },
+            #[cfg(target_os = "linux")]
+            UdSocketPath::NamespacedBytes(..) => unreachable!(),
+            UdSocketPath::Unnamed => return Err(
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "must provide a proper name for the socket"
+                ),
+            )
+        }
+
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.as_cstr().as_ptr(),
+                if is_namespaced {
+                    addr.sun_path.as_mut_ptr().offset(1)
+                } else {
+                    addr.sun_path.as_mut_ptr()
+                },
+                len_of_self,
+            );
+        }
+        Ok(mem::size_of::<sockaddr_un>())
+    }
+}
+impl UdSocketPath<'static> {
+    /// Creates a buffer suitable for usage with [`recv_from`] ([`_ancillary`]/[`_vectored`]/[`_ancillary_vectored`]). The capacity is equal to the [`MAX_UDSOCKET_PATH_LEN`] constant (the nul terminator in the `CString` is included). **The contained value is unspecified — results of reading from the buffer should not be relied upon.**
+    ///
+    /// # Example
+    /// ```
+    /// # #[cfg(unix)] {
+    /// use interprocess::os::unix::udsocket::{UdSocketPath, MAX_UDSOCKET_PATH_LEN};
+    /// use std::borrow::Cow;
+    ///
+    /// let path_buffer = UdSocketPath::buffer();
+    /// match path_buffer {
+    ///     UdSocketPath::File(cow) => match cow {
+    ///         Cow::Owned(cstring)
+    ///     => assert_eq!(cstring.into_bytes_with_nul().capacity(), MAX_UDSOCKET_PATH_LEN),
+    ///         Cow::Borrowed(..) => unreachable!(),
+    ///     }
+    ///     #[cfg(target_os = "linux")]
+    ///     UdSocketPath::Namespaced(..) | UdSocketPath::NamespacedBytes(..) => unreachable!(),
+    ///     UdSocketPath::Unnamed => unreachable!(),
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`recv_from`]: struct.UdSocket.html#method.recv_from " "
+    /// [`_ancillary`]: struct.UdSocket.html#method.recv_from " "
+    /// [`_vectored`]: struct.UdSocket.html#method.recv_from_vectored " "
+    /// [`_ancillary_vectored`]: struct.UdSocket.html#method.recv_from_ancillary_vectored " "
+    /// [`MAX_UDSOCKET_PATH_LEN`]: constant.MAX_UDSOCKET_PATH_LEN.html " "
+    #[inline]
+    pub fn buffer() -> Self {
+        Self::File(Cow::Owned(
+            CString::new(
+                vec![0x2F; MAX_UDSOCKET_PATH_LEN - 1]
+            ).expect("unexpected nul in newly created Vec, possible heap corruption")
+        ))
+    }
+
+    /// Constructs a `UdSocketPath::File` value from a `Vec` of bytes, wrapping `CString::new`.
+    #[inline]
+    pub fn file_from_vec(vec: Vec<u8>) -> Result<Self, NulError> {
+        Ok(Self::File(Cow::Owned(CString::new(vec)?)))
+    }
+    /// Constructs a `UdSocketPath::Namespaced` value from a `Vec` of bytes, wrapping `CString::new`.
+    #[cfg(any(target_os = "linux", doc))]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(target_os = "linux")))]
+    #[inline]
+    pub fn namespaced_from_vec(vec: Vec<u8>) -> Result<Self, NulError> {
+        Ok(Self::Namespaced(Cow::Owned(CString::new(vec)?)))
+    }
+}
+
+/// Trait for types which can be converted to a [path to a Unix domain socket][`UdSocketPath`].
+///
+/// The difference between this trait and [`TryInto`]`<`[`UdSocketPath`]`>` is that the latter does not constrain the error type to be [`io::Error`] and thus is not compatible with many types from the standard library which are widely expected to be convertible to Unix domain socket paths. Additionally, this makes the special syntax for namespaced sockets possible (see below).
+///
+/// ## `@` syntax for namespaced paths
+/// On Linux (since it's the only platform which supports [namespaced socket paths]), an extra syntax feature is implemented for string types which don't have file path semantics, i.e. all standard string types except for [`Path`] and [`PathBuf`]. If the first character in a string is `@`, the path is interpreted as a namespaced socket path rather than a normal file path. Read the `UdSocketPath` documentation for more on what that means. There are several ways to opt out of that behavior if you're referring to a socket at a relative path which starts from a `@`:
+/// - Use [`AsRef`] to convert the string slice type into a [`Path`] which has file path semantics and therefore does not have the `@` syntax enabled, if your string type is [`str`] or [`OsStr`]
+/// - Prefix the path with `./`, which carries the same meaning from the perspective of the OS but bypasses the `@` check
+/// - If your string type is [`CStr`] or [`CString`], explicitly construct `UdSocketPath`'s `File` variant with a [`Cow`] wrapping your string value
+///
+/// # Example
+/// The following example uses the `UdStreamListener::bind` method, but `UdStream::connect` and `UdSocket::bind`/`UdSocket::connect` accept the same argument types too.
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # #[cfg(unix)] {
+/// use interprocess::os::unix::udsocket::{UdStreamListener, UdSocketPath};
+/// use std::{ffi::{CStr, CString}, path::{Path, PathBuf}, borrow::Cow};
+///
+/// // 1. Use a string literal
+/// let listener = UdStreamListener::bind("/tmp/example1.sock")?;
+/// // If we're on Linux, we can also use the abstract socket namespace which exists separately from
+/// // the filesystem thanks to the special @ sign syntax which works with all string types
+/// #[cfg(target_os = "linux")]
+/// let listener_namespaced = UdStreamListener::bind("@namespaced_socket_1")?;
+///
+/// // 2. Use an owned string
+/// let listener = UdStreamListener::bind("/tmp/example2.sock".to_string())?;
+/// // Same story with the namespaced socket here
+/// #[cfg(target_os = "linux")]
+/// let listener_namespaced = UdStreamListener::bind("@namespaced_socket_2")?;
+///
+/// // 3. Use a path slice or an owned path
+/// let listener_by_path = UdStreamListener::bind(Path::new("/tmp/exmaple3a.sock"))?;
+/// let listener_by_pathbuf = UdStreamListener::bind(PathBuf::from("/tmp/example3b.sock"))?;
+/// // The @ syntax doesn't work with Path and PathBuf, since those are explicitly paths at the type
+/// // level, rather than strings with contextual meaning. Using AsRef to convert an &str slice or
+/// // an &OsStr slice into a &Path slice is the recommended way to disable the @ syntax.
+///
+/// // 4. Use manual creation
+/// let cstring = CString::new("/tmp/example4a.sock".to_string().into_bytes())?;
+/// let path_to_socket = UdSocketPath::File(Cow::Owned(cstring));
+/// let listener = UdStreamListener::bind(path_to_socket);
+///
+/// let cstr = CStr::from_bytes_with_nul("/tmp/example4b.sock\0".as_bytes())?;
+/// let path_to_socket = UdSocketPath::File(Cow::Borrowed(cstr));
+/// let listener = UdStreamListener::bind(path_to_socket);
+/// # }
+/// # Ok(()) }
+/// ```
+///
+/// [`UdSocketPath`]: enum.UdSocketPath.html " "
+/// [`io::Error`]: https://doc.rust-lang.org/std/io/struct.Error.html " "
+/// [`TryInto`]: https://doc.rust-lang.org/std/convert/trait.TryInto.html " "
+/// [`AsRef`]: https://doc.rust-lang.org/std/convert/trait.AsRef.html " "
+/// [namespaced socket paths]: struct.UdSocketPath.html#namespaced " "
+/// [`Path`]: https://doc.rust-lang.org/std/path/struct.Path.html " "
+/// [`PathBuf`]: https://doc.rust-lang.org/std/path/struct.PathBuf.html " "
+/// [`OsStr`]: https://doc.rust-lang.org/std/ffi/struct.OsStr.html " "
+/// [`CStr`]: https://doc.rust-lang.org/std/ffi/struct.CStr.html " "
+/// [`CString`]: https://doc.rust-lang.org/std/ffi/struct.CString.html " "
+/// [`Cow`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html " "
+/// [`str`]: https://doc.rust-lang.org/stable/std/primitive.str.html
+pub trait ToUdSocketPath<'a> {
+    /// Performs the conversion from `self` to a Unix domain socket path.
+    fn to_socket_path(self) -> io::Result<UdSocketPath<'a>>;
+}
+impl<'a> ToUdSocketPath<'a> for UdSocketPath<'a> {
+    /// Accepts explicit `UdSocketPath`s in the `bind` constructor.
+    #[inline(always)]
+    fn to_socket_path(self) -> io::Result<UdSocketPath<'a>> { Ok(self) }
+}
+impl<'a> ToUdSocketPath<'a> for &'a CStr {
+    /// Converts a borrowed [`CStr`] to a borrowed `UdSocketPath` with the same lifetime. On platforms which don't support [namespaced socket paths], the variant is always [`File`]; on Linux, which supports namespaced sockets, an extra check for the `@` character is performed. See the trait-level documentation for more.
+    ///
+    /// [`CStr`]: https://doc.rust-lang.org/std/ffi/struct.CStr.html " "
+    /// [`File`]: enum.UdSocketPath.html#file " "
+    /// [namespaced socket paths]: enum.UdSocketPath.html#namespaced " "
+    #[inline]
+    fn to_socket_path(self) -> io::Result<UdSocketPath<'a>> {
+        // 0x40 is the ASCII code for @, and since UTF-8 is ASCII-compatible, it would work too
+        #[cfg(target_os = "linux")]
+        if self.to_bytes().first() == Some(&0x40) {
+            let without_at_sign = &self.to_bytes_with_nul()[1..];
+            let without_at_sign = unsafe {
+                // SAFETY: it's safe to assume that the second byte comes before the nul
+                // terminator or is that nul terminator itself if the first one is an @ sign
+                CStr::from_bytes_with_nul_unchecked(without_at_sign)
+            };
+            // Use early return to simplify the conditional inclusion for the @ syntax check.
+            return Ok(UdSocketPath::Namespaced(
+                Cow::Borrowed(without_at_sign),
+            ));
+        }
+        Ok(UdSocketPath::File(
+            Cow::Borrowed(self),
+        ))
+
+    }
+}
+impl ToUdSocketPath<'static> for CString {
+    /// Converts an owned [`CString`] to a borrowed `UdSocketPath` with the same lifetime. On platforms which don't support [namespaced socket paths], the variant is always [`File`]; on Linux, which supports namespaced sockets, an extra check for the `@` character is performed. See the trait-level documentation for more.
+    ///
+    /// [`CString`]: https://doc.rust-lang.org/std/ffi/struct.CString.html " "
+    /// [`File`]: enum.UdSocketPath.html#file " "
+    /// [namespaced socket paths]: enum.UdSocketPath.html#namespaced " "
+    #[inline]
+    fn to_socket_path(self) -> io::Result<UdSocketPath<'static>> {
+        #[cfg(target_os = "linux")]
+        if self.as_bytes().first() == Some(&0x40) {
+            let without_at_sign = {
+                let mut without_at_sign = self.into_bytes();
+                without_at_sign.remove(0);
+                unsafe {
+                    // SAFETY: see CStr impl for why this is safe in both impls
+                    CString::from_vec_unchecked(without_at_sign)
+                }
+            };
+            // As in the CStr impl, we're using an early return to simplify conditional compilation
+            return Ok(UdSocketPath::Namespaced(
+                Cow::Owned(without_at_sign),
+            ));
+        }
+        Ok(UdSocketPath::File(
+            Cow::Owned(self),
+        ))
+    }
+}
+impl<'a> ToUdSocketPath<'a> for &'a OsStr {
+    /// Converts a borrowed [`OsStr`] to a borrowed `UdSocketPath` with the same lifetime. On platforms which don't support [namespaced socket paths], the variant is always [`File`]; on Linux, which supports namespaced sockets, an extra check for the `@` character is performed. See the trait-level documentation for more.
+    ///
+    /// If the provided string is not nul-terminated, a nul terminator is automatically added by copying the string into owned storage and adding a nul byte on its end.
+    ///
+    /// [`OsStr`]: https://doc.rust-lang.org/std/ffi/struct.OsStr.html " "
+    /// [`File`]: enum.UdSocketPath.html#file " "
+    /// [namespaced socket paths]: enum.UdSocketPath.html#namespaced " "
+    #[inline]
+    fn to_socket_path(self) -> io::Result<UdSocketPath<'a>> {
+        #[cfg(target_os = "linux")]
+        if self.as_bytes().first() == Some(&0x40) {
+            if self.as_bytes().last() != Some(&0) {
+                let mut owned = self.to_owned().into_vec();
+                owned.remove(0);
+                return Ok(UdSocketPath::Namespaced(
+                    Cow::Owned(CString::new(owned)?),
+                ));
+            } else {
+                let without_at_sign = self.as_bytes().split_at(1).1;
+                let cstr = CStr::from_bytes_with_nul(without_at_sign)
+                    .map_err(|x| io::Error::new(io::ErrorKind::InvalidInput, x))?;
+                return Ok(UdSocketPath::Namespaced(
+                    Cow::Borrowed(cstr),
+                ));
+            }
+        }
+        if self.as_bytes().last() != Some(&0) {
+            Ok(UdSocketPath::File(
+                Cow::Owned(CString::new(self.to_owned().into_vec())?),
+            ))
+        } else {
+            let cstr = CStr::from_bytes_with_nul(self.as_bytes())
+                .map_err(|x| io::Error::new(io::ErrorKind::InvalidInput, x))?;
+            Ok(UdSocketPath::File(
+                Cow::Borrowed(cstr),
+            ))
+        }
+    }
+}
+impl ToUdSocketPath<'static> for OsString {
+    /// Converts a borrowed [`OsString`] to an owned `UdSocketPath`. On platforms which don't support [namespaced socket paths], the variant is always [`File`]; on Linux, which supports namespaced sockets, an extra check for the `@` character is performed. See the trait-level documentation for more.
+    ///
+    /// If the provided string is not nul-terminated, a nul terminator is automatically added by copying the string into owned storage and adding a nul byte on its end.
+    ///
+    /// [`OsString`]: https://doc.rust-lang.org/std/ffi/struct.OsString.html " "
+    /// [`File`]: enum.UdSocketPath.html#file " "
+    /// [namespaced socket paths]: enum.UdSocketPath.html#namespaced " "
+    #[inline]
+    fn to_socket_path(self) -> io::Result<UdSocketPath<'static>> {
+        #[cfg(target_os = "linux")]
+        if self.as_os_str().as_bytes().first() == Some(&0x40) {
+            let mut without_at_sign = self.into_vec();
+            without_at_sign.remove(0);
+            return Ok(UdSocketPath::Namespaced(
+                Cow::Owned(CString::new(without_at_sign)?)
+            ));
+        }
+        Ok(UdSocketPath::File(
+            Cow::Owned(CString::new(self.into_vec())?)
+        ))
+    }
+}
+impl<'a> ToUdSocketPath<'a> for &'a Path {
+    /// Converts a borrowed [`Path`] to a borrowed [`UdSocketPath::File`] with the same lifetime.
+    ///
+    /// If the provided string is not nul-terminated, a nul terminator is automatically added by copying the string into owned storage and adding a nul byte on its end.
+    ///
+    /// [`Path`]: https://doc.rust-lang.org/std/path/struct.Path.html " "
+    /// [`UdSocketPath::File`]: struct.UdSocketPath.html#file " "
+    #[inline]
+    fn to_socket_path(self) -> io::Result<UdSocketPath<'a>> {
+        if self.as_os_str().as_bytes().last() != Some(&0) {
+            let osstring = self.to_owned().into_os_string().into_vec();
+            let cstring = CString::new(osstring)?;
+            Ok(UdSocketPath::File( Cow::Owned(cstring) ))
+        } else {
+            let cstr = CStr::from_bytes_with_nul(self.as_os_str().as_bytes())
+                    .map_err(|x| io::Error::new(io::ErrorKind::InvalidInput, x))?;
+            Ok(UdSocketPath::File( Cow::Borrowed(cstr) ))
+        }
+    }
+}
+impl ToUdSocketPath<'static> for PathBuf {
+    /// Converts an owned [`PathBuf`] to an owned [`UdSocketPath::File`].
+    ///
+    /// If the provided string is not nul-terminated, a nul terminator is automatically added by copying the string into owned storage and adding a nul byte on its end.
+    ///
+    /// [`PathBuf`]: https://doc.rust-lang.org/std/path/struct.PathBuf.html " "
+    /// [`UdSocketPath::File`]: struct.UdSocketPath.html#file " "
+    #[inline]
+    fn to_socket_path(self) -> io::Result<UdSocketPath<'static>> {
+        let cstring = CString::new(
+            self.into_os_string().into_vec(),
+        )?;
+        Ok(UdSocketPath::File( Cow::Owned(cstring) ))
+    }
+}
+impl<'a> ToUdSocketPath<'a> for &'a str {
+    /// Converts a borrowed [`str`] to a borrowed `UdSocketPath` with the same lifetime. On platforms which don't support [namespaced socket paths], the variant is always [`File`]; on Linux, which supports namespaced sockets, an extra check for the `@` character is performed. See the trait-level documentation for more.
+    ///
+    /// If the provided string is not nul-terminated, a nul terminator is automatically added by copying the string into owned storage and adding a nul byte on its end. This is done to support normal string literals, since adding `\0` at the end of every single socket name string is tedious and unappealing.
+    ///
+    /// [`str`]: https://doc.rust-lang.org/std/primitive.str.html " "
+    /// [`File`]: enum.UdSocketPath.html#file " "
+    /// [namespaced socket paths]: enum.UdSocketPath.html#namespaced " "
+    #[inline]
+    fn to_socket_path(self) -> io::Result<UdSocketPath<'a>> {
+        // Use chars().next() instead of raw indexing to account for UTF-8 with BOM
+        #[cfg(target_os = "linux")]
+        if self.starts_with('@') {
+            if !self.ends_with('\0') {
+                let mut owned = self.to_owned();
+                owned.remove(0);
+                return Ok(UdSocketPath::Namespaced(
+                    Cow::Owned(CString::new(owned)?),
+                ));
+            } else {
+                let without_at_sign = self.split_at(1).1;
+                let cstr = CStr::from_bytes_with_nul(without_at_sign.as_bytes())
+                    .map_err(|x| io::Error::new(io::ErrorKind::InvalidInput, x))?;
+                return Ok(UdSocketPath::Namespaced(
+                    Cow::Borrowed(cstr),
+                ));
+            }
+        }
+        if !self.ends_with('\0') {
+            Ok(UdSocketPath::File(
+                Cow::Owned(CString::new(self.to_owned())?),
+            ))
+        } else {
+            let cstr = CStr::from_bytes_with_nul(self.as_bytes())
+                .map_err(|x| io::Error::new(io::ErrorKind::InvalidInput, x))?;
+            Ok(UdSocketPath::File(
+                Cow::Borrowed(cstr),
+            ))
+        }
+    }
+}
+impl ToUdSocketPath<'static> for String {
+    /// Converts an owned [`String`] to an owned `UdSocketPath`. On platforms which don't support [namespaced socket paths], the variant is always [`File`]; on Linux, which supports namespaced sockets, an extra check for the `@` character is performed. See the trait-level documentation for more.
+    ///
+    /// If the provided string is not nul-terminated, a nul terminator is automatically added by copying the string into owned storage and adding a nul byte on its end.
+    ///
+    /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html " "
+    /// [`File`]: enum.UdSocketPath.html#file " "
+    /// [namespaced socket paths]: enum.UdSocketPath.html#namespaced " "
+    #[inline]
+    fn to_socket_path(self) -> io::Result<UdSocketPath<'static>> {
+        #[cfg(target_os = "linux")]
+        if self.starts_with('@') {
+            let mut without_at_sign = self;
+            without_at_sign.remove(0);
+            return Ok(UdSocketPath::Namespaced(
+                Cow::Owned(CString::new(without_at_sign.into_bytes())?)
+            ));
+        }
+        Ok(UdSocketPath::File(Cow::Owned(
+            CString::new(self.into_bytes())?)
+        ))
+    }
+}
+
+/// Ancillary data to be sent through a Unix domain socket or read from an input buffer.
+///
+/// Ancillary data gives unique possibilities to Unix domain sockets which no other POSIX API has: passing file descriptors between two processes which do not have a parent-child relationship. It also can be used to transfer credentials of a process reliably.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AncillaryData<'a> {
+    /// One or more file descriptors to be sent.
+    FileDescriptors(Cow<'a, [c_int]>),
+    /// Credentials to be sent. The specified values are checked by the system when sent for all users except for the superuser — for senders, this means that the correct values need to be filled out, otherwise, an error is returned; for receivers, this means that the credentials are to be trusted for authentification purposes. For convenience, the [`credentials`] function provides a value which is known to be valid when sent.
+    ///
+    /// [`credentials`]: #method.credentials " "
+    Credentials {
+        /// The process identificator (PID) for the process.
+        pid: pid_t,
+        /// The user identificator (UID) of the user who started the process.
+        uid: uid_t,
+        /// The group identificator (GID) of the user who started the process.
+        gid: gid_t,
+    },
+}
+impl<'a> AncillaryData<'a> {
+    /// The size of a single `AncillaryData::Credentials` element when packed into the Unix ancillary data format. Useful for allocating a buffer when you expect to receive credentials.
+    pub const ENCODED_SIZE_OF_CREDENTIALS: usize = Self::_ENCODED_SIZE_OF_CREDENTIALS;
+    #[cfg(unix)]
+    const _ENCODED_SIZE_OF_CREDENTIALS: usize = mem::size_of::<cmsghdr>() + mem::size_of::<ucred>();
+    #[cfg(not(unix))]
+    const _ENCODED_SIZE_OF_CREDENTIALS: usize = 0;
+
+    /// Calculates the size of an `AncillaryData::FileDescriptors` element with the specified amount of file descriptors when packed into the Unix ancillary data format. Useful for allocating a buffer when you expect to receive a specific amount of file descriptors.
+    #[inline(always)]
+    pub const fn encoded_size_of_file_descriptors(num_descriptors: usize) -> usize {
+        mem::size_of::<cmsghdr>() + num_descriptors * 4
+    }
+
+    /// Inexpensievly clones `self` by borrowing the `FileDescriptors` variant or copying the `Credentials` variant.
+    #[inline]
+    pub fn clone_ref(&'a self) -> Self {
+        match *self {
+            Self::FileDescriptors(ref fds) => {
+                Self::FileDescriptors(Cow::Borrowed(fds))
+            },
+            Self::Credentials {
+                pid, uid, gid,
+            } => Self::Credentials {
+                pid, uid, gid,
+            },
+        }
+    }
+
+    /// Returns the size of an ancillary data element when packed into the Unix ancillary data format.
+    #[inline]
+    pub fn encoded_size(&self) -> usize {
+        match self {
+            Self::FileDescriptors(fds) => Self::encoded_size_of_file_descriptors(fds.len()),
+            Self::Credentials {..} => Self::ENCODED_SIZE_OF_CREDENTIALS,
+        }
+    }
+
+    /// Encodes the ancillary data into `EncodedAncillaryData` which is ready to be sent via a Unix domain socket.
+    fn encode(op: impl IntoIterator<Item = Self>) -> EncodedAncillaryData<'static> {
+        let items = op.into_iter();
+        let mut buffer = Vec::with_capacity( {
+            let size_hint = items.size_hint();
+            size_hint.1.unwrap_or(size_hint.0)
+        // If we assume that all ancillary data elements are credentials, we're more than fine.
+        } * Self::ENCODED_SIZE_OF_CREDENTIALS);
+        for i in items {
+            let mut cmsg_len = mem::size_of::<cmsghdr>();
+            let cmsg_level_bytes = SOL_SOCKET.to_ne_bytes();
+            let cmsg_type_bytes;
+
+            match i {
+                AncillaryData::FileDescriptors(fds) => {
+                    cmsg_type_bytes = SCM_RIGHTS.to_ne_bytes();
+                    cmsg_len += fds.len() * 4;
+                    // #[cfg(target_pointer_width = "64")]
+                    // this was here, I don't even remember why, but that
+                    // wouldn't compile on a 32-bit machine
+                    let cmsg_len_bytes = cmsg_len.to_ne_bytes();
+                    buffer.extend_from_slice(&cmsg_len_bytes);
+                    buffer.extend_from_slice(&cmsg_level_bytes);
+                    buffer.extend_from_slice(&cmsg_type_bytes);
+                    for i in fds.iter().copied() {
+                        let desc_bytes = i.to_ne_bytes();
+                        buffer.extend_from_slice(&desc_bytes);
+                    }
+                },
+                AncillaryData::Credentials {pid, uid, gid} => {
+                    cmsg_type_bytes = SCM_RIGHTS.to_ne_bytes();
+                    cmsg_len += mem::size_of::<ucred>();
+                    // #[cfg(target_pointer_width = "64")]
+                    let cmsg_len_bytes = cmsg_len.to_ne_bytes();
+                    let pid_bytes = pid.to_ne_bytes();
+                    let uid_bytes = uid.to_ne_bytes();
+                    let gid_bytes = gid.to_ne_bytes();
+                    buffer.extend_from_slice(&cmsg_len_bytes);
+                    buffer.extend_from_slice(&cmsg_level_bytes);
+                    buffer.extend_from_slice(&cmsg_type_bytes);
+                    buffer.extend_from_slice(&pid_bytes);
+                    buffer.extend_from_slice(&uid_bytes);
+                    buffer.extend_from_slice(&gid_bytes);
+                },
+            }
+        }
+        EncodedAncillaryData(Cow::Owned(buffer))
+    }
+}
+impl AncillaryData<'static> {
+    /// Fetches the credentials of the process from the system and returns a value which can be safely sent to another process without the system complaining about an unauthorized attempt to impersonate another process/user/group.
+    ///
+    /// If you want to send credentials to another process, this is usually the function you need to obtain the desired ancillary payload.
+    #[inline]
+    pub fn credentials() -> Self {
+        Self::Credentials {
+            pid: unsafe {libc::getpid()},
+            uid: unsafe {libc::getuid()},
+            gid: unsafe {libc::getgid()},
+        }
+    }
+}
+
+/// A buffer used for sending ancillary data into Unix domain sockets.
+#[repr(transparent)]
+#[derive(Clone, Debug)]
+struct EncodedAncillaryData<'a> (pub Cow<'a, [u8]>);
+impl<'a> From<&'a [u8]> for EncodedAncillaryData<'a> {
+    #[inline(always)]
+    fn from(op: &'a [u8]) -> Self { Self(Cow::Borrowed(op)) }
+}
+impl From<Vec<u8>> for EncodedAncillaryData<'static> {
+    #[inline(always)]
+    fn from(op: Vec<u8>) -> Self { Self(Cow::Owned(op)) }
+}
+impl<'b> FromIterator<AncillaryData<'b>> for EncodedAncillaryData<'static> {
+    #[inline(always)]
+    fn from_iter<I: IntoIterator<Item = AncillaryData<'b>>>(iter: I) -> Self {
+        AncillaryData::encode(iter)
+    }
+}
+impl<'b> From<Vec<AncillaryData<'b>>> for EncodedAncillaryData<'static> {
+    #[inline(always)]
+    fn from(op: Vec<AncillaryData<'b>>) -> Self { Self::from_iter(op) }
+}
+impl<'b: 'c, 'c> From<&'c [AncillaryData<'b>]> for EncodedAncillaryData<'static> {
+    #[inline(always)]
+    fn from(op: &'c [AncillaryData<'b>]) -> Self {
+        op.iter().map(AncillaryData::clone_ref).collect::<Self>()
+    }
+}
+impl<'a> AsRef<[u8]> for EncodedAncillaryData<'a> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] { &self.0 }
+}
+
+/// A buffer used for receiving ancillary data from Unix domain sockets.
+///
+/// The actual ancillary data can be obtained using the [`decode`] method.
+///
+/// # Example
+/// See [`UdStream`] or [`UdStreamListener`] for an example of receiving ancillary data.
+///
+/// [`decode`]: #method.decode " "
+/// [`UdStream`]: struct.UdStream.html#examples " "
+/// [`UdStreamListener`]: struct.UdStreamListener.html#examples " "
+#[derive(Debug)]
+pub enum AncillaryDataBuf<'a> {
+    /// The buffer's storage is borrowed.
+    Borrowed(&'a mut [u8]),
+    /// The buffer's storage is owned by the buffer itself.
+    Owned(Vec<u8>),
+}
+impl<'a> AncillaryDataBuf<'a> {
+    /// Creates an owned ancillary data buffer with the specified capacity.
+    #[inline(always)]
+    pub fn owned_with_capacity(capacity: usize) -> Self {
+        Self::Owned(Vec::with_capacity(capacity))
+    }
+    /// Creates a decoder which decodes the ancillary data buffer into a friendly representation of its contents.
+    ///
+    /// All invalid ancillary data blocks are skipped — if there was garbage data in the buffer to begin with, the resulting buffer will either be empty or contain invalid credentials/file descriptors. This should normally never happen if the data is actually received from a Unix domain socket.
+    #[inline(always)]
+    pub fn decode(&'a self) -> AncillaryDataDecoder<'a> {
+        AncillaryDataDecoder {
+            buffer: self.as_ref(),
+            i: 0,
+        }
+    }
+}
+impl<'a> From<&'a mut [u8]> for AncillaryDataBuf<'a> {
+    #[inline(always)]
+    fn from(op: &'a mut [u8]) -> Self { Self::Borrowed(op) }
+}
+impl From<Vec<u8>> for AncillaryDataBuf<'static> {
+    #[inline(always)]
+    fn from(op: Vec<u8>) -> Self { Self::Owned(op) }
+}
+impl<'a> From<&'a mut AncillaryDataBuf<'a>> for AncillaryDataBuf<'a> {
+    #[inline]
+    fn from(op: &'a mut AncillaryDataBuf<'a>) -> Self {
+        match op {
+            Self::Borrowed(slice) => Self::Borrowed(slice),
+            Self::Owned(vec) => Self::Borrowed(vec),
+        }
+    }
+}
+impl<'a> AsRef<[u8]> for AncillaryDataBuf<'a> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::Borrowed(slice) => slice,
+            Self::Owned(vec) => vec,
+        }
+    }
+}
+impl<'a> AsMut<[u8]> for AncillaryDataBuf<'a> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [u8] {
+        match self {
+            Self::Borrowed(slice) => slice,
+            Self::Owned(vec) => vec,
+        }
+    }
+}
+
+/// An iterator which decodes ancillary data from an ancillary data buffer.
+///
+/// This iterator is created by the [`decode`] method on [`AncillaryDataBuf`] — see its documentation for more.
+///
+/// [`AncillaryDataBuf`]: struct.AncillaryDataBuf.html " "
+/// [`decode`]: struct.AncillaryDataBuf.html#method.decode " "
+#[derive(Clone, Debug)]
+pub struct AncillaryDataDecoder<'a> {
+    buffer: &'a [u8],
+    i: usize,
+}
+impl<'a> From<&'a AncillaryDataBuf<'a>> for AncillaryDataDecoder<'a> {
+    #[inline(always)]
+    fn from(buffer: &'a AncillaryDataBuf<'a>) -> Self {
+        buffer.decode()
+    }
+}
+impl<'a> Iterator for AncillaryDataDecoder<'a> {
+    type Item = AncillaryData<'static>;
+    fn next(&mut self) -> Option<Self::Item> {
+        #[inline(always)]
+        fn u32_from_slice(bytes: &[u8]) -> u32 {
+            u32::from_ne_bytes([
+                bytes[0], bytes[1],
+                bytes[2], bytes[3],
+            ])
+        }
+        #[inline(always)]
+        fn u64_from_slice(bytes: &[u8]) -> u64 {
+            u64::from_ne_bytes([
+                bytes[0], bytes[1],
+                bytes[2], bytes[3],
+                bytes[4], bytes[5],
+                bytes[6], bytes[7],
+            ])
+        }
+        let bytes = self.buffer;
+        if bytes.is_empty() {
+            return None;
+        }
+        let end = bytes.len() - 1;
+
+        if let Some(diff) = bytes.len().checked_sub(self.i) {
+            if diff == 0 { self.i = end; return None; }
+        } else { self.i = end; return None; }
+
+        // The first field is the length, which is a size_t
+        #[cfg(target_pointer_width = "64")]
+        let element_size = {
+            if bytes.len() - self.i < 8 { self.i = end; return None; }
+            u64_from_slice(
+                &bytes[self.i .. self.i + 8],
+            ) as usize
+        };
+        #[cfg(target_pointer_width = "32")]
+        let element_size = {
+            if bytes.len() - self.i < 4 { self.i = end; return None; }
+            u32_from_slice(
+                &bytes[self.i .. self.i + 4],
+            ) as usize
+        };
+        // The cmsg_level field is always SOL_SOCKET — we don't need it, let's get the
+        // cmsg_type field right away by first getting the offset at which it's
+        // located:
+        #[cfg(target_pointer_width = "64")]
+        let type_offset: usize = 8 + 4; // 8 for cmsg_size, 4 for cmsg_level
+        #[cfg(target_pointer_width = "32")]
+        let type_offset: usize = 4 + 4; // 4 for cmsg_size, 4 for cmsg_level
+
+        // Now let's get the type itself:
+        let element_type = u32_from_slice(
+            &bytes[self.i + type_offset ..= self.i + type_offset + 4],
+        );
+        // The size of cmsg_size, cmsg_level and cmsg_type together
+        let element_offset = type_offset + 4;
+
+        // Update the counter before returning.
+        self.i += element_offset // cmsg_size, cmsg_level and cmsg_type
+                + element_size;  // data size
+
+        match element_type as i32 {
+            SCM_RIGHTS => {
+                // We're reading one or multiple descriptors from the ancillary data payload.
+                // All descriptors are 4 bytes in size — leftover bytes are discarded thanks
+                // to integer division rules
+                let amount_of_descriptors = element_size / 4;
+                let mut descriptors = Vec::<c_int>::with_capacity(amount_of_descriptors);
+                let mut descriptor_offset = element_offset;
+                for _ in 0..amount_of_descriptors {
+                    descriptors.push(
+                        u32_from_slice(&bytes[descriptor_offset .. descriptor_offset + 4]) as i32
+                    );
+                    descriptor_offset += 4;
+                }
+                Some(
+                    AncillaryData::FileDescriptors(
+                        Cow::Owned(descriptors)
+                    )
+                )
+            },
+            SCM_CREDENTIALS => {
+                // We're reading a single ucred structure from the ancillary data payload.
+                let pid_offset = element_offset;
+                let pid: pid_t = u32_from_slice(&bytes[pid_offset .. pid_offset + 4]) as pid_t;
+                let uid_offset = pid_offset + 4;
+                let uid: uid_t = u32_from_slice(&bytes[uid_offset .. uid_offset + 4]);
+                let gid_offset = uid_offset + 4;
+                let gid: gid_t = u32_from_slice(&bytes[gid_offset .. gid_offset + 4]);
+                Some(
+                    AncillaryData::Credentials {
+                        pid, uid, gid
+                    }
+                )
+            },
+            _ => self.next(), // Do nothing if we hit corrupted data.
+        }
+    }
+}
+impl FusedIterator for AncillaryDataDecoder<'_> {}
+
+/// An infinite iterator over incoming client connections of a [`UdStreamListener`].
+///
+/// This iterator is created by the [`incoming`] method on [`UdStreamListener`] — see its documentation for more.
+///
+/// [`UdStreamListener`]: struct.UdStreamListener.html " "
+/// [`incoming`]: struct.UdStreamListener.html#method.incoming " "
+pub struct Incoming<'a> {
+    listener: &'a UdStreamListener,
+}
+impl<'a> Iterator for Incoming<'a> {
+    type Item = io::Result<UdStream>;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.listener.accept())
+    }
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+impl FusedIterator for Incoming<'_> {}
+impl<'a> From<&'a UdStreamListener> for Incoming<'a> {
+    #[inline(always)]
+    fn from(listener: &'a UdStreamListener) -> Self {
+        Self {listener}
+    }
+}
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{rand_string, unnamed_pipe::{pipe, UnnamedPipeReader}};
+    use std::fs::File;
+
+    fn is_cloexec(fd: &impl AsRawFd) -> bool {
+        let flags = unsafe {libc::fcntl(fd.as_raw_fd(), libc::F_GETFD)};
+        assert_ne!(flags, -1);
+        flags & libc::FD_CLOEXEC != 0
+    }
+
+    #[test]
+    fn accept_timeout_race() {
+        let path = format!("/tmp/{}.sock", rand_string(10));
+        let listener = Arc::new(UdStreamListener::bind(path.clone()).unwrap());
+        listener.set_nonblocking(true).unwrap();
+        let _client = UdStream::connect(path).unwrap();
+        // Both threads wake up for the same pending connection, and the one which loses the race
+        // for it must keep waiting until the deadline rather than fail with WouldBlock.
+        let threads = (0..2).map(|_| {
+            let listener = Arc::clone(&listener);
+            std::thread::spawn(move || listener.accept_timeout(Duration::from_millis(200)))
+        }).collect::<Vec<_>>();
+        let results = threads.into_iter().map(|thread| thread.join().unwrap()).collect::<Vec<_>>();
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+        assert!(results.iter().any(|result| {
+            result.as_ref().map_or_else(|error| error.kind() == io::ErrorKind::TimedOut, |_| false)
+        }));
+        assert!(get_nonblocking(listener.as_raw_fd()).unwrap());
+    }
+    #[test]
+    fn fd_passing_stream() {
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = UdStreamListener::bind_reclaiming(socket_name.as_str()).unwrap();
+        let client = UdStream::connect(socket_name.as_str()).unwrap();
+        let server = listener.accept().unwrap();
+
+        let (mut writer, reader) = pipe().unwrap();
+        client.send_fd(&reader).unwrap();
+        drop(reader);
+        let mut reader: UnnamedPipeReader = server.recv_fd().unwrap();
+        assert!(is_cloexec(&reader));
+        writer.write_all(b"through the socket").unwrap();
+        drop(writer);
+        let mut received = String::new();
+        reader.read_to_string(&mut received).unwrap();
+        assert_eq!(received, "through the socket");
+
+        // Regular data around the file descriptors must stay intact
+        let file = File::open("/dev/null").unwrap();
+        client.send(b"before").unwrap();
+        client.send_with_fds(b"!", &[file.as_fd(), file.as_fd()]).unwrap();
+        client.send_fds(&[]).unwrap();
+        let mut buf = [0; 6];
+        server.recv(&mut buf).unwrap();
+        assert_eq!(&buf, b"before");
+        let (bytes_read, fds) = server.recv_with_fds(&mut buf).unwrap();
+        assert_eq!((&buf[..bytes_read], fds.len()), (&b"!"[..], 2));
+        assert_eq!(
+            server.recv_fd::<File>().unwrap_err().kind(),
+            io::ErrorKind::InvalidData,
+        );
+
+        drop(client);
+        assert_eq!(server.recv_fds().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+    #[test]
+    fn datagram_pair() {
+        let (left, right) = UdSocket::pair().unwrap();
+        left.send(b"first").unwrap();
+        left.send(b"second").unwrap();
+        let mut buf = [0; 16];
+        assert_eq!(right.recv(&mut buf).unwrap(), (5, false));
+        assert_eq!(right.recv(&mut buf).unwrap(), (6, false));
+        assert_eq!(&buf[..6], b"second");
+
+        let (stream, _) = UdStream::pair().unwrap();
+        right.send_fd(&stream).unwrap();
+        let _: UdStream = left.recv_fd().unwrap();
+    }
+    #[test]
+    fn fd_passing_datagram() {
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let server = UdSocket::bind(socket_name.as_str()).unwrap();
+        let client = UdSocket::connect(socket_name.as_str()).unwrap();
+        let _ = std::fs::remove_file(&socket_name);
+
+        let file = File::open("/dev/null").unwrap();
+        client.send_fds(&[file.as_fd(); 3]).unwrap();
+        let fds = server.recv_fds().unwrap();
+        assert_eq!(fds.len(), 3);
+        assert!(fds.iter().all(is_cloexec));
+    }
+    #[test]
+    fn datagram_request_reply() {
+        let server_name = format!("/tmp/{}.sock", rand_string(10));
+        let client_name = format!("/tmp/{}.sock", rand_string(10));
+        let server = UdSocket::bind(server_name.as_str()).unwrap();
+        let client = UdSocket::bind_and_connect(client_name.as_str(), server_name.as_str()).unwrap();
+
+        client.send(b"request").unwrap();
+        let mut buf = [0; 16];
+        let mut addr_buf = UdSocketPath::buffer();
+        assert_eq!(server.recv_from(&mut buf, &mut addr_buf).unwrap(), (7, false));
+        assert_eq!(addr_buf.as_cstr().to_bytes(), client_name.as_bytes());
+        server.send_to(b"reply", addr_buf).unwrap();
+        assert_eq!(client.recv(&mut buf).unwrap(), (5, false));
+        assert_eq!(&buf[..5], b"reply");
+
+        client.disconnect().unwrap();
+        assert!(client.send(b"nowhere").is_err());
+        client.send_to(b"again", server_name.as_str()).unwrap();
+        assert_eq!(server.recv(&mut buf).unwrap(), (5, false));
+
+        let _ = std::fs::remove_file(&server_name);
+        let _ = std::fs::remove_file(&client_name);
+    }
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn socket_options() {
+        let (stream, _other) = UdStream::pair().unwrap();
+        stream.set_send_buffer_size(16384).unwrap();
+        assert!(stream.send_buffer_size().unwrap() >= 16384);
+        stream.set_recv_low_water_mark(4).unwrap();
+        assert_eq!(stream.recv_low_water_mark().unwrap(), 4);
+        assert!(stream.pass_credentials().unwrap());
+        stream.set_pass_credentials(false).unwrap();
+        assert!(!stream.pass_credentials().unwrap());
+        assert!(stream.take_error().unwrap().is_none());
+        assert!(stream.set_send_buffer_size(usize::MAX).is_err());
+
+        let socket = UdSocket::autobind().unwrap();
+        socket.set_recv_buffer_size(16384).unwrap();
+        assert!(socket.recv_buffer_size().unwrap() >= 16384);
+        let timeout = Duration::from_millis(10);
+        socket.set_read_timeout(Some(timeout)).unwrap();
+        // Rounded up to the resolution of the kernel's timer
+        assert!(socket.read_timeout().unwrap().unwrap() >= timeout);
+        let error = socket.recv(&mut [0; 4]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(socket.peek_offset().unwrap(), None);
+        socket.set_peek_offset(Some(2)).unwrap();
+        assert_eq!(socket.peek_offset().unwrap(), Some(2));
+
+        let socket_name = format!("@{}", rand_string(10));
+        let listener = UdStreamListener::bind(socket_name.as_str()).unwrap();
+        listener.set_read_timeout(Some(timeout)).unwrap();
+        assert_eq!(listener.accept().unwrap_err().kind(), io::ErrorKind::TimedOut);
+    }
+    #[test]
+    fn batches() {
+        let server_name = format!("/tmp/{}.sock", rand_string(10));
+        let client_name = format!("/tmp/{}.sock", rand_string(10));
+        let server = UdSocket::bind(server_name.as_str()).unwrap();
+        let client = UdSocket::bind_and_connect(client_name.as_str(), server_name.as_str()).unwrap();
+
+        let mut send_batch = SendBatch::new();
+        send_batch.push(b"one");
+        send_batch.push_to(b"two", server_name.as_str()).unwrap();
+        send_batch.push(b"three, which is too long");
+        assert_eq!(send_batch.len(), 3);
+        assert_eq!(client.send_batch(&mut send_batch).unwrap(), 3);
+        assert!(send_batch.is_empty());
+        assert_eq!(client.send_batch(&mut send_batch).unwrap(), 0);
+
+        // Fewer slots than datagrams, so the rest must wait for the next call
+        let mut recv_batch = RecvBatch::new(2, 8);
+        assert_eq!(server.recv_batch(&mut recv_batch).unwrap(), 2);
+        let received = recv_batch.iter().map(|d| (d.data(), d.is_truncated())).collect::<Vec<_>>();
+        assert_eq!(received, [(&b"one"[..], false), (&b"two"[..], false)]);
+        let client_path = UdSocketPath::File(Cow::Owned(CString::new(client_name.clone()).unwrap()));
+        assert_eq!(recv_batch.get(1).unwrap().addr(), client_path);
+        // The batch has no room for ancillary data, so there's nothing to decode.
+        assert!(recv_batch.iter().all(|d| d.ancillary().count() == 0));
+        assert_eq!(server.recv_batch(&mut recv_batch).unwrap(), 1);
+        let datagram = recv_batch.get(0).unwrap();
+        assert_eq!((datagram.data(), datagram.is_truncated()), (&b"three, w"[..], true));
+        assert!(recv_batch.get(1).is_none());
+
+        let _ = std::fs::remove_file(&server_name);
+        let _ = std::fs::remove_file(&client_name);
+    }
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn autobind() {
+        let server_name = UdSocketPath::NamespacedBytes(Cow::Owned(
+            format!("interprocess\0{}", rand_string(10)).into_bytes(),
+        ));
+        let server = UdSocket::bind(server_name.clone()).unwrap();
+        assert_eq!(server.local_addr().unwrap(), server_name);
+
+        let client = UdSocket::autobind().unwrap();
+        let client_name = client.local_addr().unwrap();
+        assert!(matches!(client_name, UdSocketPath::NamespacedBytes(..)));
+        client.send_to(b"request", server_name).unwrap();
+        let mut buf = [0; 16];
+        let mut addr_buf = UdSocketPath::buffer();
+        server.recv_from(&mut buf, &mut addr_buf).unwrap();
+        assert_eq!(addr_buf, client_name);
+        server.send_to(b"reply", addr_buf).unwrap();
+        assert_eq!(client.recv(&mut buf).unwrap(), (5, false));
+
+        let padded = UdSocket::bind(format!("@{}", rand_string(10)).as_str()).unwrap();
+        assert!(matches!(padded.local_addr().unwrap(), UdSocketPath::Namespaced(..)));
+    }
+    #[test]
+    fn addresses() {
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = UdStreamListener::bind_reclaiming(socket_name.as_str()).unwrap();
+        let client = UdStream::connect(socket_name.as_str()).unwrap();
+        let server = listener.accept().unwrap();
+        let path = UdSocketPath::File(Cow::Owned(CString::new(socket_name).unwrap()));
+        assert_eq!(listener.local_addr().unwrap(), path);
+        assert_eq!(server.local_addr().unwrap(), path);
+        assert_eq!(client.peer_addr().unwrap(), path);
+        assert_eq!(client.local_addr().unwrap(), UdSocketPath::Unnamed);
+        assert_eq!(server.peer_addr().unwrap(), UdSocketPath::Unnamed);
+
+        // Listeners bound in a staging directory know their final path even though the system doesn't.
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = UdStreamListenerOptions::new()
+            .mode(0o600)
+            .reclaim(true)
+            .create(socket_name.as_str())
+            .unwrap();
+        let path = UdSocketPath::File(Cow::Owned(CString::new(socket_name).unwrap()));
+        assert_eq!(listener.local_addr().unwrap(), path);
+
+        #[cfg(target_os = "linux")]
+        {
+            let socket_name = format!("@{}", rand_string(10));
+            let listener = UdStreamListener::bind(socket_name.as_str()).unwrap();
+            let client = UdStream::connect(socket_name.as_str()).unwrap();
+            let listener_addr = listener.local_addr().unwrap();
+            assert!(matches!(listener_addr, UdSocketPath::Namespaced(..)));
+            assert_eq!(client.peer_addr().unwrap(), listener_addr);
+
+            let socket = UdSocket::autobind().unwrap();
+            assert_eq!(socket.peer_addr().unwrap_err().kind(), io::ErrorKind::NotConnected);
+        }
+    }
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn long_paths() {
+        let base_dir = format!("/tmp/{}", rand_string(10));
+        let dir = format!("{}/{}/{}", base_dir, "d".repeat(60), "d".repeat(60));
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket_name = format!("{}/stream.sock", dir);
+        assert!(socket_name.len() > MAX_UDSOCKET_PATH_LEN);
+
+        let listener = UdStreamListener::bind_reclaiming(socket_name.as_str()).unwrap();
+        let path = UdSocketPath::File(Cow::Owned(CString::new(socket_name.clone()).unwrap()));
+        assert_eq!(listener.local_addr().unwrap(), path);
+        let mut client = UdStream::connect(socket_name.as_str()).unwrap();
+        let mut server = listener.accept().unwrap();
+        client.write_all(b"ping").unwrap();
+        let mut buf = [0; 4];
+        server.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"ping");
+
+        let datagram_name = format!("{}/datagram.sock", dir);
+        let receiver = UdSocket::bind(datagram_name.as_str()).unwrap();
+        let sender = UdSocket::connect(datagram_name.as_str()).unwrap();
+        sender.send(b"first").unwrap();
+        sender.send_to(b"second", datagram_name.as_str()).unwrap();
+        assert_eq!(receiver.recv(&mut buf).unwrap(), (4, true));
+        assert_eq!(receiver.recv(&mut buf).unwrap(), (4, true));
+
+        let file_name_too_long = format!("{}/{}", base_dir, "s".repeat(MAX_UDSOCKET_PATH_LEN));
+        let error = UdStreamListener::bind(file_name_too_long.as_str()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+        drop((listener, receiver));
+        std::fs::remove_dir_all(base_dir).unwrap();
+    }
+    #[test]
+    fn seqpacket() {
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = UdSeqpacketListener::bind_reclaiming(socket_name.as_str()).unwrap();
+        let mut client = UdSeqpacket::connect(socket_name.as_str()).unwrap();
+        let mut server = listener.accept().unwrap();
+        let error = listener.accept_timeout(Duration::from_millis(10)).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert!(!get_nonblocking(listener.as_raw_fd()).unwrap());
+        listener.inner.set_read_timeout(Some(Duration::from_millis(10))).unwrap();
+        assert_eq!(listener.accept().unwrap_err().kind(), io::ErrorKind::TimedOut);
+
+        client.write_msg(b"first").unwrap();
+        client.write_msg(b"second message").unwrap();
+        let mut buf = [0; 8];
+        assert_eq!(server.recv(&mut buf).unwrap(), (5, false));
+        assert_eq!(&buf[..5], b"first");
+        #[cfg(target_os = "linux")]
+        assert_eq!(server.read_msg(&mut buf).unwrap(), Err(b"second message".to_vec()));
+        #[cfg(not(target_os = "linux"))]
+        assert_eq!(server.recv(&mut buf).unwrap(), (8, true));
+
+        server.send(b"reply").unwrap();
+        assert_eq!(client.recv(&mut buf).unwrap(), (5, false));
+        drop(server);
+        assert_eq!(client.recv(&mut buf).unwrap(), (0, false));
+
+        let (left, right) = UdSeqpacket::pair().unwrap();
+        let file = File::open("/dev/null").unwrap();
+        let fds = [file.as_raw_fd()];
+        left.send_ancillary(b"fd", vec![AncillaryData::FileDescriptors(Cow::Borrowed(&fds))]).unwrap();
+        let mut abuf = AncillaryDataBuf::Owned(vec![0; 64]);
+        let (size, _, asize, _) = right.recv_ancillary(&mut buf, &mut abuf).unwrap();
+        assert_eq!(size, 2);
+        assert_ne!(asize, 0);
+    }
+}
@@ -0,0 +1,401 @@
+//! Typed accessors for the socket options of Ud-sockets, so that none of them have to be set with raw `setsockopt` calls.
+
+#[cfg(unix)]
+use libc::{c_int, SOL_SOCKET, SO_PASSCRED};
+use std::{
+    convert::TryFrom,
+    io,
+    mem,
+    time::Duration,
+};
+#[cfg(unix)]
+use std::os::unix::io::AsRawFd;
+use super::{
+    UdSocket,
+    UdStream,
+    UdStreamListener,
+    get_timeout,
+    set_timeout,
+};
+
+#[cfg(unix)]
+fn set_int_option(fd: c_int, option: c_int, value: c_int) -> io::Result<()> {
+    let success = unsafe {
+        libc::setsockopt(
+            fd,
+            SOL_SOCKET,
+            option,
+            &value as *const _ as *const _,
+            mem::size_of_val(&value) as u32,
+        ) != -1
+    };
+    if success {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+#[cfg(unix)]
+fn get_int_option(fd: c_int, option: c_int) -> io::Result<c_int> {
+    let mut value: c_int = 0;
+    let mut size = mem::size_of_val(&value) as u32;
+    let success = unsafe {
+        libc::getsockopt(
+            fd,
+            SOL_SOCKET,
+            option,
+            &mut value as *mut _ as *mut _,
+            &mut size as *mut _,
+        ) != -1
+    };
+    if success {
+        Ok(value)
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+#[cfg(unix)]
+fn set_size_option(fd: c_int, option: c_int, size: usize) -> io::Result<()> {
+    let size = c_int::try_from(size).map_err(|_| io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "size does not fit into a C int",
+    ))?;
+    set_int_option(fd, option, size)
+}
+#[cfg(unix)]
+fn get_size_option(fd: c_int, option: c_int) -> io::Result<usize> {
+    get_int_option(fd, option).map(|size| size.max(0) as usize)
+}
+#[cfg(target_os = "linux")]
+fn set_peek_offset(fd: c_int, offset: Option<usize>) -> io::Result<()> {
+    match offset {
+        Some(offset) => set_size_option(fd, libc::SO_PEEK_OFF, offset),
+        None => set_int_option(fd, libc::SO_PEEK_OFF, -1),
+    }
+}
+#[cfg(target_os = "linux")]
+fn get_peek_offset(fd: c_int) -> io::Result<Option<usize>> {
+    let offset = get_int_option(fd, libc::SO_PEEK_OFF)?;
+    Ok(if offset < 0 {None} else {Some(offset as usize)})
+}
+#[cfg(unix)]
+fn take_error(fd: c_int) -> io::Result<Option<io::Error>> {
+    let error = get_int_option(fd, libc::SO_ERROR)?;
+    Ok(if error == 0 {None} else {Some(io::Error::from_raw_os_error(error))})
+}
+
+impl UdStreamListener {
+    /// Enables or disables the `SO_PASSCRED` option on the listening socket, which makes the streams accepted from then on receive the credentials of the client with every message. See [`UdStreamListenerOptions::pass_credentials`] for the details; that option is enabled by default.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// [`UdStreamListenerOptions::pass_credentials`]: struct.UdStreamListenerOptions.html#structfield.pass_credentials " "
+    #[inline]
+    pub fn set_pass_credentials(&self, pass_credentials: bool) -> io::Result<()> {
+        set_int_option(self.as_raw_fd(), SO_PASSCRED, pass_credentials as c_int)
+    }
+    /// Returns whether the `SO_PASSCRED` option is enabled on the listening socket.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn pass_credentials(&self) -> io::Result<bool> {
+        get_int_option(self.as_raw_fd(), SO_PASSCRED).map(|x| x != 0)
+    }
+    /// Sets the timeout for [`accept`] and [`incoming`] (`SO_RCVTIMEO`). `None` disables the timeout, which is the default.
+    ///
+    /// If no client connects within the timeout, accepting fails with an error of kind [`TimedOut`]. Passing a zero duration is an error of kind [`InvalidInput`], since it would be indistinguishable from disabling the timeout. Unlike [`accept_timeout`], which takes the timeout for one call, this applies to every call until changed.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// [`accept`]: #method.accept " "
+    /// [`incoming`]: #method.incoming " "
+    /// [`accept_timeout`]: #method.accept_timeout " "
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    /// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+    #[inline]
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        set_timeout(self.as_raw_fd(), libc::SO_RCVTIMEO, timeout)
+    }
+    /// Returns the timeout for [`accept`] and [`incoming`] (`SO_RCVTIMEO`), or `None` if there is none.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    ///
+    /// [`accept`]: #method.accept " "
+    /// [`incoming`]: #method.incoming " "
+    #[inline]
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        get_timeout(self.as_raw_fd(), libc::SO_RCVTIMEO)
+    }
+    /// Retrieves and clears the pending error on the listening socket (`SO_ERROR`), returning `None` if there is none.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        take_error(self.as_raw_fd())
+    }
+}
+
+impl UdStream {
+    /// Sets the size of the send buffer of the stream (`SO_SNDBUF`), in bytes.
+    ///
+    /// The system treats the value as a hint: Linux doubles it to make room for its own bookkeeping and clamps it to the limits configured in `/proc/sys/net/core`, which is why [`send_buffer_size`] can report a different value afterwards.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// [`send_buffer_size`]: #method.send_buffer_size " "
+    #[inline]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_size_option(self.as_raw_fd(), libc::SO_SNDBUF, size)
+    }
+    /// Returns the size of the send buffer of the stream (`SO_SNDBUF`), in bytes.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        get_size_option(self.as_raw_fd(), libc::SO_SNDBUF)
+    }
+    /// Sets the size of the receive buffer of the stream (`SO_RCVBUF`), in bytes. The value is treated as a hint, just like with [`set_send_buffer_size`].
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// [`set_send_buffer_size`]: #method.set_send_buffer_size " "
+    #[inline]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_size_option(self.as_raw_fd(), libc::SO_RCVBUF, size)
+    }
+    /// Returns the size of the receive buffer of the stream (`SO_RCVBUF`), in bytes.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        get_size_option(self.as_raw_fd(), libc::SO_RCVBUF)
+    }
+    /// Sets the minimum amount of bytes which a blocking receive operation waits for before returning (`SO_RCVLOWAT`). The default is 1.
+    ///
+    /// A receive operation can still return less than that if the other side closes the connection, a timeout expires or a signal interrupts it.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    #[inline]
+    pub fn set_recv_low_water_mark(&self, bytes: usize) -> io::Result<()> {
+        set_size_option(self.as_raw_fd(), libc::SO_RCVLOWAT, bytes)
+    }
+    /// Returns the minimum amount of bytes which a blocking receive operation waits for before returning (`SO_RCVLOWAT`).
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn recv_low_water_mark(&self) -> io::Result<usize> {
+        get_size_option(self.as_raw_fd(), libc::SO_RCVLOWAT)
+    }
+    /// Enables or disables the `SO_PASSCRED` option on the stream, which makes it receive the credentials of the peer with every message. Streams are created with the option enabled.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    #[inline]
+    pub fn set_pass_credentials(&self, pass_credentials: bool) -> io::Result<()> {
+        set_int_option(self.as_raw_fd(), SO_PASSCRED, pass_credentials as c_int)
+    }
+    /// Returns whether the `SO_PASSCRED` option is enabled on the stream.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn pass_credentials(&self) -> io::Result<bool> {
+        get_int_option(self.as_raw_fd(), SO_PASSCRED).map(|x| x != 0)
+    }
+    /// Sets the offset from which data is peeked with `MSG_PEEK` (`SO_PEEK_OFF`), or disables the offset with `None`, which is the default.
+    ///
+    /// With an offset set, every peek advances the offset by the amount of bytes it returned, while every regular receive operation moves it back by the amount of bytes it consumed, which allows the incoming data to be examined piece by piece without consuming it.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// # Platform-specific behavior
+    /// Only available on Linux.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn set_peek_offset(&self, offset: Option<usize>) -> io::Result<()> {
+        set_peek_offset(self.as_raw_fd(), offset)
+    }
+    /// Returns the offset from which data is peeked with `MSG_PEEK` (`SO_PEEK_OFF`), or `None` if it's disabled.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    ///
+    /// # Platform-specific behavior
+    /// Only available on Linux.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn peek_offset(&self) -> io::Result<Option<usize>> {
+        get_peek_offset(self.as_raw_fd())
+    }
+    /// Retrieves and clears the pending error on the stream (`SO_ERROR`), returning `None` if there is none.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        take_error(self.as_raw_fd())
+    }
+}
+
+impl UdSocket {
+    /// Sets the size of the send buffer of the socket (`SO_SNDBUF`), in bytes, which also limits the size of the largest datagram that can be sent.
+    ///
+    /// The system treats the value as a hint: Linux doubles it to make room for its own bookkeeping and clamps it to the limits configured in `/proc/sys/net/core`, which is why [`send_buffer_size`] can report a different value afterwards.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// [`send_buffer_size`]: #method.send_buffer_size " "
+    #[inline]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_size_option(self.as_raw_fd(), libc::SO_SNDBUF, size)
+    }
+    /// Returns the size of the send buffer of the socket (`SO_SNDBUF`), in bytes.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        get_size_option(self.as_raw_fd(), libc::SO_SNDBUF)
+    }
+    /// Sets the size of the receive buffer of the socket (`SO_RCVBUF`), in bytes. The value is treated as a hint, just like with [`set_send_buffer_size`].
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// [`set_send_buffer_size`]: #method.set_send_buffer_size " "
+    #[inline]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        set_size_option(self.as_raw_fd(), libc::SO_RCVBUF, size)
+    }
+    /// Returns the size of the receive buffer of the socket (`SO_RCVBUF`), in bytes.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        get_size_option(self.as_raw_fd(), libc::SO_RCVBUF)
+    }
+    /// Sets the `SO_RCVLOWAT` option of the socket. The default is 1.
+    ///
+    /// Since datagrams are always received whole, the option has little to no effect on datagram sockets, and is only provided for completeness.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    #[inline]
+    pub fn set_recv_low_water_mark(&self, bytes: usize) -> io::Result<()> {
+        set_size_option(self.as_raw_fd(), libc::SO_RCVLOWAT, bytes)
+    }
+    /// Returns the value of the `SO_RCVLOWAT` option of the socket.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn recv_low_water_mark(&self) -> io::Result<usize> {
+        get_size_option(self.as_raw_fd(), libc::SO_RCVLOWAT)
+    }
+    /// Sets the timeout for receive operations on the socket. `None` disables the timeout, which is the default.
+    ///
+    /// If no datagram arrives within the timeout, the receive operation fails with an error of kind [`TimedOut`]. Passing a zero duration is an error of kind [`InvalidInput`], since it would be indistinguishable from disabling the timeout.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    /// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+    #[inline]
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        set_timeout(self.as_raw_fd(), libc::SO_RCVTIMEO, timeout)
+    }
+    /// Sets the timeout for send operations on the socket. `None` disables the timeout, which is the default.
+    ///
+    /// If the datagram can't be queued within the timeout because the receiver isn't keeping up, the send operation fails with an error of kind [`TimedOut`]. Passing a zero duration is an error of kind [`InvalidInput`], since it would be indistinguishable from disabling the timeout.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    /// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+    #[inline]
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        set_timeout(self.as_raw_fd(), libc::SO_SNDTIMEO, timeout)
+    }
+    /// Returns the timeout for receive operations on the socket, or `None` if there is none.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        get_timeout(self.as_raw_fd(), libc::SO_RCVTIMEO)
+    }
+    /// Returns the timeout for send operations on the socket, or `None` if there is none.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn write_timeout(&self) -> io::Result<Option<Duration>> {
+        get_timeout(self.as_raw_fd(), libc::SO_SNDTIMEO)
+    }
+    /// Enables or disables the `SO_PASSCRED` option on the socket, which makes it receive the credentials of the sender with every datagram. Sockets are created with the option enabled.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    #[inline]
+    pub fn set_pass_credentials(&self, pass_credentials: bool) -> io::Result<()> {
+        set_int_option(self.as_raw_fd(), SO_PASSCRED, pass_credentials as c_int)
+    }
+    /// Returns whether the `SO_PASSCRED` option is enabled on the socket.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn pass_credentials(&self) -> io::Result<bool> {
+        get_int_option(self.as_raw_fd(), SO_PASSCRED).map(|x| x != 0)
+    }
+    /// Sets the offset from which the next datagram is peeked with `MSG_PEEK` (`SO_PEEK_OFF`), or disables the offset with `None`, which is the default.
+    ///
+    /// With an offset set, every peek advances the offset by the amount of bytes it returned, while every regular receive operation moves it back by the amount of bytes it consumed, which allows a large datagram to be examined piece by piece without consuming it.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// # Platform-specific behavior
+    /// Only available on Linux.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn set_peek_offset(&self, offset: Option<usize>) -> io::Result<()> {
+        set_peek_offset(self.as_raw_fd(), offset)
+    }
+    /// Returns the offset from which the next datagram is peeked with `MSG_PEEK` (`SO_PEEK_OFF`), or `None` if it's disabled.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    ///
+    /// # Platform-specific behavior
+    /// Only available on Linux.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn peek_offset(&self) -> io::Result<Option<usize>> {
+        get_peek_offset(self.as_raw_fd())
+    }
+    /// Retrieves and clears the pending error on the socket (`SO_ERROR`), returning `None` if there is none.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        take_error(self.as_raw_fd())
+    }
+}
@@ -0,0 +1,555 @@
+//! `SOCK_SEQPACKET` Ud-sockets, which are connection-oriented like byte streams but preserve message boundaries like datagrams.
+
+#[cfg(unix)]
+use libc::{c_int, SOCK_SEQPACKET, SOL_SOCKET, SO_PASSCRED};
+use std::{
+    io::{self, IoSlice, IoSliceMut},
+    fmt::{self, Formatter, Debug},
+    iter,
+    net::Shutdown,
+    mem,
+    time::Duration,
+};
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, IntoRawFd, FromRawFd};
+use super::*;
+
+/// A Unix domain sequential packet socket server, listening for connections.
+///
+/// All such sockets have the `SOCK_SEQPACKET` socket type. The connections produced by the listener are reliable and ordered just like byte streams, but every message sent arrives as a whole and is received by exactly one receive call, much like with Windows message-mode named pipes. The other side closing the connection is reported just like with byte streams, which makes it easy to tell whether the peer is still alive.
+///
+/// # Platform-specific behavior
+/// macOS and iOS don't support `SOCK_SEQPACKET` Ud-sockets, so creating either a listener or a client socket always fails there.
+///
+/// # Example
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # #[cfg(unix)] {
+/// use interprocess::os::unix::udsocket::UdSeqpacketListener;
+///
+/// let listener = UdSeqpacketListener::bind("/tmp/example.sock")?;
+/// let conn = listener.accept()?;
+/// let mut buffer = [0; 128];
+/// loop {
+///     let (size, truncated) = conn.recv(&mut buffer)?;
+///     if size == 0 {
+///         break; // The client has disconnected
+///     }
+///     assert!(!truncated, "the message was too big");
+///     conn.send(&buffer[..size])?;
+/// }
+/// # }
+/// # Ok(()) }
+/// ```
+pub struct UdSeqpacketListener {
+    // Seqpacket listeners only differ from byte stream ones in the type of sockets they accept,
+    // so we reuse the creation and cleanup logic.
+    pub(super) inner: UdStreamListener,
+}
+impl UdSeqpacketListener {
+    /// Creates a new listener socket at the specified address.
+    ///
+    /// The listener is created with the default options, i.e. a backlog of 128 connections and `SO_PASSCRED` enabled. Use [`UdStreamListenerOptions::create_seqpacket`] to change those, as well as to set the permissions and owner of the socket file.
+    ///
+    /// # System calls
+    /// - `socket`
+    /// - `bind`
+    /// - `listen`
+    /// - `setsockopt`
+    ///
+    /// [`UdStreamListenerOptions::create_seqpacket`]: struct.UdStreamListenerOptions.html#method.create_seqpacket " "
+    #[inline]
+    pub fn bind<'a>(path: impl ToUdSocketPath<'a>) -> io::Result<Self> {
+        UdStreamListenerOptions::new().create_seqpacket(path)
+    }
+    /// Creates a new listener socket at the specified address, replacing the socket file left behind by a previous server if that server is no longer running, and removing the socket file when the listener is dropped.
+    ///
+    /// See [`UdStreamListener::bind_reclaiming`] for the details.
+    ///
+    /// [`UdStreamListener::bind_reclaiming`]: struct.UdStreamListener.html#method.bind_reclaiming " "
+    #[inline]
+    pub fn bind_reclaiming<'a>(path: impl ToUdSocketPath<'a>) -> io::Result<Self> {
+        UdStreamListenerOptions::new().reclaim(true).create_seqpacket(path)
+    }
+    /// Listens for incoming connections to the socket, blocking until a client is connected.
+    ///
+    /// Errors, including timeouts set on the listener, are reported the same way as by [`UdStreamListener::accept`].
+    ///
+    /// # System calls
+    /// - `accept`
+    ///
+    /// [`UdStreamListener::accept`]: struct.UdStreamListener.html#method.accept " "
+    #[inline]
+    pub fn accept(&self) -> io::Result<UdSeqpacket> {
+        // The accepted socket has the type of the listener, so the byte stream listener's accept
+        // works as is.
+        let fd = self.inner.accept()?.into_raw_fd();
+        // SAFETY: we just created the file descriptor, meaning that it's guaranteeed not to be
+        // used elsewhere
+        Ok(unsafe {UdSeqpacket::from_raw_fd(fd)})
+    }
+    /// Listens for incoming connections to the socket, blocking until a client is connected or the timeout expires, in which case an error of kind [`TimedOut`] is returned.
+    ///
+    /// The listener's mode is left untouched, and losing a connection to another thread or process works just like with [`UdStreamListener::accept_timeout`]: the call only keeps to the deadline in that case if the listener is in non-blocking mode.
+    ///
+    /// # System calls
+    /// - `poll`
+    /// - `accept`
+    ///
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    /// [`UdStreamListener::accept_timeout`]: struct.UdStreamListener.html#method.accept_timeout " "
+    pub fn accept_timeout(&self, timeout: Duration) -> io::Result<UdSeqpacket> {
+        let fd = accept_with_timeout(self.as_raw_fd(), timeout)?;
+        // SAFETY: we just created the file descriptor, meaning that it's guaranteeed not to be
+        // used elsewhere
+        Ok(unsafe {UdSeqpacket::from_raw_fd(fd)})
+    }
+    /// Creates an infinite iterator which calls `accept()` with each iteration. Used together with `for` loops to conveniently create a main loop for a socket server.
+    #[inline]
+    pub fn incoming(&self) -> impl Iterator<Item = io::Result<UdSeqpacket>> + '_ {
+        iter::repeat_with(move || self.accept())
+    }
+    /// Enables or disables the non-blocking mode for the listener. By default, it is disabled.
+    ///
+    /// In non-blocking mode, calling [`accept`] when there are no pending connections immediately returns an error of kind [`WouldBlock`] instead of blocking until a client connects. The non-blocking mode is not inherited by the sockets produced by the listener.
+    ///
+    /// # System calls
+    /// - `fcntl`
+    ///
+    /// [`accept`]: #method.accept " "
+    /// [`WouldBlock`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.WouldBlock " "
+    #[inline(always)]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+    /// Returns the address the listener is bound to. See [`UdStreamListener::local_addr`] for the details.
+    ///
+    /// [`UdStreamListener::local_addr`]: struct.UdStreamListener.html#method.local_addr " "
+    #[inline(always)]
+    pub fn local_addr(&self) -> io::Result<UdSocketPath<'static>> {
+        self.inner.local_addr()
+    }
+}
+impl Debug for UdSeqpacketListener {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UdSeqpacketListener")
+            .field("file_descriptor", &self.as_raw_fd())
+            .field("unlink_on_drop", &self.inner.unlink_guard.is_some())
+            .finish()
+    }
+}
+impl_owned_fd!(UdSeqpacketListener);
+#[cfg(unix)]
+impl AsRawFd for UdSeqpacketListener {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> c_int {
+        self.inner.as_raw_fd()
+    }
+}
+#[cfg(unix)]
+impl IntoRawFd for UdSeqpacketListener {
+    #[inline(always)]
+    fn into_raw_fd(self) -> c_int {
+        self.inner.into_raw_fd()
+    }
+}
+#[cfg(unix)]
+impl FromRawFd for UdSeqpacketListener {
+    #[inline(always)]
+    unsafe fn from_raw_fd(fd: c_int) -> Self {
+        Self {inner: UdStreamListener::from_raw_fd(fd)}
+    }
+}
+
+/// A Unix domain sequential packet socket connection, obtained either from [`UdSeqpacketListener`] or by connecting to an existing server.
+///
+/// Every [`send`] call produces exactly one message, which is received as a whole by one [`recv`] call on the other side, or truncated if the buffer is too small for it. A zero-sized receive means that the other side has closed the connection (or sent an empty message, which is indistinguishable from that and is thus best avoided). The [`WriteMsg`] implementation refuses to send empty messages for this reason, and the [`ReliableReadMsg`] one reports a zero-sized message as an error of kind [`UnexpectedEof`].
+///
+/// # Example
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # #[cfg(unix)] {
+/// use interprocess::os::unix::udsocket::UdSeqpacket;
+///
+/// let conn = UdSeqpacket::connect("/tmp/example.sock")?;
+/// conn.send(b"first message")?;
+/// conn.send(b"second message")?;
+/// let mut buffer = [0; 128];
+/// let (size, _) = conn.recv(&mut buffer)?;
+/// println!("Server answered: {}", String::from_utf8_lossy(&buffer[..size]));
+/// # }
+/// # Ok(()) }
+/// ```
+///
+/// [`UdSeqpacketListener`]: struct.UdSeqpacketListener.html " "
+/// [`send`]: #method.send " "
+/// [`recv`]: #method.recv " "
+/// [`WriteMsg`]: ../../../trait.WriteMsg.html " "
+/// [`ReliableReadMsg`]: ../../../trait.ReliableReadMsg.html " "
+/// [`UnexpectedEof`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof " "
+pub struct UdSeqpacket {
+    fd: FdOps,
+}
+impl UdSeqpacket {
+    /// Connects to a Unix domain sequential packet socket server at the specified path.
+    ///
+    /// # System calls
+    /// - `socket`
+    /// - `connect`
+    /// - `setsockopt`
+    pub fn connect<'a>(path: impl ToUdSocketPath<'a>) -> io::Result<Self> {
+        let path = path.to_socket_path()?; // Shadow original by conversion
+        let (addr, addrlen, _parent_dir) = make_sockaddr_un(&path)?;
+        let (success, fd) = unsafe {
+            let result = libc::socket(AF_UNIX, SOCK_SEQPACKET, 0);
+            (result != -1, result)
+        };
+        if !success {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: we just created the file descriptor, meaning that it's guaranteed not to be used
+        // elsewhere; wrapping it right away makes sure that it's closed if anything below fails
+        let socket = unsafe {Self::from_raw_fd(fd)};
+        let success = unsafe {
+            if libc::connect(
+                fd,
+                // Same as in UdStreamListener::bind()
+                &addr as *const _ as *const _,
+                addrlen as u32,
+            ) != -1 {
+                let passcred: c_int = 1;
+                libc::setsockopt(
+                    fd,
+                    SOL_SOCKET,
+                    SO_PASSCRED,
+                    &passcred as *const _ as *const _,
+                    mem::size_of_val(&passcred) as u32,
+                ) != -1
+            } else {
+                false
+            }
+        };
+        if success {
+            Ok(socket)
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+    /// Creates a pair of connected sequential packet sockets which aren't bound to any path, useful for communicating with a child process or between threads without touching the filesystem.
+    ///
+    /// # System calls
+    /// - `socketpair`
+    /// - `setsockopt`
+    #[inline]
+    pub fn pair() -> io::Result<(Self, Self)> {
+        let (fd1, fd2) = socketpair(SOCK_SEQPACKET)?;
+        Ok((Self {fd: fd1}, Self {fd: fd2}))
+    }
+
+    /// Receives a single message from the socket, returning how much of the buffer was filled out and whether a part of the message was discarded because the buffer was too small.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    #[inline(always)]
+    pub fn recv(&self, buf: &mut [u8]) -> io::Result<(usize, bool)> {
+        self.recv_vectored(&mut [IoSliceMut::new(buf)])
+    }
+    /// Receives a single message from the socket, making use of [scatter input] and returning how much of the buffers was filled out and whether a part of the message was discarded because the buffers were too small.
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    ///
+    /// [scatter input]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    #[inline(always)]
+    pub fn recv_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<(usize, bool)> {
+        self.recv_ancillary_vectored(bufs, &mut AncillaryDataBuf::Owned(Vec::new()))
+            .map(|x| (x.0, x.1))
+    }
+    /// Receives a single message and ancillary data from the socket. The return value is in the following order:
+    /// - How many bytes of the message were received
+    /// - Whether a part of the message was discarded because the buffer was too small
+    /// - How many bytes of ancillary data were received
+    /// - Whether some ancillary data was discarded because the buffer was too small
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    #[inline(always)]
+    pub fn recv_ancillary<'a: 'b, 'b>(
+        &self,
+        buf: &mut [u8],
+        abuf: &'b mut AncillaryDataBuf<'a>,
+    ) -> io::Result<(usize, bool, usize, bool)> {
+        self.recv_ancillary_vectored(&mut [IoSliceMut::new(buf)], abuf)
+    }
+    /// Receives a single message and ancillary data from the socket, making use of [scatter input]. The return value is in the same order as with [`recv_ancillary`].
+    ///
+    /// # System calls
+    /// - `recvmsg`
+    ///
+    /// [scatter input]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    /// [`recv_ancillary`]: #method.recv_ancillary " "
+    #[inline]
+    pub fn recv_ancillary_vectored<'a: 'b, 'b>(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        abuf: &'b mut AncillaryDataBuf<'a>,
+    ) -> io::Result<(usize, bool, usize, bool)> {
+        recv_msg_ancillary(self.as_raw_fd(), bufs, abuf.as_mut())
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+    /// Returns the size of the next message available on the socket without discarding it.
+    ///
+    /// This method is only available on Linux, since only Linux reports the full size of a message when peeking into a smaller buffer.
+    ///
+    /// # System calls
+    /// - `recv`
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(target_os = "linux")))]
+    #[inline(always)]
+    pub fn peek_msg_size(&self) -> io::Result<usize> {
+        peek_msg_size(self.as_raw_fd())
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+
+    /// Sends a message into the socket.
+    ///
+    /// The message is sent as a whole or not at all — if it's bigger than the send buffer of the socket, an error is returned.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    #[inline(always)]
+    pub fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        self.send_vectored(&[IoSlice::new(buf)])
+    }
+    /// Sends a message into the socket, making use of [gather output].
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///
+    /// [gather output]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    #[inline(always)]
+    pub fn send_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.send_ancillary_vectored(bufs, iter::empty())
+            .map(|x| x.0)
+    }
+    /// Sends a message and ancillary data into the socket.
+    ///
+    /// The ancillary data buffer is automatically converted from the supplied value, if possible. For that reason, slices and `Vec`s of `AncillaryData` can be passed directly.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    #[inline(always)]
+    pub fn send_ancillary<'a>(
+        &self,
+        buf: &[u8],
+        ancillary_data: impl IntoIterator<Item = AncillaryData<'a>>,
+    ) -> io::Result<(usize, usize)> {
+        self.send_ancillary_vectored(&[IoSlice::new(buf)], ancillary_data)
+    }
+    /// Sends a message and ancillary data into the socket, making use of [gather output] for the main data.
+    ///
+    /// The ancillary data buffer is automatically converted from the supplied value, if possible. For that reason, slices and `Vec`s of `AncillaryData` can be passed directly.
+    ///
+    /// # System calls
+    /// - `sendmsg`
+    ///
+    /// [gather output]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    #[inline]
+    pub fn send_ancillary_vectored<'a>(
+        &self,
+        bufs: &[IoSlice<'_>],
+        ancillary_data: impl IntoIterator<Item = AncillaryData<'a>>,
+    ) -> io::Result<(usize, usize)> {
+        let abuf = ancillary_data.into_iter().collect::<EncodedAncillaryData<'_>>();
+        send_msg_ancillary(self.as_raw_fd(), bufs, abuf.as_ref(), None)
+            .map_err(|e| timeout_to_timed_out(self.as_raw_fd(), e))
+    }
+
+    /// Enables or disables the non-blocking mode for the socket. By default, it is disabled.
+    ///
+    /// In non-blocking mode, receiving from the socket when no messages are available or sending into it when the send buffer is full immediately returns an error of kind [`WouldBlock`] instead of blocking.
+    ///
+    /// # System calls
+    /// - `fcntl`
+    ///
+    /// [`WouldBlock`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.WouldBlock " "
+    #[inline]
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        set_nonblocking(self.as_raw_fd(), nonblocking)
+    }
+    /// Returns the address the socket is bound to, which is [`Unnamed`] for sockets created by connecting to a server and the address of the listener for accepted ones.
+    ///
+    /// # System calls
+    /// - `getsockname`
+    ///
+    /// [`Unnamed`]: enum.UdSocketPath.html#variant.Unnamed " "
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<UdSocketPath<'static>> {
+        socket_addr(self.as_raw_fd(), false)
+    }
+    /// Returns the address of the other end of the connection, which is usually [`Unnamed`] for sockets accepted by a listener.
+    ///
+    /// # System calls
+    /// - `getpeername`
+    ///
+    /// [`Unnamed`]: enum.UdSocketPath.html#variant.Unnamed " "
+    #[inline]
+    pub fn peer_addr(&self) -> io::Result<UdSocketPath<'static>> {
+        socket_addr(self.as_raw_fd(), true)
+    }
+    /// Sets the timeout for receive operations on the socket. `None` disables the timeout, which is the default.
+    ///
+    /// If a receive operation doesn't complete within the timeout, it fails with an error of kind [`TimedOut`]. Passing a zero duration is an error of kind [`InvalidInput`], since it would be indistinguishable from disabling the timeout.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    /// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+    #[inline]
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        set_timeout(self.as_raw_fd(), libc::SO_RCVTIMEO, timeout)
+    }
+    /// Sets the timeout for send operations on the socket. `None` disables the timeout, which is the default.
+    ///
+    /// If a send operation doesn't complete within the timeout, it fails with an error of kind [`TimedOut`]. Passing a zero duration is an error of kind [`InvalidInput`], since it would be indistinguishable from disabling the timeout.
+    ///
+    /// # System calls
+    /// - `setsockopt`
+    ///
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut " "
+    /// [`InvalidInput`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput " "
+    #[inline]
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        set_timeout(self.as_raw_fd(), libc::SO_SNDTIMEO, timeout)
+    }
+    /// Returns the timeout for receive operations on the socket, or `None` if there is none.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        get_timeout(self.as_raw_fd(), libc::SO_RCVTIMEO)
+    }
+    /// Returns the timeout for send operations on the socket, or `None` if there is none.
+    ///
+    /// # System calls
+    /// - `getsockopt`
+    #[inline]
+    pub fn write_timeout(&self) -> io::Result<Option<Duration>> {
+        get_timeout(self.as_raw_fd(), libc::SO_SNDTIMEO)
+    }
+    /// Shuts down the receiving half, the sending half or both halves of the connection.
+    ///
+    /// Once the sending half is shut down, the other side receives a zero-sized message as soon as it receives all the messages sent before that.
+    ///
+    /// # System calls
+    /// - `shutdown`
+    #[inline]
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        let how = match how {
+            Shutdown::Read => libc::SHUT_RD,
+            Shutdown::Write => libc::SHUT_WR,
+            Shutdown::Both => libc::SHUT_RDWR,
+        };
+        let success = unsafe {
+            libc::shutdown(self.as_raw_fd(), how) != -1
+        };
+        if success {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+}
+#[cfg(target_os = "linux")]
+impl UdSeqpacket {
+    /// Removes the zero-sized message, if there is one rather than the end of the connection, so that it isn't reported again, and returns the error for it.
+    fn consume_eof(&self) -> io::Error {
+        if let Err(error) = self.recv(&mut []) {
+            return error;
+        }
+        io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "the connection was closed by the other side",
+        )
+    }
+}
+impl Debug for UdSeqpacket {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UdSeqpacket")
+            .field("file_descriptor", &self.as_raw_fd())
+            .finish()
+    }
+}
+#[cfg(target_os = "linux")]
+impl ReliableReadMsg for UdSeqpacket {
+    #[inline]
+    fn read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, Vec<u8>>> {
+        let msg_size = self.peek_msg_size()?;
+        if msg_size == 0 {
+            return Err(self.consume_eof());
+        }
+        if msg_size > buf.len() {
+            let mut new_buffer = vec![0; msg_size];
+            self.recv(&mut new_buffer)?;
+            Ok(Err(new_buffer))
+        } else {
+            Ok(Ok(self.recv(buf)?.0))
+        }
+    }
+    #[inline]
+    fn try_read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, usize>> {
+        let msg_size = self.peek_msg_size()?;
+        if msg_size == 0 {
+            return Err(self.consume_eof());
+        }
+        if msg_size > buf.len() {
+            Ok(Err(msg_size))
+        } else {
+            Ok(Ok(self.recv(buf)?.0))
+        }
+    }
+}
+#[cfg(unix)]
+impl WriteMsg for UdSeqpacket {
+    #[inline]
+    fn write_msg(&mut self, msg: &[u8]) -> io::Result<()> {
+        if msg.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "empty messages are indistinguishable from the end of the connection",
+            ));
+        }
+        if self.send(msg)? == msg.len() {
+            Ok(())
+        } else {
+            Err(io::Error::new(io::ErrorKind::Other, PartialMsgWriteError))
+        }
+    }
+}
+#[cfg(unix)]
+impl Sealed for UdSeqpacket {}
+impl_owned_fd!(UdSeqpacket);
+#[cfg(unix)]
+impl AsRawFd for UdSeqpacket {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> c_int {
+        self.fd.as_raw_fd()
+    }
+}
+#[cfg(unix)]
+impl IntoRawFd for UdSeqpacket {
+    #[inline(always)]
+    fn into_raw_fd(self) -> c_int {
+        self.fd.into_raw_fd()
+    }
+}
+#[cfg(unix)]
+impl FromRawFd for UdSeqpacket {
+    #[inline(always)]
+    unsafe fn from_raw_fd(fd: c_int) -> Self {
+        Self {fd: FdOps(fd)}
+    }
+}
@@ -0,0 +1,498 @@
+//! Asynchronous Ud-sockets for the Tokio runtime.
+//!
+//! The types in this module are registered with Tokio's reactor via [`AsyncFd`] and thus must be created from within the context of a Tokio runtime with I/O enabled, just like the networking types in Tokio itself. Each of them can be converted to and from its synchronous counterpart in the [parent module] with `from_sync` and `into_sync`.
+//!
+//! [`AsyncFd`]: https://docs.rs/tokio/*/tokio/io/unix/struct.AsyncFd.html " "
+//! [parent module]: ../index.html " "
+
+use std::{
+    io::{self, IoSlice, IoSliceMut},
+    fmt::{self, Formatter, Debug},
+    net::Shutdown,
+    os::unix::io::{AsRawFd, RawFd},
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::{
+    unix::AsyncFd,
+    AsyncRead, AsyncWrite, ReadBuf,
+};
+use super::super::{
+    tokio::{poll_fn, poll_read_with, poll_write_with, register, unregister},
+    yield_now,
+};
+use super::{
+    UdStreamListener as SyncUdStreamListener,
+    UdStream as SyncUdStream,
+    UdSocket as SyncUdSocket,
+    UdSocketPath,
+    ToUdSocketPath,
+};
+
+/// A Tokio-based Unix domain byte stream socket server, listening for connections.
+///
+/// # Example
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use interprocess::os::unix::udsocket::tokio::UdStreamListener;
+/// use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+///
+/// let listener = UdStreamListener::bind("/tmp/example.sock")?;
+/// loop {
+///     let mut conn = listener.accept().await?;
+///     tokio::spawn(async move {
+///         conn.write_all(b"Hello from server!\n").await?;
+///         let mut conn = BufReader::new(conn);
+///         let mut buffer = String::new();
+///         conn.read_line(&mut buffer).await?;
+///         println!("Client answered: {}", buffer);
+///         Ok::<_, std::io::Error>(())
+///     });
+/// }
+/// # }
+/// ```
+pub struct UdStreamListener {
+    inner: AsyncFd<SyncUdStreamListener>,
+}
+impl UdStreamListener {
+    /// Creates a new listener socket at the specified address.
+    ///
+    /// See the [synchronous version] for details on the path handling.
+    ///
+    /// # Panics
+    /// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+    ///
+    /// [synchronous version]: ../struct.UdStreamListener.html#method.bind " "
+    #[inline]
+    pub fn bind<'a>(path: impl ToUdSocketPath<'a>) -> io::Result<Self> {
+        Self::from_sync(SyncUdStreamListener::bind(path)?)
+    }
+    /// Listens for incoming connections to the socket, asynchronously waiting until a client is connected.
+    ///
+    /// This operation is cancellation-safe: if the future is dropped before it completes, no incoming connection is lost.
+    #[inline]
+    pub async fn accept(&self) -> io::Result<UdStream> {
+        poll_fn(|cx| self.poll_accept(cx)).await
+    }
+    /// Polls for an incoming connection, registering the current task for wakeup if none is pending.
+    #[inline]
+    pub fn poll_accept(&self, cx: &mut Context<'_>) -> Poll<io::Result<UdStream>> {
+        match poll_read_with(&self.inner, cx, SyncUdStreamListener::accept_nonblocking) {
+            Poll::Ready(Ok(stream)) => Poll::Ready(UdStream::from_sync(stream)),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+    /// Returns the address the listener is bound to. See [the synchronous version] for the details.
+    ///
+    /// [the synchronous version]: ../struct.UdStreamListener.html#method.local_addr " "
+    #[inline(always)]
+    pub fn local_addr(&self) -> io::Result<UdSocketPath<'static>> {
+        self.inner.get_ref().local_addr()
+    }
+    /// Registers a synchronous listener with Tokio's reactor, putting it into non-blocking mode.
+    ///
+    /// # Panics
+    /// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+    #[inline]
+    pub fn from_sync(sync: SyncUdStreamListener) -> io::Result<Self> {
+        Ok(Self {inner: register(sync)?})
+    }
+    /// Unregisters the listener from Tokio's reactor and puts it back into blocking mode.
+    #[inline]
+    pub fn into_sync(self) -> io::Result<SyncUdStreamListener> {
+        unregister(self.inner)
+    }
+}
+impl Debug for UdStreamListener {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UdStreamListener")
+            .field("file_descriptor", &self.as_raw_fd())
+            .finish()
+    }
+}
+impl AsRawFd for UdStreamListener {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+/// A Tokio-based Unix domain socket byte stream, obtained either from [`UdStreamListener`] or by connecting to an existing server.
+///
+/// # Example
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use interprocess::os::unix::udsocket::tokio::UdStream;
+/// use tokio::io::{AsyncReadExt, AsyncWriteExt};
+///
+/// let mut conn = UdStream::connect("/tmp/example.sock").await?;
+/// conn.write_all(b"Hello from client!").await?;
+/// let mut string_buffer = String::new();
+/// conn.read_to_string(&mut string_buffer).await?;
+/// println!("Server answered: {}", string_buffer);
+/// # Ok(()) }
+/// ```
+///
+/// [`UdStreamListener`]: struct.UdStreamListener.html " "
+pub struct UdStream {
+    inner: AsyncFd<SyncUdStream>,
+}
+impl UdStream {
+    /// Connects to a Unix domain socket server at the specified path.
+    ///
+    /// The socket is connected in non-blocking mode, so if the server's listen backlog is full, the returned future waits for the connection to go through instead of blocking the runtime's thread.
+    ///
+    /// # Panics
+    /// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+    #[inline]
+    pub async fn connect<'a>(path: impl ToUdSocketPath<'a>) -> io::Result<Self> {
+        let path = path.to_socket_path()?;
+        let inner = register(SyncUdStream::new_nonblocking()?)?;
+        loop {
+            match inner.get_ref().connect_nonblocking(&path) {
+                Ok(true) => break,
+                Ok(false) => {
+                    inner.writable().await?.retain_ready();
+                    if let Some(error) = inner.get_ref().take_error()? {
+                        return Err(error);
+                    }
+                    break;
+                }
+                // The server's backlog is full, and there's no readiness event for it getting
+                // room again, so give the other tasks a chance to run before retrying.
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => yield_now().await,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(Self {inner})
+    }
+    /// Receives bytes from the socket stream.
+    #[inline]
+    pub async fn recv(&self, buf: &mut [u8]) -> io::Result<usize> {
+        poll_fn(|cx| poll_read_with(&self.inner, cx, |s| s.recv_nonblocking(buf))).await
+    }
+    /// Receives bytes from the socket stream, making use of [scatter input] for the main data.
+    ///
+    /// [scatter input]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    #[inline]
+    pub async fn recv_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        poll_fn(|cx| poll_read_with(&self.inner, cx, |s| s.recv_vectored_nonblocking(bufs))).await
+    }
+    /// Sends bytes into the socket stream.
+    #[inline]
+    pub async fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        poll_fn(|cx| poll_write_with(&self.inner, cx, |s| s.send_nonblocking(buf))).await
+    }
+    /// Sends bytes into the socket stream, making use of [gather output] for the main data.
+    ///
+    /// [gather output]: https://en.wikipedia.org/wiki/Vectored_I/O " "
+    #[inline]
+    pub async fn send_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        poll_fn(|cx| poll_write_with(&self.inner, cx, |s| s.send_vectored_nonblocking(bufs))).await
+    }
+    /// Shuts down the read half, the write half or both halves of the stream. See [the synchronous version] for the details.
+    ///
+    /// Shutting down the write half is also what [`poll_shutdown`] does.
+    ///
+    /// [the synchronous version]: ../struct.UdStream.html#method.shutdown " "
+    /// [`poll_shutdown`]: https://docs.rs/tokio/*/tokio/io/trait.AsyncWrite.html#tymethod.poll_shutdown " "
+    #[inline(always)]
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.inner.get_ref().shutdown(how)
+    }
+    /// Returns the address the stream is bound to. See [the synchronous version] for the details.
+    ///
+    /// [the synchronous version]: ../struct.UdStream.html#method.local_addr " "
+    #[inline(always)]
+    pub fn local_addr(&self) -> io::Result<UdSocketPath<'static>> {
+        self.inner.get_ref().local_addr()
+    }
+    /// Returns the address of the other end of the stream. See [the synchronous version] for the details.
+    ///
+    /// [the synchronous version]: ../struct.UdStream.html#method.peer_addr " "
+    #[inline(always)]
+    pub fn peer_addr(&self) -> io::Result<UdSocketPath<'static>> {
+        self.inner.get_ref().peer_addr()
+    }
+    /// Registers a synchronous Ud-socket stream with Tokio's reactor, putting it into non-blocking mode.
+    ///
+    /// # Panics
+    /// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+    #[inline]
+    pub fn from_sync(sync: SyncUdStream) -> io::Result<Self> {
+        Ok(Self {inner: register(sync)?})
+    }
+    /// Unregisters the stream from Tokio's reactor and puts it back into blocking mode.
+    #[inline]
+    pub fn into_sync(self) -> io::Result<SyncUdStream> {
+        unregister(self.inner)
+    }
+}
+impl AsyncRead for UdStream {
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let unfilled = buf.initialize_unfilled();
+        match poll_read_with(&self.inner, cx, |s| s.recv_nonblocking(unfilled)) {
+            Poll::Ready(Ok(bytes_read)) => {
+                buf.advance(bytes_read);
+                Poll::Ready(Ok(()))
+            },
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+impl AsyncWrite for UdStream {
+    #[inline]
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        poll_write_with(&self.inner, cx, |s| s.send_nonblocking(buf))
+    }
+    #[inline]
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        poll_write_with(&self.inner, cx, |s| s.send_vectored_nonblocking(bufs))
+    }
+    #[inline(always)]
+    fn is_write_vectored(&self) -> bool {
+        true
+    }
+    #[inline(always)]
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.shutdown(Shutdown::Write))
+    }
+}
+impl Debug for UdStream {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UdStream")
+            .field("file_descriptor", &self.as_raw_fd())
+            .finish()
+    }
+}
+impl AsRawFd for UdStream {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+/// A Tokio-based datagram socket in the Unix domain.
+///
+/// # Example
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use interprocess::os::unix::udsocket::tokio::UdSocket;
+///
+/// let socket = UdSocket::bind("/tmp/example.sock")?;
+/// let mut buffer = [0; 128];
+/// loop {
+///     let (bytes_read, _) = socket.recv(&mut buffer).await?;
+///     println!("Received: {}", String::from_utf8_lossy(&buffer[..bytes_read]));
+/// }
+/// # }
+/// ```
+pub struct UdSocket {
+    inner: AsyncFd<SyncUdSocket>,
+}
+impl UdSocket {
+    /// Creates a new socket bound to the specified address.
+    ///
+    /// # Panics
+    /// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+    #[inline]
+    pub fn bind<'a>(path: impl ToUdSocketPath<'a>) -> io::Result<Self> {
+        Self::from_sync(SyncUdSocket::bind(path)?)
+    }
+    /// Creates a new socket connected to the specified remote address.
+    ///
+    /// # Panics
+    /// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+    #[inline]
+    pub fn connect<'a>(path: impl ToUdSocketPath<'a>) -> io::Result<Self> {
+        Self::from_sync(SyncUdSocket::connect(path)?)
+    }
+    /// Receives a single datagram from the socket, returning how much of the buffer was filled out and whether a part of the datagram was discarded because the buffer was too small.
+    #[inline]
+    pub async fn recv(&self, buf: &mut [u8]) -> io::Result<(usize, bool)> {
+        poll_fn(|cx| self.poll_recv(cx, buf)).await
+    }
+    /// Polls for a single datagram, registering the current task for wakeup if none is pending.
+    #[inline]
+    pub fn poll_recv(&self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<(usize, bool)>> {
+        poll_read_with(&self.inner, cx, |s| s.recv_nonblocking(buf))
+    }
+    /// Receives a single datagram and the source address from the socket, returning how much of the buffer was filled out and whether a part of the datagram was discarded because the buffer was too small.
+    #[inline]
+    pub async fn recv_from<'a: 'b, 'b>(
+        &self,
+        buf: &mut [u8],
+        addr_buf: &'b mut UdSocketPath<'a>,
+    ) -> io::Result<(usize, bool)> {
+        poll_fn(|cx| poll_read_with(&self.inner, cx, |s| s.recv_from_nonblocking(buf, addr_buf))).await
+    }
+    /// Sends a datagram into the socket.
+    #[inline]
+    pub async fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        poll_fn(|cx| self.poll_send(cx, buf)).await
+    }
+    /// Polls for the ability to send a datagram and sends it once the socket becomes writable.
+    #[inline]
+    pub fn poll_send(&self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        poll_write_with(&self.inner, cx, |s| s.send_nonblocking(buf))
+    }
+    /// Returns the address the socket is bound to. See [the synchronous version] for the details.
+    ///
+    /// [the synchronous version]: ../struct.UdSocket.html#method.local_addr " "
+    #[inline(always)]
+    pub fn local_addr(&self) -> io::Result<UdSocketPath<'static>> {
+        self.inner.get_ref().local_addr()
+    }
+    /// Returns the address of the peer the socket is connected to. See [the synchronous version] for the details.
+    ///
+    /// [the synchronous version]: ../struct.UdSocket.html#method.peer_addr " "
+    #[inline(always)]
+    pub fn peer_addr(&self) -> io::Result<UdSocketPath<'static>> {
+        self.inner.get_ref().peer_addr()
+    }
+    /// Registers a synchronous datagram socket with Tokio's reactor, putting it into non-blocking mode.
+    ///
+    /// # Panics
+    /// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+    #[inline]
+    pub fn from_sync(sync: SyncUdSocket) -> io::Result<Self> {
+        Ok(Self {inner: register(sync)?})
+    }
+    /// Unregisters the socket from Tokio's reactor and puts it back into blocking mode.
+    #[inline]
+    pub fn into_sync(self) -> io::Result<SyncUdSocket> {
+        unregister(self.inner)
+    }
+}
+impl Debug for UdSocket {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UdSocket")
+            .field("file_descriptor", &self.as_raw_fd())
+            .finish()
+    }
+}
+impl AsRawFd for UdSocket {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Read, Write};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use super::*;
+    use crate::{os::unix::get_nonblocking, rand_string};
+
+    #[tokio::test]
+    async fn stream_conversion() {
+        let path = format!("/tmp/{}.sock", rand_string(10));
+        let listener = UdStreamListener::bind(path.clone()).unwrap();
+        let mut client = UdStream::connect(path).await.unwrap();
+        let mut server = listener.accept().await.unwrap();
+        client.write_all(b"async").await.unwrap();
+        let mut buffer = [0; 5];
+        server.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, b"async");
+
+        let listener = listener.into_sync().unwrap();
+        assert!(!get_nonblocking(listener.as_raw_fd()).unwrap());
+        UdStreamListener::from_sync(listener).unwrap();
+
+        let mut client = client.into_sync().unwrap();
+        let mut server = server.into_sync().unwrap();
+        assert!(!get_nonblocking(client.as_raw_fd()).unwrap());
+        client.write_all(b"sync!").unwrap();
+        server.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"sync!");
+
+        let (left, right) = SyncUdStream::pair().unwrap();
+        let mut left = UdStream::from_sync(left).unwrap();
+        let mut right = UdStream::from_sync(right).unwrap();
+        left.write_all(b"again").await.unwrap();
+        right.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, b"again");
+    }
+    #[tokio::test]
+    #[cfg(target_os = "linux")]
+    async fn connect_full_backlog() {
+        use super::super::UdStreamListenerOptions;
+
+        let path = format!("/tmp/{}.sock", rand_string(10));
+        let listener = UdStreamListenerOptions::new().backlog(0).create(path.clone()).unwrap();
+        // Fill the backlog without blocking the test's only thread.
+        let socket_path = path.clone().to_socket_path().unwrap();
+        let mut queued = Vec::new();
+        loop {
+            let stream = SyncUdStream::new_nonblocking().unwrap();
+            match stream.connect_nonblocking(&socket_path) {
+                Ok(true) => queued.push(stream),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                other => panic!("unexpected connection result: {:?}", other),
+            }
+        }
+        let connect = tokio::spawn(UdStream::connect(path));
+        for _ in 0..16 {
+            tokio::task::yield_now().await;
+        }
+        assert!(!connect.is_finished());
+        for _ in &queued {
+            listener.accept().unwrap();
+        }
+        let mut client = connect.await.unwrap().unwrap();
+        let mut server = listener.accept().unwrap();
+        client.write_all(b"room").await.unwrap();
+        let mut buffer = [0; 4];
+        server.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"room");
+    }
+    #[tokio::test]
+    async fn socket_conversion() {
+        let (left, right) = SyncUdSocket::pair().unwrap();
+        let left = UdSocket::from_sync(left).unwrap();
+        let right = UdSocket::from_sync(right).unwrap();
+        left.send(b"async").await.unwrap();
+        let mut buffer = [0; 5];
+        assert_eq!(right.recv(&mut buffer).await.unwrap().0, 5);
+        assert_eq!(&buffer, b"async");
+
+        let left = left.into_sync().unwrap();
+        let right = right.into_sync().unwrap();
+        assert!(!get_nonblocking(left.as_raw_fd()).unwrap());
+        left.send(b"sync!").unwrap();
+        right.recv(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"sync!");
+
+        let left = UdSocket::from_sync(left).unwrap();
+        let right = UdSocket::from_sync(right).unwrap();
+        left.send(b"again").await.unwrap();
+        right.recv(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, b"again");
+    }
+}
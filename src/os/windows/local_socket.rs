@@ -4,39 +4,80 @@ use std::{
     ffi::{OsStr, OsString, c_void},
     //path::{Path, PathBuf},
     borrow::Cow,
+    net::Shutdown,
+    process,
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
     os::windows::io::{AsRawHandle, IntoRawHandle, FromRawHandle},
 };
 use crate::local_socket::{
     NameTypeSupport,
     LocalSocketName,
+    LocalSocketListenerOptions,
     ToLocalSocketName,
 };
+use winapi::um::{
+    namedpipeapi::SetNamedPipeHandleState,
+    winbase::PIPE_READMODE_MESSAGE,
+};
+use crate::{ReliableReadMsg, WriteMsg, Sealed};
 use super::named_pipe::{
     PipeListener as GenericPipeListener,
     DuplexBytePipeStream as PipeStream,
+    DuplexMsgPipeStream as MsgPipeStream,
     PipeListenerOptions,
     PipeMode,
 };
 
 type PipeListener = GenericPipeListener<PipeStream>;
+type MsgPipeListener = GenericPipeListener<MsgPipeStream>;
 
 pub struct LocalSocketListener {
     inner: PipeListener,
+    // Named pipes can't be asked for their name, so we remember it ourselves.
+    name: OsString,
 }
 impl LocalSocketListener {
     #[inline]
     pub fn bind<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
-        let name = name.to_local_socket_name()?;
+        let name = name.to_local_socket_name()?.into_inner();
         let inner = PipeListenerOptions::new()
-            .name(name.into_inner())
+            .name(name.clone())
             .mode(PipeMode::Bytes)
             .create()?;
-        Ok(Self {inner})
+        Ok(Self {inner, name})
+    }
+    #[inline(always)]
+    pub fn bind_reclaiming<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        // Named pipes disappear as soon as the last handle to them is closed, so there is never
+        // anything to reclaim.
+        Self::bind(name)
+    }
+    #[inline]
+    pub fn create<'a>(name: impl ToLocalSocketName<'a>, options: &LocalSocketListenerOptions) -> io::Result<Self> {
+        // The backlog has no counterpart for named pipes, and neither does reclaiming.
+        if options.nonblocking {
+            return Err(unsupported("non-blocking mode"));
+        }
+        Self::bind(name)
     }
     #[inline(always)]
     pub fn accept(&self) -> io::Result<LocalSocketStream> {
         let inner = self.inner.accept()?;
-        Ok(LocalSocketStream {inner})
+        Ok(LocalSocketStream {inner, local_name: Some(self.name.clone()), peer_name: None})
+    }
+    #[inline(always)]
+    pub fn accept_timeout(&self, _timeout: Duration) -> io::Result<LocalSocketStream> {
+        Err(unsupported("the accept timeout"))
+    }
+    #[inline(always)]
+    pub fn set_nonblocking(&self, _nonblocking: bool) -> io::Result<()> {
+        Err(unsupported("non-blocking mode"))
+    }
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<LocalSocketName<'static>> {
+        Ok(LocalSocketName::from_raw_parts(Cow::Owned(self.name.clone()), true))
     }
 }
 impl Debug for LocalSocketListener {
@@ -48,36 +89,123 @@ impl Debug for LocalSocketListener {
 
 pub struct LocalSocketStream {
     inner: PipeStream,
+    // Not queryable either. The server side has the name of the pipe as its local name and the client
+    // side has it as its peer name, mirroring what accepting and connecting do on Unix.
+    local_name: Option<OsString>,
+    peer_name: Option<OsString>,
 }
 impl LocalSocketStream {
     pub fn connect<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
-        let name = name.to_local_socket_name()?;
-        let inner = PipeStream::connect(name.inner())?;
-        Ok(Self {inner})
+        let name = name.to_local_socket_name()?.into_inner();
+        let inner = PipeStream::connect(&name)?;
+        Ok(Self {inner, local_name: None, peer_name: Some(name)})
+    }
+    pub fn pair() -> io::Result<(Self, Self)> {
+        // Named pipes can't be anonymous and duplex at the same time, so we create one with a
+        // unique name, connect to it right away and let the name go.
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let name = OsString::from(format!(
+            "interprocess-pair-{}-{}",
+            process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed),
+        ));
+        let listener: PipeListener = PipeListenerOptions::new()
+            .name(name.clone())
+            .mode(PipeMode::Bytes)
+            .create()?;
+        let client = PipeStream::connect(&name)?;
+        let server = listener.accept()?;
+        // The name is released right away, so the pair is unnamed, just like on Unix.
+        Ok((
+            Self {inner: server, local_name: None, peer_name: None},
+            Self {inner: client, local_name: None, peer_name: None},
+        ))
+    }
+    #[inline(always)]
+    pub fn set_nonblocking(&self, _nonblocking: bool) -> io::Result<()> {
+        Err(unsupported("non-blocking mode"))
+    }
+    #[inline(always)]
+    pub fn set_read_timeout(&self, _timeout: Option<Duration>) -> io::Result<()> {
+        Err(unsupported("the read timeout"))
+    }
+    #[inline(always)]
+    pub fn set_write_timeout(&self, _timeout: Option<Duration>) -> io::Result<()> {
+        Err(unsupported("the write timeout"))
+    }
+    #[inline(always)]
+    pub fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        Ok(None)
+    }
+    #[inline(always)]
+    pub fn write_timeout(&self) -> io::Result<Option<Duration>> {
+        Ok(None)
+    }
+    #[inline(always)]
+    pub fn shutdown(&self, _how: Shutdown) -> io::Result<()> {
+        Err(unsupported("shutting down one direction of the connection"))
+    }
+    #[inline(always)]
+    pub fn try_clone(&self) -> io::Result<Self> {
+        // Dropping a named pipe stream disconnects the pipe, so a second owner of the same
+        // instance would cut the connection off for the first one.
+        Err(unsupported("cloning"))
+    }
+    #[inline]
+    pub fn local_addr(&self) -> io::Result<Option<LocalSocketName<'static>>> {
+        Ok(self.local_name.clone().map(|name| LocalSocketName::from_raw_parts(Cow::Owned(name), true)))
+    }
+    #[inline]
+    pub fn peer_addr(&self) -> io::Result<Option<LocalSocketName<'static>>> {
+        Ok(self.peer_name.clone().map(|name| LocalSocketName::from_raw_parts(Cow::Owned(name), true)))
+    }
+}
+impl Read for &LocalSocketStream {
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&self.inner).read(buf)
+    }
+    #[inline(always)]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (&self.inner).read_vectored(bufs)
+    }
+}
+impl Write for &LocalSocketStream {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&self.inner).write(buf)
+    }
+    #[inline(always)]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (&self.inner).write_vectored(bufs)
+    }
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        (&self.inner).flush()
     }
 }
 impl Read for LocalSocketStream {
     #[inline(always)]
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        self.inner.read(buf)
+        (&*self).read(buf)
     }
     #[inline(always)]
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
-        self.inner.read_vectored(bufs)
+        (&*self).read_vectored(bufs)
     }
 }
 impl Write for LocalSocketStream {
     #[inline(always)]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.inner.write(buf)
+        (&*self).write(buf)
     }
     #[inline(always)]
     fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
-        self.inner.write_vectored(bufs)
+        (&*self).write_vectored(bufs)
     }
     #[inline(always)]
     fn flush(&mut self) -> io::Result<()> {
-        self.inner.flush()
+        (&*self).flush()
     }
 }
 impl Debug for LocalSocketStream {
@@ -103,10 +231,134 @@ impl IntoRawHandle for LocalSocketStream {
 impl FromRawHandle for LocalSocketStream {
     #[inline(always)]
     unsafe fn from_raw_handle(handle: *mut c_void) -> Self {
-        Self {inner: PipeStream::from_raw_handle(handle)}
+        Self {inner: PipeStream::from_raw_handle(handle), local_name: None, peer_name: None}
+    }
+}
+
+pub struct LocalSocketMsgListener {
+    inner: MsgPipeListener,
+}
+impl LocalSocketMsgListener {
+    #[inline]
+    pub fn bind<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        let name = name.to_local_socket_name()?;
+        let inner = PipeListenerOptions::new()
+            .name(name.into_inner())
+            .mode(PipeMode::Messages)
+            .create()?;
+        Ok(Self {inner})
+    }
+    #[inline(always)]
+    pub fn bind_reclaiming<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        // Same as with byte streams, there is never anything to reclaim.
+        Self::bind(name)
+    }
+    #[inline(always)]
+    pub fn accept(&self) -> io::Result<LocalSocketMsgStream> {
+        let inner = self.inner.accept()?;
+        Ok(LocalSocketMsgStream {inner})
+    }
+    #[inline(always)]
+    pub fn set_nonblocking(&self, _nonblocking: bool) -> io::Result<()> {
+        Err(unsupported("non-blocking mode"))
+    }
+}
+impl Debug for LocalSocketMsgListener {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("LocalSocketMsgListener")
+    }
+}
+
+pub struct LocalSocketMsgStream {
+    inner: MsgPipeStream,
+}
+impl LocalSocketMsgStream {
+    pub fn connect<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        let name = name.to_local_socket_name()?;
+        let inner = MsgPipeStream::connect(name.inner())?;
+        // Clients start out in byte read mode regardless of the pipe type, which would make reads
+        // run across message boundaries.
+        let mut mode = PIPE_READMODE_MESSAGE;
+        let success = unsafe {
+            SetNamedPipeHandleState(
+                inner.as_raw_handle(),
+                &mut mode as *mut _,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            ) != 0
+        };
+        if success {
+            Ok(Self {inner})
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+    #[inline(always)]
+    pub fn set_nonblocking(&self, _nonblocking: bool) -> io::Result<()> {
+        Err(unsupported("non-blocking mode"))
+    }
+    #[inline(always)]
+    pub fn set_read_timeout(&self, _timeout: Option<Duration>) -> io::Result<()> {
+        Err(unsupported("the read timeout"))
+    }
+    #[inline(always)]
+    pub fn set_write_timeout(&self, _timeout: Option<Duration>) -> io::Result<()> {
+        Err(unsupported("the write timeout"))
+    }
+}
+impl ReliableReadMsg for LocalSocketMsgStream {
+    #[inline(always)]
+    fn read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, Vec<u8>>> {
+        self.inner.read_msg(buf)
+    }
+    #[inline(always)]
+    fn try_read_msg(&mut self, buf: &mut [u8]) -> io::Result<Result<usize, usize>> {
+        self.inner.try_read_msg(buf)
+    }
+}
+impl WriteMsg for LocalSocketMsgStream {
+    #[inline(always)]
+    fn write_msg(&mut self, msg: &[u8]) -> io::Result<()> {
+        self.inner.write_msg(msg)
+    }
+}
+impl Sealed for LocalSocketMsgStream {}
+impl Debug for LocalSocketMsgStream {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSocketMsgStream")
+            .field("handle", &self.as_raw_handle())
+            .finish()
+    }
+}
+impl AsRawHandle for LocalSocketMsgStream {
+    #[inline(always)]
+    fn as_raw_handle(&self) -> *mut c_void {
+        self.inner.as_raw_handle()
+    }
+}
+impl IntoRawHandle for LocalSocketMsgStream {
+    #[inline(always)]
+    fn into_raw_handle(self) -> *mut c_void {
+        self.inner.into_raw_handle()
+    }
+}
+impl FromRawHandle for LocalSocketMsgStream {
+    #[inline(always)]
+    unsafe fn from_raw_handle(handle: *mut c_void) -> Self {
+        Self {inner: MsgPipeStream::from_raw_handle(handle)}
     }
 }
 
+/// Named pipes in byte mode only support blocking operations without timeouts, at least without switching to overlapped I/O.
+pub(crate) fn unsupported(what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        format!("{} is not supported for local sockets on Windows", what),
+    )
+}
+
 pub const NAME_TYPE_ALWAYS_SUPPORTED: NameTypeSupport = NameTypeSupport::OnlyNamespaced;
 
 #[inline(always)]
@@ -6,6 +6,10 @@ pub mod unnamed_pipe;
 //pub mod mailslot;
 #[cfg(windows)]
 pub(crate) mod local_socket;
+#[cfg(all(windows, feature = "nonblocking"))]
+pub(crate) mod nonblocking;
+#[cfg(all(windows, feature = "tokio"))]
+pub(crate) mod tokio;
 
 #[cfg(windows)]
 use winapi::{
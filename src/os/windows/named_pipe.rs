@@ -70,7 +70,7 @@ fake_consts! {
 pub type DWORD = u32;
 use crate::{
     Sealed,
-    ReliableReadMsg, PartialMsgWriteError,
+    ReliableReadMsg, WriteMsg, PartialMsgWriteError,
 };
 
 fn convert_path(osstr: &OsStr) -> Vec<u16> {
@@ -266,7 +266,7 @@ mod seal {
                         ReadFile(
                             self.as_raw_handle(),
                             new_buffer.as_mut_slice().as_mut_ptr() as *mut _,
-                            buf.len() as DWORD,
+                            new_buffer.len() as DWORD,
                             &mut _number_of_bytes_read as *mut _,
                             zeroed(),
                         ) != 0
@@ -778,6 +778,23 @@ impl Write for DuplexBytePipeStream {
         self.instance.0.flush()
     }
 }
+/// Allows a stream shared between threads to be read from and written into at the same time. Keep in mind that Windows serializes I/O on a synchronous pipe handle, so a read which is waiting for data blocks writes from other threads until it completes.
+impl Read for &DuplexBytePipeStream {
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.instance.0.read_bytes(buf)
+    }
+}
+impl Write for &DuplexBytePipeStream {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.instance.0.write(buf)
+    }
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        self.instance.0.flush()
+    }
+}
 
 impl Read for MsgReaderPipeStream {
     #[inline(always)]
@@ -796,6 +813,12 @@ impl ReliableReadMsg for MsgReaderPipeStream {
     }
 }
 
+impl WriteMsg for MsgWriterPipeStream {
+    #[inline(always)]
+    fn write_msg(&mut self, msg: &[u8]) -> io::Result<()> {
+        self.write(msg).map(|_| ())
+    }
+}
 impl Write for MsgWriterPipeStream {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
@@ -828,6 +851,12 @@ impl ReliableReadMsg for DuplexMsgPipeStream {
         self.instance.0.try_read_msg(buf)
     }
 }
+impl WriteMsg for DuplexMsgPipeStream {
+    #[inline(always)]
+    fn write_msg(&mut self, msg: &[u8]) -> io::Result<()> {
+        self.write(msg).map(|_| ())
+    }
+}
 impl Write for DuplexMsgPipeStream {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
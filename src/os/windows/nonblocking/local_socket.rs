@@ -0,0 +1,191 @@
+use blocking::{unblock, Unblock};
+use futures::{
+    io::{self, AsyncReadExt},
+    stream::Stream,
+    task::{Context, Poll},
+    AsyncRead, AsyncWrite,
+};
+use std::{
+    io::{IoSlice, IoSliceMut},
+    net::Shutdown,
+    pin::Pin,
+    sync::Arc,
+};
+
+use crate::local_socket::{self as sync, ToLocalSocketName};
+use super::super::local_socket::unsupported;
+
+#[derive(Debug)]
+pub struct LocalSocketListener {
+    inner: Arc<sync::LocalSocketListener>,
+}
+impl LocalSocketListener {
+    #[inline]
+    pub async fn bind(name: impl ToLocalSocketName<'_> + Send + 'static) -> io::Result<Self> {
+        Ok(Self {
+            inner: Arc::new(unblock(move || sync::LocalSocketListener::bind(name)).await?),
+        })
+    }
+    #[inline]
+    pub async fn accept(&self) -> io::Result<LocalSocketStream> {
+        let s = Arc::clone(&self.inner);
+        Ok(LocalSocketStream {
+            inner: Unblock::new(unblock(move || s.accept()).await?),
+        })
+    }
+    #[inline]
+    pub fn incoming(&self) -> Incoming {
+        Incoming {
+            inner: Unblock::new(SyncArcIncoming {
+                inner: Arc::clone(&self.inner),
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Incoming {
+    inner: Unblock<SyncArcIncoming>,
+}
+impl Stream for Incoming {
+    type Item = io::Result<LocalSocketStream>;
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(ctx).map(|val| {
+            val.map(|val| {
+                val.map(|inner| LocalSocketStream {
+                    inner: Unblock::new(inner),
+                })
+            })
+        })
+    }
+}
+
+#[derive(Debug)]
+struct SyncArcIncoming {
+    inner: Arc<sync::LocalSocketListener>,
+}
+impl Iterator for SyncArcIncoming {
+    type Item = io::Result<sync::LocalSocketStream>;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.inner.accept())
+    }
+}
+
+#[derive(Debug)]
+pub struct LocalSocketStream {
+    inner: Unblock<sync::LocalSocketStream>,
+}
+impl LocalSocketStream {
+    #[inline]
+    pub async fn connect<'a>(name: impl ToLocalSocketName<'a> + Send + 'static) -> io::Result<Self> {
+        Ok(Self {
+            inner: Unblock::new(unblock(move || sync::LocalSocketStream::connect(name)).await?),
+        })
+    }
+    #[inline(always)]
+    pub fn shutdown(&self, _how: Shutdown) -> io::Result<()> {
+        Err(unsupported("shutting down one direction of the connection"))
+    }
+    #[inline]
+    pub fn into_split(self) -> (ReadHalf, WriteHalf) {
+        // Unblock needs exclusive access to the stream to hand it to the thread pool, so the
+        // halves have to take turns through a lock.
+        let (read_half, write_half) = AsyncReadExt::split(self);
+        (ReadHalf {inner: read_half}, WriteHalf {inner: write_half})
+    }
+}
+
+impl AsyncRead for LocalSocketStream {
+    #[inline(always)]
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        AsyncRead::poll_read(Pin::new(&mut self.inner), cx, buf)
+    }
+    #[inline(always)]
+    fn poll_read_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<usize>> {
+        AsyncRead::poll_read_vectored(Pin::new(&mut self.inner), cx, bufs)
+    }
+}
+impl AsyncWrite for LocalSocketStream {
+    #[inline(always)]
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        AsyncWrite::poll_write(Pin::new(&mut self.inner), cx, buf)
+    }
+    #[inline(always)]
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        AsyncWrite::poll_write_vectored(Pin::new(&mut self.inner), cx, bufs)
+    }
+    #[inline(always)]
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_flush(Pin::new(&mut self.inner), cx)
+    }
+    #[inline(always)]
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_close(Pin::new(&mut self.inner), cx)
+    }
+}
+
+#[derive(Debug)]
+pub struct ReadHalf {
+    inner: io::ReadHalf<LocalSocketStream>,
+}
+impl ReadHalf {
+    #[inline]
+    pub fn reunite(self, write_half: WriteHalf) -> Result<LocalSocketStream, (Self, WriteHalf)> {
+        self.inner.reunite(write_half.inner)
+            .map_err(|io::ReuniteError(read_half, write_half)| (
+                Self {inner: read_half},
+                WriteHalf {inner: write_half},
+            ))
+    }
+}
+impl AsyncRead for ReadHalf {
+    #[inline(always)]
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        AsyncRead::poll_read(Pin::new(&mut self.inner), cx, buf)
+    }
+    #[inline(always)]
+    fn poll_read_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<usize>> {
+        AsyncRead::poll_read_vectored(Pin::new(&mut self.inner), cx, bufs)
+    }
+}
+
+#[derive(Debug)]
+pub struct WriteHalf {
+    inner: io::WriteHalf<LocalSocketStream>,
+}
+impl AsyncWrite for WriteHalf {
+    #[inline(always)]
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        AsyncWrite::poll_write(Pin::new(&mut self.inner), cx, buf)
+    }
+    #[inline(always)]
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        AsyncWrite::poll_write_vectored(Pin::new(&mut self.inner), cx, bufs)
+    }
+    #[inline(always)]
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_flush(Pin::new(&mut self.inner), cx)
+    }
+    #[inline(always)]
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_close(Pin::new(&mut self.inner), cx)
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    io,
+    ffi::{OsStr, OsString},
+    fmt::{self, Formatter, Debug},
+    mem,
+    net::Shutdown,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    sync::Mutex,
+    net::windows::named_pipe::{
+        ClientOptions, NamedPipeClient, NamedPipeServer, PipeMode, ServerOptions,
+    },
+};
+use crate::local_socket::ToLocalSocketName;
+use super::super::local_socket::unsupported;
+
+fn convert_path(osstr: &OsStr) -> OsString {
+    let mut path = OsString::from(r"\\.\pipe\");
+    path.push(osstr);
+    path
+}
+
+pub struct LocalSocketListener {
+    name: OsString,
+    // The instance which is waiting for the next client. Stays in place while an accept operation
+    // is in progress and is only replaced once a client has connected to it.
+    instance: Mutex<NamedPipeServer>,
+}
+impl LocalSocketListener {
+    #[inline]
+    pub fn bind<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        let name = convert_path(name.to_local_socket_name()?.inner());
+        let instance = ServerOptions::new()
+            .first_pipe_instance(true)
+            .pipe_mode(PipeMode::Byte)
+            .create(&name)?;
+        Ok(Self {
+            name,
+            instance: Mutex::new(instance),
+        })
+    }
+    pub async fn accept(&self) -> io::Result<LocalSocketStream> {
+        let mut instance = self.instance.lock().await;
+        // If this future is dropped while waiting, the instance, along with a client which might
+        // have connected to it in the meantime, is picked up by the next call.
+        if let Err(error) = instance.connect().await {
+            // The instance can't be reused after a failed connection attempt.
+            *instance = self.create_instance()?;
+            return Err(error);
+        }
+        // Have the next instance ready before returning so that clients don't get a
+        // "file not found" error while nobody is accepting. If that fails, the connected instance
+        // stays in place and is returned by the next call instead.
+        let next = self.create_instance()?;
+        let connected = mem::replace(&mut *instance, next);
+        Ok(LocalSocketStream {inner: PipeStream::Server(connected)})
+    }
+    #[inline]
+    fn create_instance(&self) -> io::Result<NamedPipeServer> {
+        ServerOptions::new()
+            .pipe_mode(PipeMode::Byte)
+            .create(&self.name)
+    }
+}
+impl Debug for LocalSocketListener {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSocketListener")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+enum PipeStream {
+    Server(NamedPipeServer),
+    Client(NamedPipeClient),
+}
+
+#[derive(Debug)]
+pub struct LocalSocketStream {
+    inner: PipeStream,
+}
+impl LocalSocketStream {
+    #[inline]
+    pub async fn connect<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        let name = convert_path(name.to_local_socket_name()?.inner());
+        let client = ClientOptions::new().open(name)?;
+        Ok(Self {inner: PipeStream::Client(client)})
+    }
+    #[inline(always)]
+    pub fn shutdown(&self, _how: Shutdown) -> io::Result<()> {
+        Err(unsupported("shutting down one direction of the connection"))
+    }
+}
+impl AsyncRead for LocalSocketStream {
+    #[inline]
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        match &mut self.inner {
+            PipeStream::Server(s) => AsyncRead::poll_read(Pin::new(s), cx, buf),
+            PipeStream::Client(s) => AsyncRead::poll_read(Pin::new(s), cx, buf),
+        }
+    }
+}
+impl AsyncWrite for LocalSocketStream {
+    #[inline]
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        match &mut self.inner {
+            PipeStream::Server(s) => AsyncWrite::poll_write(Pin::new(s), cx, buf),
+            PipeStream::Client(s) => AsyncWrite::poll_write(Pin::new(s), cx, buf),
+        }
+    }
+    #[inline]
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match &mut self.inner {
+            PipeStream::Server(s) => AsyncWrite::poll_flush(Pin::new(s), cx),
+            PipeStream::Client(s) => AsyncWrite::poll_flush(Pin::new(s), cx),
+        }
+    }
+    #[inline]
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match &mut self.inner {
+            PipeStream::Server(s) => AsyncWrite::poll_shutdown(Pin::new(s), cx),
+            PipeStream::Client(s) => AsyncWrite::poll_shutdown(Pin::new(s), cx),
+        }
+    }
+}
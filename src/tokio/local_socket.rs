@@ -0,0 +1,260 @@
+//! Tokio-based local sockets.
+//!
+//! See the [blocking version of this module] for more on what those are.
+//!
+//! [blocking version of this module]: ../../local_socket/index.html " "
+
+use std::{
+    io,
+    fmt::{self, Formatter, Debug},
+    net::Shutdown,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::local_socket::{self as sync, ToLocalSocketName};
+
+impmod!{tokio::local_socket,
+    LocalSocketListener as LocalSocketListenerImpl,
+    LocalSocketStream as LocalSocketStreamImpl,
+}
+
+/// A Tokio-based local socket server, listening for connections.
+///
+/// # Example
+/// ```no_run
+/// use interprocess::tokio::local_socket::LocalSocketListener;
+/// use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let listener = LocalSocketListener::bind("/tmp/example.sock")?;
+/// loop {
+///     let mut conn = listener.accept().await?;
+///     tokio::spawn(async move {
+///         conn.write_all(b"Hello from server!\n").await?;
+///         let mut conn = BufReader::new(conn);
+///         let mut buffer = String::new();
+///         conn.read_line(&mut buffer).await?;
+///         println!("Client answered: {}", buffer);
+///         Ok::<_, std::io::Error>(())
+///     });
+/// }
+/// # }
+/// ```
+pub struct LocalSocketListener {
+    inner: LocalSocketListenerImpl,
+}
+impl LocalSocketListener {
+    /// Creates a socket server with the specified local socket name.
+    ///
+    /// # Panics
+    /// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+    #[inline]
+    pub fn bind<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        Ok(Self {inner: LocalSocketListenerImpl::bind(name)?})
+    }
+    /// Listens for incoming connections to the socket, asynchronously waiting until a client is connected.
+    ///
+    /// This operation is cancellation-safe: if the future is dropped before it completes, no incoming connection is lost.
+    #[inline]
+    pub async fn accept(&self) -> io::Result<LocalSocketStream> {
+        Ok(LocalSocketStream {inner: self.inner.accept().await?})
+    }
+    /// Registers a blocking local socket server with the Tokio runtime.
+    ///
+    /// Only available on Unix, since named pipes need to be opened in overlapped mode to be used with Tokio on Windows, which the blocking local sockets don't do.
+    ///
+    /// # Panics
+    /// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+    #[cfg(unix)]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+    #[inline]
+    pub fn from_sync(sync: sync::LocalSocketListener) -> io::Result<Self> {
+        Ok(Self {inner: LocalSocketListenerImpl::from_sync(sync.inner)?})
+    }
+    /// Unregisters the local socket server from the Tokio runtime and converts it into a blocking one.
+    ///
+    /// Only available on Unix, for the same reason as [`from_sync`].
+    ///
+    /// [`from_sync`]: #method.from_sync " "
+    #[cfg(unix)]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+    #[inline]
+    pub fn into_sync(self) -> io::Result<sync::LocalSocketListener> {
+        Ok(sync::LocalSocketListener {inner: self.inner.into_sync()?})
+    }
+}
+impl Debug for LocalSocketListener {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+
+/// A Tokio-based local socket byte stream, obtained eiter from [`LocalSocketListener`] or by connecting to an existing local socket.
+///
+/// # Example
+/// ```no_run
+/// use interprocess::tokio::local_socket::LocalSocketStream;
+/// use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // Replace the path as necessary on Windows.
+/// let mut conn = LocalSocketStream::connect("/tmp/example.sock").await?;
+/// conn.write_all(b"Hello from client!\n").await?;
+/// let mut conn = BufReader::new(conn);
+/// let mut buffer = String::new();
+/// conn.read_line(&mut buffer).await?;
+/// println!("Server answered: {}", buffer);
+/// # Ok(()) }
+/// ```
+///
+/// [`LocalSocketListener`]: struct.LocalSocketListener.html " "
+pub struct LocalSocketStream {
+    inner: LocalSocketStreamImpl,
+}
+impl LocalSocketStream {
+    /// Connects to a remote local socket server.
+    ///
+    /// # Panics
+    /// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+    #[inline]
+    pub async fn connect<'a>(name: impl ToLocalSocketName<'a>) -> io::Result<Self> {
+        Ok(Self {inner: LocalSocketStreamImpl::connect(name).await?})
+    }
+    /// Shuts down the read half, the write half or both halves of the stream. See [the synchronous version] for the details.
+    ///
+    /// Shutting down the stream via [`AsyncWriteExt::shutdown`] shuts down the write half as well.
+    ///
+    /// # Platform-specific behavior
+    /// Not supported on Windows, where an error of kind [`Other`] is always returned and `AsyncWriteExt::shutdown` has no effect.
+    ///
+    /// [the synchronous version]: ../../local_socket/struct.LocalSocketStream.html#method.shutdown " "
+    /// [`AsyncWriteExt::shutdown`]: https://docs.rs/tokio/*/tokio/io/trait.AsyncWriteExt.html#method.shutdown " "
+    /// [`Other`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Other " "
+    #[inline(always)]
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        self.inner.shutdown(how)
+    }
+    /// Registers a blocking local socket stream with the Tokio runtime.
+    ///
+    /// Only available on Unix, since named pipes need to be opened in overlapped mode to be used with Tokio on Windows, which the blocking local sockets don't do.
+    ///
+    /// # Panics
+    /// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+    #[cfg(unix)]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+    #[inline]
+    pub fn from_sync(sync: sync::LocalSocketStream) -> io::Result<Self> {
+        Ok(Self {inner: LocalSocketStreamImpl::from_sync(sync.inner)?})
+    }
+    /// Unregisters the local socket stream from the Tokio runtime and converts it into a blocking one.
+    ///
+    /// Only available on Unix, for the same reason as [`from_sync`].
+    ///
+    /// [`from_sync`]: #method.from_sync " "
+    #[cfg(unix)]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+    #[inline]
+    pub fn into_sync(self) -> io::Result<sync::LocalSocketStream> {
+        Ok(sync::LocalSocketStream {inner: self.inner.into_sync()?})
+    }
+}
+impl AsyncRead for LocalSocketStream {
+    #[inline(always)]
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        AsyncRead::poll_read(Pin::new(&mut self.inner), cx, buf)
+    }
+}
+impl AsyncWrite for LocalSocketStream {
+    #[inline(always)]
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        AsyncWrite::poll_write(Pin::new(&mut self.inner), cx, buf)
+    }
+    #[inline(always)]
+    fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_flush(Pin::new(&mut self.inner), cx)
+    }
+    #[inline(always)]
+    fn poll_shutdown(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        AsyncWrite::poll_shutdown(Pin::new(&mut self.inner), cx)
+    }
+}
+impl Debug for LocalSocketStream {
+    #[inline(always)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use super::{LocalSocketListener, LocalSocketStream};
+    use crate::rand_string;
+
+    #[tokio::test]
+    async fn basic() {
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = LocalSocketListener::bind(socket_name.clone()).unwrap();
+
+        let client = tokio::spawn(async move {
+            let mut conn = LocalSocketStream::connect(socket_name).await.unwrap();
+            conn.write_all(b"Hello from client!\n").await.unwrap();
+            let mut conn = BufReader::new(conn);
+            let mut buffer = String::new();
+            conn.read_line(&mut buffer).await.unwrap();
+            assert_eq!(buffer, "Hello from server!\n");
+        });
+
+        let mut conn = BufReader::new(listener.accept().await.unwrap());
+        let mut buffer = String::new();
+        conn.read_line(&mut buffer).await.unwrap();
+        assert_eq!(buffer, "Hello from client!\n");
+        conn.get_mut().write_all(b"Hello from server!\n").await.unwrap();
+        client.await.unwrap();
+    }
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn conversion() {
+        use std::{io::{Read, Write}, os::unix::io::AsRawFd};
+        use crate::{local_socket as sync, os::unix::get_nonblocking};
+
+        let socket_name = format!("/tmp/{}.sock", rand_string(10));
+        let listener = LocalSocketListener::bind(socket_name).unwrap();
+        let listener = listener.into_sync().unwrap();
+        LocalSocketListener::from_sync(listener).unwrap();
+
+        let (left, right) = sync::LocalSocketStream::pair().unwrap();
+        let mut left = LocalSocketStream::from_sync(left).unwrap();
+        let mut right = LocalSocketStream::from_sync(right).unwrap();
+        left.write_all(b"async").await.unwrap();
+        let mut buffer = [0; 5];
+        right.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, b"async");
+
+        let mut left = left.into_sync().unwrap();
+        let mut right = right.into_sync().unwrap();
+        assert!(!get_nonblocking(left.as_raw_fd()).unwrap());
+        left.write_all(b"sync!").unwrap();
+        right.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"sync!");
+    }
+}
@@ -0,0 +1,15 @@
+//! Tokio-based asynchronous versions of the interprocess communication primitives.
+//!
+//! Unlike the runtime-agnostic [`nonblocking`] module, the types here are registered directly with the reactor of the Tokio runtime they are created in and implement Tokio's own [`AsyncRead`]/[`AsyncWrite`] traits, so they have to be created from within a Tokio runtime with I/O enabled. Each of them can be converted to and from its blocking counterpart with `from_sync` and `into_sync`, except for local sockets on Windows, where the blocking named pipes aren't opened in the overlapped mode which Tokio requires.
+//!
+//! The layout of this module mirrors the crate root. The Tokio versions of the Unix-specific Ud-socket types can be found in [`os::unix::udsocket::tokio`].
+//!
+//! [`nonblocking`]: ../nonblocking/index.html " "
+//! [`AsyncRead`]: https://docs.rs/tokio/*/tokio/io/trait.AsyncRead.html " "
+//! [`AsyncWrite`]: https://docs.rs/tokio/*/tokio/io/trait.AsyncWrite.html " "
+//! [`os::unix::udsocket::tokio`]: ../os/unix/udsocket/tokio/index.html " "
+
+pub mod local_socket;
+#[cfg(unix)]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(unix)))]
+pub mod unnamed_pipe;
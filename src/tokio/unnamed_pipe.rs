@@ -0,0 +1,186 @@
+//! Tokio-based unnamed pipes.
+//!
+//! Only available on Unix, since anonymous pipes on Windows do not support overlapped I/O and thus cannot be registered with an I/O completion port. See the [blocking version of this module] for more on unnamed pipes.
+//!
+//! [blocking version of this module]: ../../unnamed_pipe/index.html " "
+
+use std::{
+    io::{self, Read, Write},
+    fmt::{self, Formatter, Debug},
+    os::unix::io::{AsRawFd, RawFd},
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::{unix::AsyncFd, AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::{
+    os::unix::tokio::{register, unregister},
+    unnamed_pipe as sync,
+};
+
+/// Creates a new pipe with the default creation settings, registers both of its ends with the Tokio runtime and returns the handles to its writing end and reading end.
+///
+/// # Panics
+/// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+#[inline]
+pub fn pipe() -> io::Result<(UnnamedPipeWriter, UnnamedPipeReader)> {
+    let (writer, reader) = sync::pipe()?;
+    Ok((UnnamedPipeWriter::from_sync(writer)?, UnnamedPipeReader::from_sync(reader)?))
+}
+
+/// A Tokio-based handle to the reading end of an unnamed pipe, created by the [`pipe`] function together with the [writing end].
+///
+/// [`pipe`]: fn.pipe.html " "
+/// [writing end]: struct.UnnamedPipeWriter.html " "
+pub struct UnnamedPipeReader {
+    inner: AsyncFd<sync::UnnamedPipeReader>,
+}
+impl UnnamedPipeReader {
+    /// Registers the reading end of a blocking unnamed pipe with the Tokio runtime, putting it into non-blocking mode.
+    ///
+    /// # Panics
+    /// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+    #[inline]
+    pub fn from_sync(sync: sync::UnnamedPipeReader) -> io::Result<Self> {
+        Ok(Self {inner: register(sync)?})
+    }
+    /// Unregisters the reading end from the Tokio runtime and puts it back into blocking mode.
+    #[inline]
+    pub fn into_sync(self) -> io::Result<sync::UnnamedPipeReader> {
+        unregister(self.inner)
+    }
+}
+impl AsyncRead for UnnamedPipeReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            let mut guard = match self.inner.poll_read_ready_mut(cx) {
+                Poll::Ready(Ok(guard)) => guard,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+            let unfilled = buf.initialize_unfilled();
+            match guard.try_io(|inner| inner.get_mut().read(unfilled)) {
+                Ok(Ok(bytes_read)) => {
+                    buf.advance(bytes_read);
+                    return Poll::Ready(Ok(()));
+                },
+                Ok(Err(e)) => return Poll::Ready(Err(e)),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+}
+impl Debug for UnnamedPipeReader {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnnamedPipeReader")
+            .field("file_descriptor", &self.as_raw_fd())
+            .finish()
+    }
+}
+impl AsRawFd for UnnamedPipeReader {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+/// A Tokio-based handle to the writing end of an unnamed pipe, created by the [`pipe`] function together with the [reading end].
+///
+/// [`pipe`]: fn.pipe.html " "
+/// [reading end]: struct.UnnamedPipeReader.html " "
+pub struct UnnamedPipeWriter {
+    inner: AsyncFd<sync::UnnamedPipeWriter>,
+}
+impl UnnamedPipeWriter {
+    /// Registers the writing end of a blocking unnamed pipe with the Tokio runtime, putting it into non-blocking mode.
+    ///
+    /// # Panics
+    /// Panics if called outside of the context of a Tokio runtime with I/O enabled.
+    #[inline]
+    pub fn from_sync(sync: sync::UnnamedPipeWriter) -> io::Result<Self> {
+        Ok(Self {inner: register(sync)?})
+    }
+    /// Unregisters the writing end from the Tokio runtime and puts it back into blocking mode.
+    #[inline]
+    pub fn into_sync(self) -> io::Result<sync::UnnamedPipeWriter> {
+        unregister(self.inner)
+    }
+}
+impl AsyncWrite for UnnamedPipeWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        loop {
+            let mut guard = match self.inner.poll_write_ready_mut(cx) {
+                Poll::Ready(Ok(guard)) => guard,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+            if let Ok(result) = guard.try_io(|inner| inner.get_mut().write(buf)) {
+                return Poll::Ready(result);
+            }
+        }
+    }
+    #[inline(always)]
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // Pipes have no userspace buffering to flush.
+        Poll::Ready(Ok(()))
+    }
+    #[inline(always)]
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // The only way to shut down a pipe is to close it, which happens on drop.
+        Poll::Ready(Ok(()))
+    }
+}
+impl Debug for UnnamedPipeWriter {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnnamedPipeWriter")
+            .field("file_descriptor", &self.as_raw_fd())
+            .finish()
+    }
+}
+impl AsRawFd for UnnamedPipeWriter {
+    #[inline(always)]
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Read, Write};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use super::*;
+    use crate::os::unix::get_nonblocking;
+
+    #[tokio::test]
+    async fn conversion() {
+        let (mut writer, mut reader) = pipe().unwrap();
+        writer.write_all(b"async").await.unwrap();
+        let mut buffer = [0; 5];
+        reader.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, b"async");
+
+        let mut writer = writer.into_sync().unwrap();
+        let mut reader = reader.into_sync().unwrap();
+        assert!(!get_nonblocking(writer.as_raw_fd()).unwrap());
+        assert!(!get_nonblocking(reader.as_raw_fd()).unwrap());
+        writer.write_all(b"sync!").unwrap();
+        reader.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"sync!");
+
+        let mut writer = UnnamedPipeWriter::from_sync(writer).unwrap();
+        let mut reader = UnnamedPipeReader::from_sync(reader).unwrap();
+        writer.write_all(b"again").await.unwrap();
+        reader.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(&buffer, b"again");
+    }
+}